@@ -0,0 +1,95 @@
+//! Compares [`FrcStructureBytes::pack_many`] on trivially copyable structures,
+//! which are copied straight from memory, against the same fields packed one at a time
+
+use std::io::Cursor;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use frclib_core::structure::{FrcStructure, FrcStructureBytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+#[repr(C)]
+struct Translation2d {
+    x: f64,
+    y: f64,
+}
+
+/// The same layout without `repr(C)`, so it isn't trivially copyable
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+struct FieldTranslation2d {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+#[repr(C)]
+struct SwerveModuleState {
+    speed: f64,
+    angle: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+struct FieldSwerveModuleState {
+    speed: f64,
+    angle: f64,
+}
+
+const COUNT: usize = 1000;
+
+fn translations(c: &mut Criterion) {
+    assert!(
+        Translation2d::TRIVIALLY_COPYABLE.get() && !FieldTranslation2d::TRIVIALLY_COPYABLE.get(),
+        "only the repr(C) translation should take the bulk path"
+    );
+    let bulk = (0..COUNT)
+        .map(|index| Translation2d {
+            x: index as f64,
+            y: -(index as f64),
+        })
+        .collect::<Vec<_>>();
+    let fields = bulk
+        .iter()
+        .map(|translation| FieldTranslation2d {
+            x: translation.x,
+            y: translation.y,
+        })
+        .collect::<Vec<_>>();
+    let mut group = c.benchmark_group("bulk_pack/translation2d");
+    let _ = group.bench_function("fields", |b| {
+        b.iter(|| black_box(FrcStructureBytes::pack_many(black_box(&fields))));
+    });
+    let _ = group.bench_function("bulk", |b| {
+        b.iter(|| black_box(FrcStructureBytes::pack_many(black_box(&bulk))));
+    });
+    group.finish();
+}
+
+fn swerve_states(c: &mut Criterion) {
+    let bulk = (0..COUNT)
+        .map(|index| {
+            [0.0, 0.25, 0.5, 0.75].map(|offset| SwerveModuleState {
+                speed: index as f64 * 0.01,
+                angle: offset,
+            })
+        })
+        .collect::<Vec<_>>();
+    let fields = bulk
+        .iter()
+        .map(|modules| {
+            modules.map(|module| FieldSwerveModuleState {
+                speed: module.speed,
+                angle: module.angle,
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut group = c.benchmark_group("bulk_pack/swerve_states");
+    let _ = group.bench_function("fields", |b| {
+        b.iter(|| black_box(FrcStructureBytes::pack_many(black_box(&fields))));
+    });
+    let _ = group.bench_function("bulk", |b| {
+        b.iter(|| black_box(FrcStructureBytes::pack_many(black_box(&bulk))));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, translations, swerve_states);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Compares packing a frame with a growing buffer against [`FrcStructureBytes::pack_many`]
+
+use std::io::Cursor;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use frclib_core::structure::{FrcStructure, FrcStructureBytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+struct Telemetry {
+    timestamp: u64,
+    voltage: f32,
+    current: f32,
+    temperature: f32,
+    faults: u16,
+    enabled: bool,
+}
+
+const COUNT: usize = 10_000;
+
+fn telemetry() -> Vec<Telemetry> {
+    (0..COUNT)
+        .map(|index| Telemetry {
+            timestamp: index as u64,
+            voltage: 12.5,
+            current: index as f32 * 0.1,
+            temperature: 40.0,
+            faults: (index % 16) as u16,
+            enabled: index % 2 == 0,
+        })
+        .collect()
+}
+
+fn naive(items: &[Telemetry]) -> FrcStructureBytes {
+    let mut buffer = Vec::new();
+    for item in items {
+        item.pack(&mut buffer);
+    }
+    FrcStructureBytes::from_parts(
+        &Telemetry::DESCRIPTION,
+        items.len(),
+        buffer.into_boxed_slice(),
+    )
+}
+
+fn pack_many(c: &mut Criterion) {
+    let items = telemetry();
+    let mut group = c.benchmark_group("pack_many");
+    let _ = group.bench_function("naive", |b| {
+        b.iter(|| black_box(naive(black_box(&items))));
+    });
+    let _ = group.bench_function("reserved", |b| {
+        b.iter(|| black_box(FrcStructureBytes::pack_many(black_box(&items))));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, pack_many);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+//! Compares [`FrcStructureBytes::to_vec`] against [`FrcStructureBytes::par_to_vec`] on a large frame
+
+use std::io::Cursor;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use frclib_core::structure::{FrcStructure, FrcStructureBytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+struct Point {
+    x: f32,
+    y: f32,
+    z: f32,
+    intensity: u16,
+}
+
+const COUNT: usize = 50_000;
+
+fn point_cloud() -> FrcStructureBytes {
+    let points = (0..COUNT)
+        .map(|index| Point {
+            x: index as f32,
+            y: -(index as f32),
+            z: 0.5,
+            intensity: (index % 1024) as u16,
+        })
+        .collect::<Vec<_>>();
+    FrcStructureBytes::pack_many(&points)
+}
+
+fn to_vec(c: &mut Criterion) {
+    let bytes = point_cloud();
+    let mut group = c.benchmark_group("to_vec");
+    let _ = group.bench_function("serial", |b| {
+        b.iter(|| black_box(bytes.to_vec::<Point>()));
+    });
+    let _ = group.bench_function("parallel", |b| {
+        b.iter(|| black_box(bytes.par_to_vec::<Point>()));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, to_vec);
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+//! Compares unpacking a small structure through a [`Cursor`] against [`FrcStructure::unpack_array`]
+
+use std::io::Cursor;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use frclib_core::structure::FrcStructure;
+
+#[derive(Debug, Clone, Copy, PartialEq, FrcStructure)]
+struct Sample {
+    timestamp: u64,
+    position: [f32; 3],
+    velocity: f64,
+    id: u16,
+    enabled: bool,
+}
+
+const COUNT: usize = 1024;
+
+fn packed_samples() -> Vec<[u8; Sample::SIZE]> {
+    (0..COUNT)
+        .map(|index| {
+            Sample {
+                timestamp: index as u64,
+                position: [index as f32, 1.0, -1.0],
+                velocity: 0.5,
+                id: 7,
+                enabled: index % 2 == 0,
+            }
+            .pack_fixed()
+        })
+        .collect()
+}
+
+fn unpack(c: &mut Criterion) {
+    let samples = packed_samples();
+    let mut group = c.benchmark_group("unpack");
+    let _ = group.bench_function("cursor", |b| {
+        b.iter(|| {
+            for bytes in &samples {
+                let _ = black_box(Sample::unpack(&mut Cursor::new(black_box(&bytes[..]))));
+            }
+        });
+    });
+    let _ = group.bench_function("array", |b| {
+        b.iter(|| {
+            for bytes in &samples {
+                let _ = black_box(Sample::unpack_array(black_box(bytes)));
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, unpack);
+criterion_main!(benches);
@@ -1,10 +1,64 @@
 use proc_macro::TokenStream;
 use proc_macro2::{TokenStream as TokenStream2, TokenTree};
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 use syn::{Attribute, DeriveInput, Fields, Ident, Meta, MetaList, QSelf, Token, Variant};
 
 /// Derive macro generating an impl of the trait `FrcStructure`.
-#[proc_macro_derive(FrcStructure)]
+///
+/// Named and tuple structs are supported, tuple struct fields are named `_0`, `_1`, ... in the schema.
+/// Struct fields can be primitives, other `FrcStructure` types
+/// or fixed-size arrays of either, arrays are written as `type name[N]` in the schema.
+/// `Option` fields are written as a `bool name_present` flag followed by the value,
+/// this is an frclib extension that other `WPIlib` struct decoders will see as two fields.
+///
+/// A `NameView<'a>` type is generated next to the structure,
+/// it wraps packed bytes and has a method per field that reads only that field.
+///
+/// Structs can have type parameters, each one is required to implement `FrcStructure`.
+/// The `TYPE` of a generic structure is its name joined with the `TYPE` of each parameter,
+/// `Bounded<f64>` is `Bounded_float64`.
+/// Arrays and strings append their length and options are prefixed with `Option`,
+/// so `Bounded<[f64; 2]>` is `Bounded_float64x2` and `Bounded<Option<f64>>` is `Bounded_Option_float64`.
+/// Every instantiation needs its own description so generic structures aren't registered
+/// at link time, register the ones used with `FrcStructDescDB::register`.
+/// Lifetime and const parameters are not supported.
+///
+/// Fields accept the following attributes:
+/// - `#[frc(skip)]` leaves the field out of the schema and the packed bytes,
+///   it is filled with [`Default::default`] when unpacking.
+/// - `#[frc(rename = "name")]` changes the name of the field in the schema,
+///   the packed bytes are unaffected.
+/// - `#[frc(bits)]` packs a `bool` field into a single bit, consecutive `bits` fields
+///   share a byte, up to 8 per byte with the first field in the lowest bit.
+///   They are written as `bool name:1` bit-fields in the schema.
+///   Without it every `bool` takes a whole byte.
+///
+/// Structs accept `#[frc(assert_size = N)]`, which fails to compile if the packed size isn't `N` bytes.
+/// It guards a layout shared with another language against fields being added or changed by accident,
+/// generic structs don't support it as their size depends on the parameters.
+///
+/// Structs also accept `#[frc(endian = "big")]` for devices that speak big-endian struct data,
+/// `pack`, `unpack` and the other methods without an explicit byte order
+/// then write every multi-byte field most significant byte first.
+/// The `_with_endian` methods still use the order they are given and the schema is unaffected.
+/// [`FrcStructureBytes`](frclib_core::structure::FrcStructureBytes), values and structures
+/// containing a big-endian one keep its bytes in little-endian so they match the schema.
+/// The default is `"little"`, the order of the `WPIlib` struct spec.
+///
+/// Fieldless enums with a `#[repr(iN)]` or `#[repr(uN)]` are packed as that integer,
+/// their schema is `enum {A=0, B=-1, ..} int8 variant` so the variant names travel with it.
+/// Discriminants follow the rust rules, a variant without one is one more than the variant before it.
+/// `try_unpack` rejects values that aren't a variant, `unpack` falls back to [`Default::default`].
+///
+/// A structure containing itself, directly, inside an array or in the generic arguments of a field type,
+/// is a compile error. The name is matched as `Name`, `self::Name` or `Self`,
+/// so a field of another module's type with the same name, like `other::Name`, is allowed.
+/// Cycles through other types can't be seen by the derive,
+/// they are rejected by rustc as infinitely sized types,
+/// and a hand-written schema that loops is caught at runtime by the schema parser.
+#[proc_macro_derive(FrcStructure, attributes(frc))]
 pub fn frc_structure(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(input).unwrap();
     let name = &ast.ident;
@@ -12,7 +66,40 @@ pub fn frc_structure(input: TokenStream) -> TokenStream {
     let attr_tokens = get_frcstructre_attr(&ast.attrs);
 
     match &ast.data {
-        syn::Data::Struct(syn::DataStruct { fields, .. }) => impl_frc_struct(name, fields).into(),
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            if let Some(field) = fields.iter().find(|field| references_type(&field.ty, name)) {
+                return syn::Error::new_spanned(
+                    &field.ty,
+                    format!(
+                        "`{}` cannot contain itself as a field, FrcStructure types must have a known size",
+                        name
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+            if let Some(field) = fields.iter().find(|field| is_nested_array(&field.ty)) {
+                return syn::Error::new_spanned(
+                    &field.ty,
+                    "multidimensional arrays cannot be described by a struct schema",
+                )
+                .to_compile_error()
+                .into();
+            }
+            StructAttrs::parse(&ast.attrs)
+                .and_then(|attrs| {
+                    impl_frc_struct(
+                        name,
+                        &ast.vis,
+                        &ast.generics,
+                        fields,
+                        is_repr_c(&ast.attrs),
+                        attrs,
+                    )
+                })
+                .unwrap_or_else(syn::Error::into_compile_error)
+                .into()
+        }
         syn::Data::Enum(syn::DataEnum { variants, .. }) => {
             let repr = ast
                 .attrs
@@ -33,12 +120,94 @@ pub fn frc_structure(input: TokenStream) -> TokenStream {
                 }
             });
             let variants = variants.iter().cloned().collect::<Vec<_>>();
-            impl_frc_enum(name, variants, repr, allow_fields).into()
+            impl_frc_enum(name, variants, repr, allow_fields)
+                .unwrap_or_else(syn::Error::into_compile_error)
+                .into()
         }
         _ => panic!("Only known size structs and c-style enums are supported"),
     }
 }
 
+/// Derive macro generating an impl of the trait `FrcDynStructure`.
+///
+/// Named and tuple structs are supported, every field has to implement `FrcDynStructure`,
+/// which covers every `FrcStructure` and `Vec`s of them.
+/// Fields are packed in declaration order, tuple struct fields are named `_0`, `_1`, ... in the schema.
+#[proc_macro_derive(FrcDynStructure)]
+pub fn frc_dyn_structure(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(input).unwrap();
+    impl_frc_dyn_struct(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn impl_frc_dyn_struct(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &ast.ident;
+    let syn::Data::Struct(syn::DataStruct { fields, .. }) = &ast.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "FrcDynStructure can only be derived for structs",
+        ));
+    };
+    if let Some(param) = ast.generics.params.first() {
+        return Err(syn::Error::new_spanned(
+            param,
+            "generic parameters are not supported on FrcDynStructure types",
+        ));
+    }
+
+    let mut schema_fields = Vec::new();
+    let mut packs = Vec::new();
+    let mut unpacks = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let (member, schema_name) = match &field.ident {
+            Some(ident) => (syn::Member::Named(ident.clone()), ident.unraw().to_string()),
+            None => (
+                syn::Member::Unnamed(syn::Index {
+                    index: index as u32,
+                    span: field.ty.span(),
+                }),
+                format!("_{}", index),
+            ),
+        };
+        let field_type = &field.ty;
+        schema_fields.push(quote! {
+            <#field_type as frclib_core::structure::FrcDynStructure>::format_dyn_field(#schema_name)
+        });
+        packs.push(quote! {
+            <#field_type as frclib_core::structure::FrcDynStructure>::pack_dyn(&self.#member, buffer);
+        });
+        // struct expressions evaluate their fields in the order they are written
+        unpacks.push(quote! {
+            #member: <#field_type as frclib_core::structure::FrcDynStructure>::try_unpack_dyn(buffer)?
+        });
+    }
+    let type_str = name.to_string();
+
+    Ok(quote! {
+        impl frclib_core::structure::FrcDynStructure for #name {
+            fn dyn_schema() -> String {
+                let fields: &[String] = &[#(#schema_fields),*];
+                fields.join("; ")
+            }
+
+            fn format_dyn_field(field: &str) -> String {
+                format!("{} {}", #type_str, field)
+            }
+
+            fn pack_dyn(&self, buffer: &mut Vec<u8>) {
+                #(#packs)*
+            }
+
+            fn try_unpack_dyn(
+                buffer: &mut frclib_core::structure::Cursor<&[u8]>,
+            ) -> Result<Self, frclib_core::structure::StructUnpackError> {
+                Ok(Self { #(#unpacks),* })
+            }
+        }
+    })
+}
+
 fn get_frcstructre_attr(attr: &[Attribute]) -> TokenStream2 {
     let frc_attrs: Vec<MetaList> = attr
         .iter()
@@ -61,6 +230,140 @@ fn get_frcstructre_attr(attr: &[Attribute]) -> TokenStream2 {
     out
 }
 
+/// The parsed `#[frc(...)]` attributes of a struct
+#[derive(Default)]
+struct StructAttrs {
+    assert_size: Option<syn::LitInt>,
+    big_endian: bool,
+}
+
+impl StructAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("frc")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("assert_size") {
+                    let size: syn::LitInt = meta.value()?.parse()?;
+                    out.assert_size = Some(size);
+                    Ok(())
+                } else if meta.path.is_ident("endian") {
+                    let endian: syn::LitStr = meta.value()?.parse()?;
+                    out.big_endian = match endian.value().as_str() {
+                        "big" => true,
+                        "little" => false,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                endian,
+                                "expected `endian = \"big\"` or `endian = \"little\"`",
+                            ))
+                        }
+                    };
+                    Ok(())
+                } else {
+                    Err(meta
+                        .error("unknown frc struct attribute, expected `assert_size` or `endian`"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+/// The parsed `#[frc(...)]` attributes of a struct field
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    rename: Option<syn::LitStr>,
+    bits: bool,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("frc")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    out.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let rename: syn::LitStr = meta.value()?.parse()?;
+                    if !is_schema_ident(&rename.value()) {
+                        return Err(syn::Error::new_spanned(
+                            &rename,
+                            "renamed fields must be valid schema identifiers",
+                        ));
+                    }
+                    out.rename = Some(rename);
+                    Ok(())
+                } else if meta.path.is_ident("bits") {
+                    out.bits = true;
+                    Ok(())
+                } else {
+                    Err(meta
+                        .error("unknown frc field attribute, expected `skip`, `rename` or `bits`"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+/// returns true if `name` can be used as a field name in a schema
+fn is_schema_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "enum"
+}
+
+/// returns true if `typ` is `name` or `Self`, or an array or generic of them
+fn references_type(typ: &syn::Type, name: &Ident) -> bool {
+    match typ {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            //only paths that resolve to this module can name the structure,
+            //`other::Node` is a different type even if the structure is called `Node`
+            let segments = path
+                .segments
+                .iter()
+                .map(|segment| &segment.ident)
+                .collect::<Vec<_>>();
+            let is_self = match segments.as_slice() {
+                [ident] => *ident == name || *ident == "Self",
+                [module, ident] => {
+                    path.leading_colon.is_none() && *module == "self" && *ident == name
+                }
+                _ => false,
+            };
+            is_self || path.segments.iter().any(|segment| {
+                matches!(
+                    &segment.arguments,
+                    syn::PathArguments::AngleBracketed(args) if args.args.iter().any(|arg| {
+                        matches!(arg, syn::GenericArgument::Type(typ) if references_type(typ, name))
+                    })
+                )
+            })
+        }
+        syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. }) => references_type(elem, name),
+        _ => false,
+    }
+}
+
+/// returns true if `typ` is an array of arrays
+fn is_nested_array(typ: &syn::Type) -> bool {
+    match typ {
+        syn::Type::Array(syn::TypeArray { elem, .. }) => {
+            matches!(elem.as_ref(), syn::Type::Array(_)) || is_nested_array(elem)
+        }
+        syn::Type::Group(syn::TypeGroup { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. }) => is_nested_array(elem),
+        _ => false,
+    }
+}
+
 /// rerurns `<typ as FrcStructure>`
 fn type_as_frcstructure(typ: &syn::Type) -> syn::Type {
     let path =
@@ -78,113 +381,518 @@ fn type_as_frcstructure(typ: &syn::Type) -> syn::Type {
     })
 }
 
-fn impl_frc_struct(name: &Ident, fields: &Fields) -> TokenStream2 {
+/// Whether the struct is `#[repr(C)]` or `#[repr(transparent)]`,
+/// these are the only layouts where the fields are in memory in declaration order
+fn is_repr_c(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .any(|attr| {
+            let mut found = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= meta.path.is_ident("C") || meta.path.is_ident("transparent");
+                Ok(())
+            });
+            found
+        })
+}
+
+/// A field of the struct as it is laid out in the packed bytes
+enum Slot {
+    /// A field packed with its own `FrcStructure` impl
+    Field {
+        /// `<T as FrcStructure>`
+        typ: Box<syn::Type>,
+        /// `T`, the type returned by the view
+        view_type: Box<syn::Type>,
+        name: syn::Member,
+        schema_name: syn::LitStr,
+        accessor: Ident,
+    },
+    /// Up to 8 consecutive `#[frc(bits)]` fields packed into a byte, lowest bit first
+    Bits(Vec<(syn::Member, syn::LitStr, Ident)>),
+}
+
+/// returns true if `typ` is `bool`
+fn is_bool(typ: &syn::Type) -> bool {
+    matches!(typ, syn::Type::Path(syn::TypePath { qself: None, path }) if path.is_ident("bool"))
+}
+
+fn impl_frc_struct(
+    name: &Ident,
+    vis: &syn::Visibility,
+    generics: &syn::Generics,
+    fields: &Fields,
+    repr_c: bool,
+    attrs: StructAttrs,
+) -> syn::Result<TokenStream2> {
+    // type parameters have to be structures themselves,
+    // lifetimes and const parameters can't be told apart by the `TYPE`
+    if let Some(param) = generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, syn::GenericParam::Type(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            param,
+            "only type parameters are supported on FrcStructure types",
+        ));
+    }
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    // the free const checking the size can't name type parameters either
+    let size_assert = match attrs.assert_size {
+        Some(size) if !type_params.is_empty() => {
+            return Err(syn::Error::new_spanned(
+                size,
+                "`assert_size` is not supported on generic structures",
+            ));
+        }
+        Some(size) => {
+            let expected = size.base10_parse::<usize>()?;
+            let message = format!(
+                "the packed size of `{}` is not the {} bytes given by `#[frc(assert_size)]`",
+                name, size
+            );
+            quote_spanned! {size.span()=>
+                const _: () = assert!(<#name as FrcStructure>::SIZE == #expected, #message);
+            }
+        }
+        None => quote!(),
+    };
+    let mut generics = generics.clone();
+    for param in type_params.iter() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#param: FrcStructure));
+    }
+
     // every supported field type implements `FrcStructure`
-    // so we can use it to generate the schema, size, pack, and unpack functions
-    let mut field_types: Vec<syn::Type> = Vec::new();
-    let mut field_strs: Vec<syn::LitStr> = Vec::new();
-    let mut field_names: Vec<syn::Ident> = Vec::new();
+    // so we can use it to generate the schema, size, pack, and unpack functions,
+    // only `#[frc(bits)]` fields are packed by the derive itself
+    let mut slots: Vec<Slot> = Vec::new();
+    // skipped fields are only rebuilt from their default when unpacking
+    let mut skipped_names: Vec<syn::Member> = Vec::new();
+    let mut skipped_asserts: Vec<TokenStream2> = Vec::new();
 
     match fields {
-        syn::Fields::Named(syn::FieldsNamed { named, .. }) => {
-            for field in named {
-                let field_name = field
-                    .ident
-                    .as_ref()
-                    .expect("Only named fields are supported");
+        syn::Fields::Named(_) | syn::Fields::Unnamed(_) => {
+            for (index, field) in fields.iter().enumerate() {
+                // tuple struct fields are named `_0`, `_1`, ... in the schema
+                let (field_name, schema_name) = match &field.ident {
+                    Some(ident) => (syn::Member::Named(ident.clone()), ident.unraw().to_string()),
+                    None => (
+                        syn::Member::Unnamed(syn::Index {
+                            index: index as u32,
+                            span: field.ty.span(),
+                        }),
+                        format!("_{}", index),
+                    ),
+                };
                 let field_type = &field.ty;
-                field_names.push(field_name.clone());
-                field_strs.push(syn::LitStr::new(
-                    field_name.to_string().as_str(),
-                    field_name.span(),
-                ));
-                field_types.push(field_type.clone());
+                let attrs = FieldAttrs::parse(&field.attrs)?;
+                if attrs.skip {
+                    skipped_names.push(field_name);
+                    // a free const can't name the type parameters so generic structures
+                    // require `Default` in their where clause instead
+                    if !type_params.is_empty() {
+                        generics
+                            .make_where_clause()
+                            .predicates
+                            .push(syn::parse_quote!(#field_type: Default));
+                        continue;
+                    }
+                    skipped_asserts.push(quote_spanned! {field_type.span()=>
+                        const _: fn() = || {
+                            fn skipped_field_must_be_default<T: Default>() {}
+                            skipped_field_must_be_default::<#field_type>();
+                        };
+                    });
+                    continue;
+                }
+                let accessor = match &field_name {
+                    syn::Member::Named(ident) => ident.clone(),
+                    syn::Member::Unnamed(_) => format_ident!("{}", schema_name),
+                };
+                let schema_name = attrs
+                    .rename
+                    .unwrap_or_else(|| syn::LitStr::new(&schema_name, field_type.span()));
+                if attrs.bits {
+                    if !is_bool(field_type) {
+                        return Err(syn::Error::new_spanned(
+                            field_type,
+                            "`#[frc(bits)]` can only be used on `bool` fields",
+                        ));
+                    }
+                    match slots.last_mut() {
+                        Some(Slot::Bits(bits)) if bits.len() < 8 => {
+                            bits.push((field_name, schema_name, accessor))
+                        }
+                        _ => slots.push(Slot::Bits(vec![(field_name, schema_name, accessor)])),
+                    }
+                    continue;
+                }
+                slots.push(Slot::Field {
+                    typ: Box::new(type_as_frcstructure(field_type)),
+                    view_type: Box::new(field_type.clone()),
+                    name: field_name,
+                    schema_name,
+                    accessor,
+                });
             }
         }
-        _ => panic!("Unit structs are not supported"),
+        syn::Fields::Unit => panic!("Unit structs are not supported"),
     };
-    field_types = field_types.iter().map(type_as_frcstructure).collect();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let field_types = slots
+        .iter()
+        .filter_map(|slot| match slot {
+            Slot::Field { typ, .. } => Some(typ),
+            Slot::Bits(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let has_bits = slots.iter().any(|slot| matches!(slot, Slot::Bits(_)));
 
-    //generate schema
+    //generate schema, nested structures are referenced by their type name
+    //and bits are written as `bool name:1` bit-fields
     let schema = {
-        let schema_template = "{}".repeat(field_types.len());
-        let format_exprs = {
-            field_types
-                .iter()
-                .zip(field_strs.iter())
-                .map(|(typ, name)| {
-                    format!(
-                        "{}::format_field({})",
-                        typ.into_token_stream(),
-                        name.into_token_stream()
-                    )
-                })
-                .collect::<Vec<_>>()
-        };
-        syn::parse_str::<syn::Expr>(&format!(
-            "format!(\"{}\", {})",
-            schema_template,
-            format_exprs.join(", ")
-        ))
-        .expect("Failed to parse format! call")
+        let count = slots.len();
+        let parts = slots.iter().map(|slot| match slot {
+            Slot::Field {
+                typ, schema_name, ..
+            } => quote!(#typ::format_field(#schema_name)),
+            Slot::Bits(bits) => {
+                let declarations = bits
+                    .iter()
+                    .map(|(_, schema_name, _)| format!("bool {}:1", schema_name.value()))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                quote!(String::from(#declarations))
+            }
+        });
+        quote!({
+            let parts: [String; #count] = [#(#parts),*];
+            parts.join("; ")
+        })
     };
 
     //generate size
     let size = {
-        let mut size = syn::parse_str::<syn::Expr>("0usize").expect("Failed to parse 0usize");
-        for typ in field_types.iter() {
-            let size_expr =
-                syn::parse_str::<syn::Expr>(format!("{}::SIZE", typ.into_token_stream()).as_str())
-                    .expect("Failed to parse size");
-            size = syn::parse_str::<syn::Expr>(
-                format!(
-                    "{} + {}",
-                    size.into_token_stream(),
-                    size_expr.into_token_stream()
-                )
-                .as_str(),
-            )
-            .expect("Failed to parse size addition");
+        let sizes = slots.iter().map(|slot| match slot {
+            Slot::Field { typ, .. } => quote!(#typ::SIZE),
+            Slot::Bits(_) => quote!(1usize),
+        });
+        quote!(0usize #(+ #sizes)*)
+    };
+
+    let endian = if attrs.big_endian {
+        quote!(
+            const ENDIAN: frclib_core::structure::Endianness =
+                frclib_core::structure::Endianness::Big;
+        )
+    } else {
+        quote!()
+    };
+
+    //generate trivially copyable, the memory has to be the packed bytes in schema order
+    let trivially_copyable = {
+        let copyable = repr_c && skipped_names.is_empty() && !has_bits && !attrs.big_endian;
+        quote! {
+            if #copyable
+                #(&& #field_types::TRIVIALLY_COPYABLE.get())*
+                && core::mem::size_of::<Self>() == <Self as FrcStructure>::SIZE
+            {
+                // SAFETY: a `repr(C)` struct lays its fields out in declaration order, which is
+                // the schema order, every field is laid out like its packed bytes,
+                // and as their sizes add up to the size of the struct there is no padding
+                unsafe { frclib_core::structure::TriviallyCopyable::new_unchecked() }
+            } else {
+                frclib_core::structure::TriviallyCopyable::NO
+            }
         }
-        size
     };
 
-    //generate pack
-    let pack = {
-        let mut pack = "{".to_string();
-        for (typ, name) in field_types.iter().zip(field_names.iter()) {
-            pack = format!(
-                "{}{}::pack(&self.{}, buffer);",
-                pack,
-                typ.into_token_stream(),
-                name.into_token_stream()
-            );
+    //generate pack and pack_with_endian, bits are a single byte so the order doesn't matter
+    //big-endian structures pack every field with the big-endian order by default
+    let big = quote!(frclib_core::structure::Endianness::Big);
+    let little = quote!(frclib_core::structure::Endianness::Little);
+    //little-endian structures still pack big-endian fields in the spec order,
+    //so the schema describes the whole structure, the check is folded at compile time
+    let in_spec_order = |typ: &syn::Type, plain: TokenStream2, with_little: TokenStream2| {
+        quote! {
+            if #typ::ENDIAN == #little {
+                #plain
+            } else {
+                #with_little
+            }
+        }
+    };
+    let (pack, pack_with_endian) = {
+        let pack_bits = |bits: &Vec<(syn::Member, syn::LitStr, Ident)>| {
+            let names = bits.iter().map(|(name, _, _)| name);
+            quote! {
+                buffer.push(frclib_core::structure::pack_bits(&[#(self.#names),*]));
+            }
+        };
+        let mut pack = Vec::new();
+        let mut pack_with_endian = Vec::new();
+        for slot in slots.iter() {
+            match slot {
+                Slot::Field { typ, name, .. } if attrs.big_endian => {
+                    pack.push(quote!(#typ::pack_with_endian(&self.#name, buffer, #big);));
+                    pack_with_endian
+                        .push(quote!(#typ::pack_with_endian(&self.#name, buffer, endian);));
+                }
+                Slot::Field { typ, name, .. } => {
+                    pack.push(in_spec_order(
+                        typ,
+                        quote!(#typ::pack(&self.#name, buffer);),
+                        quote!(#typ::pack_with_endian(&self.#name, buffer, #little);),
+                    ));
+                    pack_with_endian
+                        .push(quote!(#typ::pack_with_endian(&self.#name, buffer, endian);));
+                }
+                Slot::Bits(bits) => {
+                    pack.push(pack_bits(bits));
+                    pack_with_endian.push(pack_bits(bits));
+                }
+            }
         }
-        pack = format!("{}}}", pack);
-        syn::parse_str::<syn::Stmt>(pack.as_str()).expect("Failed to parse pack statement")
+        (quote!(#(#pack)*), quote!(#(#pack_with_endian)*))
     };
 
-    //generate unpack
-    let unpack = {
-        let mut unpack = "Self {".to_string();
-        for (typ, name) in field_types.iter().zip(field_names.iter()) {
-            unpack = format!(
-                "{}{}: {}::unpack(buffer),",
-                unpack,
-                name.into_token_stream(),
-                typ.into_token_stream()
-            );
+    //generate unpack, try_unpack and unpack_with_endian,
+    //each slot is read in order into a local before the struct is built
+    //unpack_slice reads each slot at its offset instead of advancing a cursor
+    let unpack_with = |read: &dyn Fn(&syn::Type, &TokenStream2) -> TokenStream2| {
+        let byte_type = type_as_frcstructure(&syn::parse_quote!(u8));
+        let mut reads = Vec::new();
+        let mut inits = Vec::new();
+        let mut offset = quote!(0usize);
+        for (index, slot) in slots.iter().enumerate() {
+            let local = format_ident!("frc_slot_{}", index);
+            match slot {
+                Slot::Field { typ, name, .. } => {
+                    let read = read(typ, &offset);
+                    reads.push(quote!(let #local = #read;));
+                    inits.push(quote!(#name: #local));
+                    offset = quote!(#offset + #typ::SIZE);
+                }
+                Slot::Bits(bits) => {
+                    let read = read(&byte_type, &offset);
+                    reads.push(quote!(let #local = #read;));
+                    offset = quote!(#offset + 1usize);
+                    for (bit, (name, _, _)) in bits.iter().enumerate() {
+                        let bit = bit as u8;
+                        inits.push(quote!(#name: frclib_core::structure::unpack_bit(#local, #bit)));
+                    }
+                }
+            }
+        }
+        quote! {
+            #(#reads)*
+            Self {
+                #(#inits,)*
+                #(#skipped_names: Default::default(),)*
+            }
+        }
+    };
+    //checked before reading any field so a short buffer reports the whole structure
+    //and the cursor isn't left part way through it
+    let check_remaining = quote! {
+        let available = frclib_core::structure::remaining(buffer);
+        if available < Self::SIZE {
+            return Err(frclib_core::structure::StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
         }
-        unpack = format!("{}}}", unpack);
-        syn::parse_str::<syn::ExprStruct>(unpack.as_str())
-            .expect("Failed to parse unpack expression")
+    };
+    let unpack_with_endian =
+        unpack_with(&|typ, _| quote!(#typ::unpack_with_endian(buffer, endian)));
+    let try_unpack_with_endian =
+        unpack_with(&|typ, _| quote!(#typ::try_unpack_with_endian(buffer, endian)?));
+    let (unpack, try_unpack, unpack_slice) = if attrs.big_endian {
+        (
+            unpack_with(&|typ, _| quote!(#typ::unpack_with_endian(buffer, #big))),
+            unpack_with(&|typ, _| quote!(#typ::try_unpack_with_endian(buffer, #big)?)),
+            unpack_with(&|typ, offset| {
+                quote!(#typ::unpack_with_endian(
+                    &mut Cursor::new(frclib_core::structure::slice_from(bytes, #offset)),
+                    #big,
+                ))
+            }),
+        )
+    } else {
+        (
+            unpack_with(&|typ, _| {
+                in_spec_order(
+                    typ,
+                    quote!(#typ::unpack(buffer)),
+                    quote!(#typ::unpack_with_endian(buffer, #little)),
+                )
+            }),
+            unpack_with(&|typ, _| {
+                let read = in_spec_order(
+                    typ,
+                    quote!(#typ::try_unpack(buffer)),
+                    quote!(#typ::try_unpack_with_endian(buffer, #little)),
+                );
+                quote!((#read)?)
+            }),
+            unpack_with(&|typ, offset| {
+                in_spec_order(
+                    typ,
+                    quote!(#typ::unpack_slice(frclib_core::structure::slice_from(bytes, #offset))),
+                    quote!(#typ::unpack_with_endian(
+                        &mut Cursor::new(frclib_core::structure::slice_from(bytes, #offset)),
+                        #little,
+                    )),
+                )
+            }),
+        )
     };
 
-    quote! {
-        impl FrcStructure for #name {
+    //generate a view that reads fields from packed bytes without unpacking the rest
+    let view = {
+        let view_name = format_ident!("{}View", name);
+        let view_doc = format!(
+            "A view of a packed [`{}`] that reads single fields in place",
+            name
+        );
+        let mut offset = quote!(0usize);
+        let mut accessors = Vec::new();
+        for slot in slots.iter() {
+            match slot {
+                Slot::Field {
+                    typ,
+                    view_type,
+                    accessor,
+                    ..
+                } => {
+                    let accessor_doc = format!("Reads the `{}` field", accessor);
+                    let read = if attrs.big_endian {
+                        quote!(#typ::unpack_with_endian(&mut Cursor::new(&self.bytes[offset..]), #big))
+                    } else {
+                        in_spec_order(
+                            typ,
+                            quote!(#typ::unpack(&mut Cursor::new(&self.bytes[offset..]))),
+                            quote!(#typ::unpack_with_endian(&mut Cursor::new(&self.bytes[offset..]), #little)),
+                        )
+                    };
+                    accessors.push(quote! {
+                        #[doc = #accessor_doc]
+                        #[must_use]
+                        #vis fn #accessor(&self) -> #view_type {
+                            let offset: usize = #offset;
+                            #read
+                        }
+                    });
+                    offset = quote!(#offset + #typ::SIZE);
+                }
+                Slot::Bits(bits) => {
+                    for (bit, (_, _, accessor)) in bits.iter().enumerate() {
+                        let bit = bit as u8;
+                        let accessor_doc = format!("Reads the `{}` field", accessor);
+                        accessors.push(quote! {
+                            #[doc = #accessor_doc]
+                            #[must_use]
+                            #vis fn #accessor(&self) -> bool {
+                                let offset: usize = #offset;
+                                frclib_core::structure::unpack_bit(self.bytes[offset], #bit)
+                            }
+                        });
+                    }
+                    offset = quote!(#offset + 1usize);
+                }
+            }
+        }
+        // generic views carry their type parameters in a marker
+        let mut view_generics = generics.clone();
+        view_generics.params.insert(0, syn::parse_quote!('a));
+        let (view_impl_generics, view_ty_generics, _) = view_generics.split_for_impl();
+        let (marker_field, marker_init) = if type_params.is_empty() {
+            (quote!(), quote!())
+        } else {
+            (
+                quote!(marker: core::marker::PhantomData<fn() -> (#(#type_params,)*)>,),
+                quote!(marker: core::marker::PhantomData,),
+            )
+        };
+        quote! {
+            #[doc = #view_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #[allow(dead_code)]
+            #vis struct #view_name #view_impl_generics #where_clause {
+                bytes: &'a [u8],
+                #marker_field
+            }
+
+            #[allow(dead_code)]
+            impl #view_impl_generics #view_name #view_ty_generics #where_clause {
+                /// Creates a view of packed bytes,
+                /// returns `None` if there are fewer bytes than the structure's size
+                #[must_use]
+                #vis fn new(bytes: &'a [u8]) -> Option<Self> {
+                    if bytes.len() < <#name #ty_generics as FrcStructure>::SIZE {
+                        None
+                    } else {
+                        Some(Self { bytes, #marker_init })
+                    }
+                }
+
+                /// The packed bytes behind the view
+                #[must_use]
+                #vis const fn as_bytes(&self) -> &'a [u8] {
+                    self.bytes
+                }
+
+                #(#accessors)*
+            }
+        }
+    };
+
+    //generate the type name, generic structures append the `GENERIC_NAME` of each parameter
+    //and can't be registered at link time as every instantiation has its own description
+    let (type_name, type_name_helper, registration) = if type_params.is_empty() {
+        (
+            quote!(stringify!(#name)),
+            quote!(),
+            quote!(frclib_core::structure_submit!(#name);),
+        )
+    } else {
+        (
+            quote!(frclib_core::structure::type_name_str(Self::FRC_TYPE_NAME)),
+            quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #[doc(hidden)]
+                    const FRC_TYPE_NAME: &'static [u8; frclib_core::structure::GENERIC_TYPE_LEN] =
+                        &frclib_core::structure::join_type_name(&[
+                            stringify!(#name),
+                            #(<#type_params as FrcStructure>::GENERIC_NAME),*
+                        ]);
+                }
+            },
+            quote!(),
+        )
+    };
+
+    Ok(quote! {
+        #view
+        #(#skipped_asserts)*
+        #size_assert
+        #type_name_helper
+        impl #impl_generics FrcStructure for #name #ty_generics #where_clause {
             const SIZE: usize = #size;
-            const TYPE: &'static str = stringify!(#name);
+            const TYPE: &'static str = #type_name;
             const SCHEMA_SUPPLIER: fn() -> String = || #schema;
+            const TRIVIALLY_COPYABLE: frclib_core::structure::TriviallyCopyable<Self> =
+                #trivially_copyable;
+            #endian
 
             fn pack(&self, buffer: &mut Vec<u8>) {
                 #pack
@@ -193,24 +901,63 @@ fn impl_frc_struct(name: &Ident, fields: &Fields) -> TokenStream2 {
             fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
                 #unpack
             }
-        }
-        frclib_core::structure::inventory::submit! { <#name as FrcStructure>::DESCRIPTION }
-        ///This isnt a generic impl for every struct because of primitive and unit types
-        impl Into<frclib_core::value::FrcValue> for #name {
-            fn into(self) -> frclib_core::value::FrcValue {
-                let mut buffer = Vec::with_capacity(Self::SIZE);
-                self.pack(&mut buffer);
-                frclib_core::value::FrcValue::Struct(
-                    Box::new(
-                        FrcStructureBytes::from_parts(
-                            &Self::DESCRIPTION,
-                            1,
-                            buffer.into_boxed_slice()
-                        )
-                    )
-                )
+
+            #[inline]
+            fn unpack_slice(bytes: &[u8]) -> Self {
+                #unpack_slice
+            }
+
+            fn try_unpack(
+                buffer: &mut Cursor<&[u8]>,
+            ) -> Result<Self, frclib_core::structure::StructUnpackError> {
+                #check_remaining
+                Ok({ #try_unpack })
+            }
+
+            fn pack_with_endian(
+                &self,
+                buffer: &mut Vec<u8>,
+                endian: frclib_core::structure::Endianness,
+            ) {
+                #pack_with_endian
+            }
+
+            fn unpack_with_endian(
+                buffer: &mut Cursor<&[u8]>,
+                endian: frclib_core::structure::Endianness,
+            ) -> Self {
+                #unpack_with_endian
+            }
+
+            fn try_unpack_with_endian(
+                buffer: &mut Cursor<&[u8]>,
+                endian: frclib_core::structure::Endianness,
+            ) -> Result<Self, frclib_core::structure::StructUnpackError> {
+                #check_remaining
+                Ok({ #try_unpack_with_endian })
             }
         }
+        #registration
+        frclib_core::structure_into_value!(#name #ty_generics, impl [#impl_generics] [#where_clause]);
+    })
+}
+
+/// The value of an integer literal discriminant, optionally negated like `-1`,
+/// parsed as an `i128` so every `i64` and `u64` discriminant fits
+fn discriminant_value(discriminant: &syn::Expr) -> Option<i128> {
+    match discriminant {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit_int),
+            ..
+        }) => lit_int.base10_parse::<i128>().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).and_then(i128::checked_neg),
+        syn::Expr::Group(syn::ExprGroup { expr, .. })
+        | syn::Expr::Paren(syn::ExprParen { expr, .. }) => discriminant_value(expr),
+        _ => None,
     }
 }
 
@@ -219,9 +966,10 @@ fn impl_frc_enum(
     variants: Vec<Variant>,
     repr: Ident,
     allow_fields: bool,
-) -> TokenStream2 {
-    let mut enum_variants: Vec<(syn::Ident, syn::LitInt)> = Vec::new();
-    let mut last_value = 0;
+) -> syn::Result<TokenStream2> {
+    let mut enum_variants: Vec<(syn::Ident, i128)> = Vec::new();
+    // the discriminant of the next variant without an explicit one
+    let mut next_value = 0i128;
     for variant in variants {
         let variant_name = variant.ident;
         //if the variant has fields print a warning
@@ -236,24 +984,17 @@ fn impl_frc_enum(
                 );
             }
         }
-        if let Some(variant_value) = variant.discriminant {
-            let variant_value = match variant_value.1 {
-                syn::Expr::Lit(syn::ExprLit {
-                    lit: syn::Lit::Int(lit_int),
-                    ..
-                }) => lit_int,
-                _ => panic!("Enum variants must have integer values"),
-            };
-            last_value = variant_value.base10_parse::<i64>().unwrap();
-            enum_variants.push((variant_name, variant_value));
-        } else {
-            let span = variant_name.span();
-            enum_variants.push((
-                variant_name,
-                syn::LitInt::new(last_value.to_string().as_str(), span),
-            ));
-            last_value += 1;
-        }
+        let value = match variant.discriminant {
+            Some((_, discriminant)) => discriminant_value(&discriminant).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &discriminant,
+                    "enum discriminants must be integer literals, optionally negated like `-1`",
+                )
+            })?,
+            None => next_value,
+        };
+        next_value = value.saturating_add(1);
+        enum_variants.push((variant_name, value));
     }
 
     let schema = {
@@ -288,7 +1029,7 @@ fn impl_frc_enum(
         syn::parse_str::<syn::Item>(impl_str.as_str()).expect("Failed to parse impl")
     };
 
-    quote! {
+    Ok(quote! {
         #from_repr
         impl FrcStructure for #name {
             const SIZE: usize = <#repr as FrcStructure>::SIZE;
@@ -304,23 +1045,55 @@ fn impl_frc_enum(
                 let repr = <#repr as FrcStructure>::unpack(buffer);
                 Self::from_repr(repr).unwrap_or_default()
             }
-        }
-        frclib_core::structure::inventory::submit! { <#name as FrcStructure>::DESCRIPTION }
-        ///This isnt a generic impl for every struct because of primitive and unit types
-        impl Into<frclib_core::value::FrcValue> for #name {
-            fn into(self) -> frclib_core::value::FrcValue {
-                let mut buffer = Vec::with_capacity(Self::SIZE);
-                self.pack(&mut buffer);
-                frclib_core::value::FrcValue::Struct(
-                    Box::new(
-                        FrcStructureBytes::from_parts(
-                            &Self::DESCRIPTION,
-                            1,
-                            buffer.into_boxed_slice()
-                        )
-                    )
-                )
+
+            fn unpack_slice(bytes: &[u8]) -> Self {
+                let repr = <#repr as FrcStructure>::unpack_slice(bytes);
+                Self::from_repr(repr).unwrap_or_default()
+            }
+
+            fn pack_with_endian(
+                &self,
+                buffer: &mut Vec<u8>,
+                endian: frclib_core::structure::Endianness,
+            ) {
+                let repr = *self as #repr;
+                <#repr as FrcStructure>::pack_with_endian(&repr, buffer, endian);
+            }
+
+            fn unpack_with_endian(
+                buffer: &mut Cursor<&[u8]>,
+                endian: frclib_core::structure::Endianness,
+            ) -> Self {
+                let repr = <#repr as FrcStructure>::unpack_with_endian(buffer, endian);
+                Self::from_repr(repr).unwrap_or_default()
+            }
+
+            fn try_unpack(
+                buffer: &mut Cursor<&[u8]>,
+            ) -> Result<Self, frclib_core::structure::StructUnpackError> {
+                let repr = <#repr as FrcStructure>::try_unpack(buffer)?;
+                Self::from_repr(repr).ok_or_else(|| {
+                    frclib_core::structure::StructUnpackError::InvalidDiscriminant {
+                        type_str: Self::TYPE,
+                        discriminant: repr.to_string(),
+                    }
+                })
+            }
+
+            fn try_unpack_with_endian(
+                buffer: &mut Cursor<&[u8]>,
+                endian: frclib_core::structure::Endianness,
+            ) -> Result<Self, frclib_core::structure::StructUnpackError> {
+                let repr = <#repr as FrcStructure>::try_unpack_with_endian(buffer, endian)?;
+                Self::from_repr(repr).ok_or_else(|| {
+                    frclib_core::structure::StructUnpackError::InvalidDiscriminant {
+                        type_str: Self::TYPE,
+                        discriminant: repr.to_string(),
+                    }
+                })
             }
         }
-    }
+        frclib_core::structure_submit!(#name);
+        frclib_core::structure_into_value!(#name);
+    })
 }
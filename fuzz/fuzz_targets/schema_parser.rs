@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|schema: &str| {
+    let _ = frclib_core::structure::parse_schema(schema);
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use frclib_core::structure::{DynamicStructure, FrcStructureBytes};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: FrcStructureBytes| {
+    for chunk in bytes.data.chunks(bytes.desc.size.max(1)) {
+        if let Ok(dynamic) = DynamicStructure::try_new(bytes.desc, chunk.into()) {
+            for field in frclib_core::structure::parse_schema(bytes.desc.schema())
+                .into_iter()
+                .flatten()
+            {
+                let _ = dynamic.get_field(&field.name);
+            }
+        }
+    }
+});
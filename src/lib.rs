@@ -6,6 +6,17 @@
 //! This library goes as far as it can to be panic free,
 //! the only function that can panic is [HAL Initialization](crate::hal::HAL).
 //!
+//! The `std`, `serde`, `structure` and `inventory` features are on by default.
+//! Without `std` the crate is `no_std` + `alloc` and the units and structure layers can be enabled
+//! (`default-features = false, features = ["units", "structure"]`),
+//! units only implement `serde` traits when the `serde` feature is enabled.
+//! Without `std` structures unpack from `structure::Cursor`, a cursor with the methods of `std::io::Cursor`
+//! that works without `std`, derived structures need it and `String`, `Vec`, `ToString` and `format!`
+//! from `alloc` in scope.
+//! Derived structures are only collected at link time with the `inventory` feature,
+//! without it they are added with `FrcStructDescDB::register`.
+//! Values and time still require `std`.
+//!
 // ## Modules
 //
 // ### [Value](crate::value)
@@ -41,6 +52,7 @@
 // - [Torque](crate::units::torque)
 // - [Data](crate::units::data)
 // - [Data Rate](crate::units::data_rate)
+// - [Voltage](crate::units::voltage)
 //
 
 #![deny(clippy::all, clippy::pedantic, clippy::nursery)]
@@ -58,7 +70,6 @@
     unused_lifetimes,
     unused_unsafe,
     useless_ptr_null_checks,
-    cenum_impl_drop_cast,
     while_true,
     unused_features,
     absolute_paths_not_starting_with_crate,
@@ -73,7 +84,6 @@
     clippy::unwrap_used,
     clippy::panicking_unwrap,
     missing_abi,
-    missing_fragment_specifier,
     clippy::missing_safety_doc,
     clippy::missing_asserts_for_indexing,
     clippy::missing_assert_message,
@@ -91,6 +101,9 @@
     )
 )]
 #![cfg_attr(not(test), warn(missing_docs))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 #[cfg(feature = "hal")]
 pub mod hal;
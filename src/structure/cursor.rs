@@ -0,0 +1,80 @@
+use core::fmt;
+
+/// A cursor over an in-memory buffer, used in place of `std::io::Cursor` without `std`.
+///
+/// It only has the parts of `std::io::Cursor` structures use,
+/// so implementations written against one also compile against the other.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Cursor<T> {
+    inner: T,
+    position: u64,
+}
+
+impl<T> Cursor<T> {
+    /// Creates a new cursor at the start of `inner`
+    #[must_use]
+    pub const fn new(inner: T) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Consumes the cursor and returns the underlying buffer
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying buffer
+    #[must_use]
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying buffer
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// The current position of the cursor, which can be past the end of the buffer
+    #[must_use]
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Sets the position of the cursor, which can be past the end of the buffer
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+}
+
+impl<T: AsRef<[u8]>> Cursor<T> {
+    /// Fills `buf` from the current position and advances past the read bytes
+    ///
+    /// # Errors
+    /// Returns [`UnexpectedEof`] if fewer than `buf.len()` bytes remain,
+    /// the cursor is then moved to the end of the buffer like `std::io::Cursor` does
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), UnexpectedEof> {
+        let inner = self.inner.as_ref();
+        let start = usize::try_from(self.position)
+            .unwrap_or(usize::MAX)
+            .min(inner.len());
+        if let Some(bytes) = inner.get(start..start.saturating_add(buf.len())) {
+            buf.copy_from_slice(bytes);
+            self.position = u64::try_from(start + buf.len()).unwrap_or(u64::MAX);
+            Ok(())
+        } else {
+            self.position = u64::try_from(inner.len()).unwrap_or(u64::MAX);
+            Err(UnexpectedEof)
+        }
+    }
+}
+
+/// The error of [`Cursor::read_exact`] when the buffer ends before `buf` is filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnexpectedEof;
+
+impl fmt::Display for UnexpectedEof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to fill whole buffer")
+    }
+}
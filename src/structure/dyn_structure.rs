@@ -0,0 +1,116 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{remaining, Cursor, FrcStructure, StructUnpackError};
+
+/// A structure whose packed size depends on its value, like one holding a [`Vec`].
+///
+/// Variable-length fields are written as a `uint32` element count followed by that many packed elements,
+/// the schema notes them as `uint32 name_len; type name[]`.
+/// Every [`FrcStructure`] is also a `FrcDynStructure` so fixed-size fields can sit next to the dynamic ones.
+///
+/// These structures have no `SIZE`, so they don't fit anywhere a fixed size is assumed:
+/// they can't be stored in [`FrcStructureBytes`](super::FrcStructureBytes),
+/// described by an [`FrcStructDesc`](super::FrcStructDesc) or registered in the
+/// [`FrcStructDescDB`](super::FrcStructDescDB),
+/// and `name[]` is not part of the `WPIlib` struct spec so [`parse_schema`](super::parse_schema)
+/// and other `WPIlib` consumers reject their schema.
+/// Only use them between programs that both know the rust type.
+///
+/// The methods are suffixed with `_dyn` so they don't clash with the [`FrcStructure`] ones
+/// when both traits are in scope.
+///
+/// # Example
+/// ```
+/// use frclib_core::structure::FrcDynStructure;
+/// use std::io::Cursor;
+///
+/// #[derive(Debug, PartialEq, FrcDynStructure)]
+/// struct Path {
+///     id: u8,
+///     points: Vec<f64>,
+/// }
+///
+/// let path = Path { id: 3, points: vec![0.5, 1.0, 2.0] };
+/// let mut buffer = Vec::new();
+/// path.pack_dyn(&mut buffer);
+/// assert_eq!(buffer.len(), 1 + 4 + 3 * 8);
+/// assert_eq!(Path::dyn_schema(), "uint8 id; uint32 points_len; float64 points[]");
+/// assert_eq!(Path::try_unpack_dyn(&mut Cursor::new(&buffer)), Ok(path));
+/// ```
+pub trait FrcDynStructure: Sized {
+    /// The schema of the structure, variable-length fields are written as
+    /// `uint32 name_len; type name[]`
+    fn dyn_schema() -> String;
+
+    /// Formats a field of this type for the schema of a structure containing it
+    fn format_dyn_field(field: &str) -> String;
+
+    /// Packs the structure into a buffer
+    fn pack_dyn(&self, buffer: &mut Vec<u8>);
+
+    /// Unpacks the structure from a buffer
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too short for the lengths it holds
+    /// or contains an invalid enum discriminant
+    fn try_unpack_dyn(buffer: &mut Cursor<&[u8]>) -> Result<Self, StructUnpackError>;
+}
+
+impl<T: FrcStructure> FrcDynStructure for T {
+    fn dyn_schema() -> String {
+        (T::SCHEMA_SUPPLIER)()
+    }
+
+    fn format_dyn_field(field: &str) -> String {
+        T::format_field(field)
+    }
+
+    fn pack_dyn(&self, buffer: &mut Vec<u8>) {
+        self.pack(buffer);
+    }
+
+    fn try_unpack_dyn(buffer: &mut Cursor<&[u8]>) -> Result<Self, StructUnpackError> {
+        T::try_unpack(buffer)
+    }
+}
+
+/// Packs as a little-endian `uint32` length followed by the packed elements,
+/// a vector longer than [`u32::MAX`] only packs its first [`u32::MAX`] elements.
+impl<T: FrcStructure> FrcDynStructure for Vec<T> {
+    fn dyn_schema() -> String {
+        String::new()
+    }
+
+    fn format_dyn_field(field: &str) -> String {
+        format!(
+            "uint32 {field}_len; {}",
+            T::format_field(&format!("{field}[]"))
+        )
+    }
+
+    fn pack_dyn(&self, buffer: &mut Vec<u8>) {
+        let len = u32::try_from(self.len()).unwrap_or(u32::MAX);
+        len.pack(buffer);
+        buffer.reserve(T::SIZE.saturating_mul(self.len()));
+        for item in self.iter().take(len as usize) {
+            item.pack(buffer);
+        }
+    }
+
+    fn try_unpack_dyn(buffer: &mut Cursor<&[u8]>) -> Result<Self, StructUnpackError> {
+        let len = u32::try_unpack(buffer)? as usize;
+        // checked before allocating so a corrupt length can't reserve gigabytes
+        let needed = T::SIZE.saturating_mul(len);
+        let available = remaining(buffer);
+        if available < needed {
+            return Err(StructUnpackError::InsufficientBytes {
+                type_str: T::TYPE,
+                needed,
+                available,
+            });
+        }
+        (0..len).map(|_| T::try_unpack(buffer)).collect()
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use crate::value::FrcValue;
+
+use super::{
+    parse_schema, DynamicStructureError, FrcStructDesc, FrcStructureError, SchemaParseError,
+    StructureFieldTypes,
+};
+
+/// A structure that is only known by its description at runtime,
+/// its primitive fields can be read and written by name.
+///
+/// Field names are the same as the ones produced by [`parse_schema`],
+/// fields with a count of 1 are scalar values and other counts are arrays.
+/// Values are little endian like [`FrcStructure::pack`](super::FrcStructure::pack).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicStructure {
+    desc: &'static FrcStructDesc,
+    buffer: Box<[u8]>,
+    fields: Arc<FieldLayout>,
+}
+
+/// The offset, type and bit-field bit of every primitive field by name
+type FieldLayout = HashMap<String, (usize, StructureFieldTypes, Option<u8>)>;
+
+/// A parsed layout along with the schema and size it was parsed for
+struct CachedLayout {
+    schema: &'static str,
+    size: usize,
+    fields: Arc<FieldLayout>,
+}
+
+/// Parsed layouts by type so structures of the same type only parse the schema once
+fn layout_cache() -> &'static RwLock<HashMap<&'static str, CachedLayout>> {
+    static CACHE: OnceLock<RwLock<HashMap<&'static str, CachedLayout>>> = OnceLock::new();
+    CACHE.get_or_init(RwLock::default)
+}
+
+/// Gets the field layout of a structure, parsing and caching it on first use
+///
+/// A cached layout is only reused if the schema and size of `desc` still match it,
+/// otherwise the schema is parsed again and replaces the cached layout.
+pub(super) fn field_layout(
+    desc: &'static FrcStructDesc,
+) -> Result<Arc<FieldLayout>, SchemaParseError> {
+    let schema = desc.schema();
+    let is_current = |cached: &CachedLayout| cached.schema == schema && cached.size == desc.size;
+    if let Some(cached) = layout_cache()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(desc.type_str)
+        .filter(|cached| is_current(cached))
+    {
+        return Ok(Arc::clone(&cached.fields));
+    }
+
+    let parsed = parse_schema(schema)?;
+    let schema_size = parsed
+        .iter()
+        .map(|field| field.offset + field.field_type.size())
+        .max()
+        .unwrap_or_default();
+    if schema_size != desc.size {
+        return Err(SchemaParseError::SizeMismatch {
+            type_str: desc.type_str.to_owned(),
+            expected: desc.size,
+            actual: schema_size,
+        });
+    }
+    let fields: Arc<FieldLayout> = Arc::new(
+        parsed
+            .into_iter()
+            .map(|field| (field.name, (field.offset, field.field_type, field.bit)))
+            .collect(),
+    );
+    let _ = layout_cache()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(
+            desc.type_str,
+            CachedLayout {
+                schema,
+                size: desc.size,
+                fields: Arc::clone(&fields),
+            },
+        );
+    Ok(fields)
+}
+
+impl DynamicStructure {
+    /// Creates a new [`DynamicStructure`] from a description and a buffer holding a single structure
+    ///
+    /// The parsed schema is cached by type, so creating many structures of the same type is cheap.
+    ///
+    /// # Errors
+    /// Returns [`DynamicStructureError::BufferSize`] if the buffer is not the size of the structure
+    /// or [`FrcStructureError::SchemaParse`] if the schema of the structure could not be parsed
+    pub fn try_new(
+        desc: &'static FrcStructDesc,
+        buffer: Box<[u8]>,
+    ) -> Result<Self, FrcStructureError> {
+        if buffer.len() != desc.size {
+            return Err(DynamicStructureError::BufferSize {
+                expected: desc.size,
+                actual: buffer.len(),
+            }
+            .into());
+        }
+        let fields = field_layout(desc)?;
+        Ok(Self {
+            desc,
+            buffer,
+            fields,
+        })
+    }
+
+    /// The description of the structure
+    #[must_use]
+    pub const fn description(&self) -> &'static FrcStructDesc {
+        self.desc
+    }
+
+    /// The packed bytes of the structure
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Reads a primitive field by name,
+    /// returns None if the structure has no such field
+    #[must_use]
+    pub fn get_field(&self, name: &str) -> Option<FrcValue> {
+        let &(offset, field_type, bit) = self.fields.get(name)?;
+        if let Some(bit) = bit {
+            return Some(FrcValue::Boolean((self.buffer[offset] >> bit) & 1 != 0));
+        }
+        Some(decode(
+            &self.buffer[offset..offset + field_type.size()],
+            field_type,
+        ))
+    }
+
+    /// Writes a primitive field by name
+    ///
+    /// # Errors
+    /// Returns [`DynamicStructureError::UnknownField`] if the structure has no such field
+    /// or [`DynamicStructureError::FieldTypeMismatch`] if the value does not fit the type of the field
+    pub fn set_field(&mut self, name: &str, value: &FrcValue) -> Result<(), FrcStructureError> {
+        let &(offset, field_type, bit) = self
+            .fields
+            .get(name)
+            .ok_or_else(|| DynamicStructureError::UnknownField(name.to_owned()))?;
+        if let Some(bit) = bit {
+            let FrcValue::Boolean(value) = value else {
+                return Err(DynamicStructureError::FieldTypeMismatch(name.to_owned()).into());
+            };
+            self.buffer[offset] = (self.buffer[offset] & !(1 << bit)) | (u8::from(*value) << bit);
+            return Ok(());
+        }
+        let bytes = encode(value, field_type)
+            .ok_or_else(|| DynamicStructureError::FieldTypeMismatch(name.to_owned()))?;
+        self.buffer[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+fn elements<const N: usize>(bytes: &[u8]) -> impl Iterator<Item = [u8; N]> + '_ {
+    bytes.chunks_exact(N).map(|chunk| {
+        let mut element = [0u8; N];
+        element.copy_from_slice(chunk);
+        element
+    })
+}
+
+fn one_or_many<T>(
+    mut values: impl Iterator<Item = T>,
+    count: usize,
+    one: fn(T) -> FrcValue,
+    many: fn(Box<[T]>) -> FrcValue,
+) -> FrcValue {
+    if count == 1 {
+        values.next().map_or(FrcValue::Void, one)
+    } else {
+        many(values.collect())
+    }
+}
+
+fn decode(bytes: &[u8], field_type: StructureFieldTypes) -> FrcValue {
+    use StructureFieldTypes as T;
+
+    let count = field_type.count();
+    let int = |values: Box<dyn Iterator<Item = i64> + '_>| {
+        one_or_many(values, count, FrcValue::Int, FrcValue::IntArray)
+    };
+    match field_type {
+        T::Bool(_) => one_or_many(
+            bytes.iter().map(|byte| *byte != 0),
+            count,
+            FrcValue::Boolean,
+            FrcValue::BooleanArray,
+        ),
+        T::Char(_) => {
+            FrcValue::String(String::from_utf8_lossy(bytes).trim_end_matches('\0').into())
+        }
+        T::Int8(_) => int(Box::new(
+            elements(bytes).map(|b| i64::from(i8::from_le_bytes(b))),
+        )),
+        T::Int16(_) => int(Box::new(
+            elements(bytes).map(|b| i64::from(i16::from_le_bytes(b))),
+        )),
+        T::Int32(_) => int(Box::new(
+            elements(bytes).map(|b| i64::from(i32::from_le_bytes(b))),
+        )),
+        // uint64 is reinterpreted as it does not fit in an `i64`
+        T::Int64(_) | T::UInt64(_) => int(Box::new(elements(bytes).map(i64::from_le_bytes))),
+        T::UInt8(_) => int(Box::new(
+            elements(bytes).map(|b| i64::from(u8::from_le_bytes(b))),
+        )),
+        T::UInt16(_) => int(Box::new(
+            elements(bytes).map(|b| i64::from(u16::from_le_bytes(b))),
+        )),
+        T::UInt32(_) => int(Box::new(
+            elements(bytes).map(|b| i64::from(u32::from_le_bytes(b))),
+        )),
+        T::Float32(_) => one_or_many(
+            elements(bytes).map(f32::from_le_bytes),
+            count,
+            FrcValue::Float,
+            FrcValue::FloatArray,
+        ),
+        T::Float64(_) => one_or_many(
+            elements(bytes).map(f64::from_le_bytes),
+            count,
+            FrcValue::Double,
+            FrcValue::DoubleArray,
+        ),
+    }
+}
+
+fn values_of<T: Copy>(one: Option<T>, many: Option<&[T]>, count: usize) -> Option<Vec<T>> {
+    match (one, many) {
+        (Some(value), _) if count == 1 => Some(vec![value]),
+        (_, Some(values)) if values.len() == count => Some(values.to_vec()),
+        _ => None,
+    }
+}
+
+macro_rules! encode_ints {
+    ($values:expr, $prim:ty) => {
+        $values
+            .into_iter()
+            .map(|value| <$prim>::try_from(value).ok().map(<$prim>::to_le_bytes))
+            .collect::<Option<Vec<_>>>()
+            .map(|values| values.concat())
+    };
+}
+
+fn encode(value: &FrcValue, field_type: StructureFieldTypes) -> Option<Vec<u8>> {
+    use StructureFieldTypes as T;
+
+    let count = field_type.count();
+    let ints = || match value {
+        FrcValue::Int(v) => values_of(Some(*v), None, count),
+        FrcValue::IntArray(v) => values_of(None, Some(v), count),
+        _ => None,
+    };
+    let floats = || match value {
+        FrcValue::Float(v) => values_of(Some(f64::from(*v)), None, count),
+        FrcValue::Double(v) => values_of(Some(*v), None, count),
+        FrcValue::FloatArray(v) => values_of(
+            None,
+            Some(&v.iter().copied().map(f64::from).collect::<Vec<_>>()),
+            count,
+        ),
+        FrcValue::DoubleArray(v) => values_of(None, Some(v), count),
+        _ => None,
+    };
+    match field_type {
+        T::Bool(_) => match value {
+            FrcValue::Boolean(v) => values_of(Some(*v), None, count),
+            FrcValue::BooleanArray(v) => values_of(None, Some(v), count),
+            _ => None,
+        }
+        .map(|values| values.into_iter().map(u8::from).collect()),
+        T::Char(_) => match value {
+            FrcValue::String(v) if v.len() <= count => {
+                let mut bytes = v.as_bytes().to_vec();
+                bytes.resize(count, 0);
+                Some(bytes)
+            }
+            _ => None,
+        },
+        T::Int8(_) => encode_ints!(ints()?, i8),
+        T::Int16(_) => encode_ints!(ints()?, i16),
+        T::Int32(_) => encode_ints!(ints()?, i32),
+        T::Int64(_) | T::UInt64(_) => encode_ints!(ints()?, i64),
+        T::UInt8(_) => encode_ints!(ints()?, u8),
+        T::UInt16(_) => encode_ints!(ints()?, u16),
+        T::UInt32(_) => encode_ints!(ints()?, u32),
+        #[allow(clippy::cast_possible_truncation)]
+        T::Float32(_) => Some(
+            floats()?
+                .into_iter()
+                .flat_map(|v| (v as f32).to_le_bytes())
+                .collect(),
+        ),
+        T::Float64(_) => Some(floats()?.into_iter().flat_map(f64::to_le_bytes).collect()),
+    }
+}
@@ -0,0 +1,44 @@
+use super::schema::packed_fields;
+
+/// The byte order of packed structure data
+///
+/// Only multi-byte fields are affected by the byte order,
+/// `int16`, `int32`, `int64`, `uint16`, `uint32`, `uint64`, `float32` and `float64` are flipped
+/// while `bool`, `char`, `int8` and `uint8` are the same in either order.
+///
+/// The `WPIlib` struct spec is little-endian so [`Little`](Endianness::Little) is the default,
+/// it is what [`pack`](super::FrcStructure::pack) and [`unpack`](super::FrcStructure::unpack) use
+/// unless the structure sets a different [`ENDIAN`](super::FrcStructure::ENDIAN).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Least significant byte first, used by the `WPIlib` struct spec
+    #[default]
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+impl Endianness {
+    /// The byte order of the target platform
+    pub const NATIVE: Self = if cfg!(target_endian = "big") {
+        Self::Big
+    } else {
+        Self::Little
+    };
+}
+
+/// Reverses the bytes of every multi-byte primitive in a packed structure,
+/// converting it between little and big endian.
+///
+/// Structures with an empty schema are treated as an array of the primitive named by `type_str`.
+pub(super) fn flip_packed(bytes: &mut [u8], type_str: &str, schema: &str) {
+    for (offset, field_type, _) in packed_fields(type_str, schema, bytes.len()) {
+        let base_size = field_type.base_size();
+        for index in 0..field_type.count() {
+            let start = offset + index * base_size;
+            if let Some(element) = bytes.get_mut(start..start + base_size) {
+                element.reverse();
+            }
+        }
+    }
+}
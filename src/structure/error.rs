@@ -0,0 +1,255 @@
+use alloc::string::String;
+use core::fmt;
+
+/// An error that occurs when parsing a structure schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaParseError {
+    /// The schema contains characters that are not part of the schema grammar
+    InvalidToken(usize),
+    /// A field declaration does not follow the schema grammar
+    MalformedField(String),
+    /// A field has a type that is neither a primitive nor a registered structure
+    UnknownType(String),
+    /// A field is a bit-field other than `bool name:1`, these are not supported
+    BitField(String),
+    /// A structure contains itself, either directly or through other structures
+    Recursive(String),
+    /// A field makes the structure larger than fits in memory
+    TooLarge(String),
+    /// The registered size of a structure does not match the size of its schema
+    SizeMismatch {
+        /// The type of the structure
+        type_str: String,
+        /// The size in the structure description
+        expected: usize,
+        /// The size of the fields in the schema
+        actual: usize,
+    },
+}
+
+/// An error that occurs when creating or modifying a [`DynamicStructure`](super::DynamicStructure)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynamicStructureError {
+    /// The buffer is not the size of the structure
+    BufferSize {
+        /// The size of the structure
+        expected: usize,
+        /// The size of the buffer
+        actual: usize,
+    },
+    /// The schema of the structure could not be parsed
+    Schema(SchemaParseError),
+    /// The structure has no primitive field with the given name
+    UnknownField(String),
+    /// The value is not the type, array length or range of the field
+    FieldTypeMismatch(String),
+}
+
+/// An error that occurs when unpacking a structure from untrusted bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StructUnpackError {
+    /// The buffer ended before the whole structure could be read
+    InsufficientBytes {
+        /// The type of the structure being unpacked
+        type_str: &'static str,
+        /// The size of the structure
+        needed: usize,
+        /// The bytes remaining in the buffer
+        available: usize,
+    },
+    /// An enum was unpacked from a value that is not one of its variants
+    InvalidDiscriminant {
+        /// The type of the enum being unpacked
+        type_str: &'static str,
+        /// The unpacked discriminant
+        discriminant: String,
+    },
+    /// The bytes were packed with a schema that neither starts with nor is a start of the local one
+    IncompatibleSchema {
+        /// The type of the structure being unpacked
+        type_str: &'static str,
+        /// The type the bytes were packed as
+        foreign: &'static str,
+    },
+}
+
+/// An error that occurs when working with [`FrcStructureBytes`](super::FrcStructureBytes)
+///
+/// This also wraps the other errors of the structure module,
+/// so code mixing them can use `?` with a single error type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrcStructureError {
+    /// The requested type is not the type of the stored structures
+    TypeMismatch {
+        /// The requested type
+        expected: &'static str,
+        /// The type in the structure description
+        actual: &'static str,
+    },
+    /// The length of the data does not match the size and count of the structures
+    LengthMismatch {
+        /// The size of the structure multiplied by the count
+        expected: usize,
+        /// The length of the data
+        actual: usize,
+    },
+    /// A field annotated with `enum {..}` in the schema holds a value that isn't one of its variants
+    InvalidEnumValue {
+        /// The name of the field like [`parse_schema`](super::parse_schema) names it,
+        /// with the index of the element for arrays
+        field: String,
+        /// The offset of the field from the start of the data
+        offset: usize,
+        /// The value of the field
+        value: i128,
+    },
+    /// A structure could not be unpacked
+    Unpack(StructUnpackError),
+    /// The structure type is not registered in the [`FrcStructDescDB`](super::FrcStructDescDB)
+    UnknownType(String),
+    /// Another structure with the same type but a different size or schema is registered
+    /// in the [`FrcStructDescDB`](super::FrcStructDescDB)
+    ConflictingDescription(&'static str),
+    /// A structure schema could not be parsed
+    SchemaParse(SchemaParseError),
+    /// A [`DynamicStructure`](super::DynamicStructure) could not be created or modified
+    Dynamic(DynamicStructureError),
+    /// Reading the structure from an IO source failed
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+// written out instead of derived with `thiserror` so structures work without `std`
+impl fmt::Display for SchemaParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidToken(offset) => write!(f, "Invalid token at byte {offset} of the schema"),
+            Self::MalformedField(field) => write!(f, "Malformed field declaration `{field}`"),
+            Self::UnknownType(type_str) => write!(
+                f,
+                "Unknown type `{type_str}`, structures have to be registered in the `FrcStructDescDB`"
+            ),
+            Self::BitField(field) => write!(f, "Bit-field `{field}` is not supported"),
+            Self::Recursive(type_str) => write!(f, "Structure `{type_str}` contains itself"),
+            Self::TooLarge(field) => write!(f, "Field `{field}` makes the structure too large"),
+            Self::SizeMismatch {
+                type_str,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Structure `{type_str}` has a size of {expected} but its schema has a size of {actual}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaParseError {}
+
+impl fmt::Display for DynamicStructureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferSize { expected, actual } => write!(
+                f,
+                "Buffer size ({actual}) does not match structure size ({expected})"
+            ),
+            Self::Schema(error) => error.fmt(f),
+            Self::UnknownField(field) => write!(f, "Structure has no field `{field}`"),
+            Self::FieldTypeMismatch(field) => {
+                write!(f, "Value does not fit the type of field `{field}`")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DynamicStructureError {}
+
+impl From<SchemaParseError> for DynamicStructureError {
+    fn from(error: SchemaParseError) -> Self {
+        Self::Schema(error)
+    }
+}
+
+impl fmt::Display for StructUnpackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientBytes {
+                type_str,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Not enough bytes to unpack {type_str}, needed {needed} but only {available} remain"
+            ),
+            Self::InvalidDiscriminant {
+                type_str,
+                discriminant,
+            } => write!(f, "Invalid discriminant {discriminant} for enum {type_str}"),
+            Self::IncompatibleSchema { type_str, foreign } => {
+                write!(f, "Structure {foreign} is not compatible with {type_str}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StructUnpackError {}
+
+impl fmt::Display for FrcStructureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected, actual } => write!(
+                f,
+                "Expected structures of type {expected} but found {actual}"
+            ),
+            Self::LengthMismatch { expected, actual } => write!(
+                f,
+                "Expected {expected} bytes of structure data but found {actual}"
+            ),
+            Self::InvalidEnumValue {
+                field,
+                offset,
+                value,
+            } => write!(
+                f,
+                "Field `{field}` at byte {offset} holds {value}, which is not one of its enum values"
+            ),
+            Self::Unpack(error) => error.fmt(f),
+            Self::UnknownType(type_str) => write!(
+                f,
+                "Structure type `{type_str}` is not registered in the `FrcStructDescDB`"
+            ),
+            Self::ConflictingDescription(type_str) => write!(
+                f,
+                "Structure type `{type_str}` is registered with a different size or schema"
+            ),
+            Self::SchemaParse(error) => error.fmt(f),
+            Self::Dynamic(error) => error.fmt(f),
+            #[cfg(feature = "std")]
+            Self::Io(kind) => write!(f, "Failed to read structure: {kind}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FrcStructureError {}
+
+impl From<StructUnpackError> for FrcStructureError {
+    fn from(error: StructUnpackError) -> Self {
+        Self::Unpack(error)
+    }
+}
+
+impl From<SchemaParseError> for FrcStructureError {
+    fn from(error: SchemaParseError) -> Self {
+        Self::SchemaParse(error)
+    }
+}
+
+impl From<DynamicStructureError> for FrcStructureError {
+    fn from(error: DynamicStructureError) -> Self {
+        Self::Dynamic(error)
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{FrcStructDesc, FrcStructDescDB, FrcStructureBytes};
+
+/// The most structures generated into a single [`FrcStructureBytes`]
+const MAX_COUNT: usize = 64;
+
+impl FrcStructureBytes {
+    /// Generates `count * desc.size` random bytes for a random `count`.
+    ///
+    /// The length always matches the description,
+    /// the contents are random so they may still fail to unpack (e.g. invalid enum discriminants).
+    ///
+    /// # Errors
+    /// Returns an error if `u` can't produce a count
+    pub fn arbitrary_for(desc: &'static FrcStructDesc, u: &mut Unstructured<'_>) -> Result<Self> {
+        let count = u.int_in_range(0..=MAX_COUNT)?;
+        let data = random_bytes(u, count * desc.size)?;
+        Ok(Self::from_parts(desc, count, data))
+    }
+
+    /// Generates random bytes whose length doesn't match `count` structures of `desc`,
+    /// for checking that decoders reject malformed data instead of misreading it.
+    ///
+    /// # Errors
+    /// Returns an error if `u` can't produce a count
+    pub fn arbitrary_malformed_for(
+        desc: &'static FrcStructDesc,
+        u: &mut Unstructured<'_>,
+    ) -> Result<Self> {
+        let count = u.int_in_range(0..=MAX_COUNT)?;
+        let expected = count * desc.size;
+        let offset = u.int_in_range(1..=desc.size.max(1))?;
+        let len = if expected >= offset && bool::arbitrary(u)? {
+            expected - offset
+        } else {
+            expected + offset
+        };
+        let data = random_bytes(u, len)?;
+        Ok(Self::from_parts(desc, count, data))
+    }
+}
+
+/// Picks a registered structure description and generates valid bytes for it,
+/// see [`FrcStructureBytes::arbitrary_for`]
+impl<'a> Arbitrary<'a> for FrcStructureBytes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let descs = FrcStructDescDB::iter().collect::<Vec<_>>();
+        let desc = *u.choose(&descs)?;
+        Self::arbitrary_for(desc, u)
+    }
+}
+
+/// `len` bytes from `u`, padded with zeros once `u` runs out of data
+fn random_bytes(u: &mut Unstructured<'_>, len: usize) -> Result<Box<[u8]>> {
+    let mut data = vec![0; len].into_boxed_slice();
+    u.fill_buffer(&mut data)?;
+    Ok(data)
+}
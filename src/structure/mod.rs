@@ -1,19 +1,223 @@
+//! An implementation of the `WPIlib` struct spec for rust.
+//!
 //! This module contains an implementation of [WPIlib struct spec](https://github.com/wpilibsuite/allwpilib/blob/main/wpiutil/doc/struct.adoc)
 //! for rust and a macro to generate the trait implementation for a given struct.
 
 #[cfg(test)]
 mod test;
 
+#[cfg(not(feature = "std"))]
+mod cursor;
+mod dyn_structure;
+#[cfg(feature = "value-union")]
+mod dynamic;
+mod endian;
+mod error;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+mod lock;
 mod prims;
+mod schema;
+#[cfg(feature = "serde")]
+mod serde_repr;
+mod string;
 
-// use logos::Logos;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
 
-use std::io::Cursor;
+use lock::Lock;
 
+#[cfg(not(feature = "std"))]
+pub use cursor::{Cursor, UnexpectedEof};
+/// The cursor structures are unpacked from,
+/// without `std` this is an `alloc`-free cursor with the same methods.
+#[cfg(feature = "std")]
+pub use std::io::Cursor;
+
+pub use dyn_structure::FrcDynStructure;
+#[cfg(feature = "value-union")]
+pub use dynamic::DynamicStructure;
+pub use endian::Endianness;
+pub use error::{DynamicStructureError, FrcStructureError, SchemaParseError, StructUnpackError};
+pub use schema::{parse_schema, rust_source, rust_type_for, SchemaField, StructureFieldTypes};
+pub use string::StructString;
+
+#[cfg(feature = "inventory")]
 pub use inventory;
 
+/// NOT FOR DIRECT USE
+///
+/// Used by the derive macro, expands to a [`From`] impl for [`FrcValue`](crate::value::FrcValue)
+/// only when the `value-union` feature is enabled.
+/// This isnt a generic impl for every struct because of primitive and unit types.
+#[cfg(feature = "value-union")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! structure_into_value {
+    ($name:ty) => {
+        $crate::structure_into_value!($name, impl [] []);
+    };
+    ($name:ty, impl [$($impl_generics:tt)*] [$($where_clause:tt)*]) => {
+        impl $($impl_generics)* From<$name> for $crate::value::FrcValue $($where_clause)* {
+            fn from(value: $name) -> Self {
+                Self::Struct(Box::new($crate::structure::FrcStructureBytes::pack_many(&[value])))
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[cfg(not(feature = "value-union"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! structure_into_value {
+    ($($tokens:tt)*) => {};
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Used by the derive macro, submits the description to the [`inventory`]
+/// when the `inventory` feature is enabled and the `runtime-registry` feature isn't.
+#[cfg(all(feature = "inventory", not(feature = "runtime-registry")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! structure_submit {
+    ($name:ty) => {
+        $crate::structure::inventory::submit! {
+            <$name as $crate::structure::FrcStructure>::DESCRIPTION
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[cfg(not(all(feature = "inventory", not(feature = "runtime-registry"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! structure_submit {
+    ($name:ty) => {};
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Used by the derive macro, packs up to 8 `#[frc(bits)]` fields into a byte,
+/// the first field is the lowest bit.
+#[doc(hidden)]
+#[must_use]
+pub fn pack_bits(bits: &[bool]) -> u8 {
+    bits.iter()
+        .take(8)
+        .enumerate()
+        .fold(0, |byte, (index, bit)| byte | (u8::from(*bit) << index))
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Used by the derive macro, reads the `#[frc(bits)]` field at `index` of a byte.
+#[doc(hidden)]
+#[must_use]
+pub const fn unpack_bit(byte: u8, index: u8) -> bool {
+    (byte >> index) & 1 != 0
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The longest `TYPE` a generic structure can have, see [`join_type_name`].
+#[doc(hidden)]
+pub const GENERIC_TYPE_LEN: usize = 128;
+
+/// NOT FOR DIRECT USE
+///
+/// Used by the derive macro, joins the name of a generic structure and the `TYPE`s
+/// of its type parameters with `_` at compile time, like `Bounded_float64`.
+/// The rest of the array is zeroed, [`type_name_str`] trims it back off.
+#[doc(hidden)]
+#[must_use]
+pub const fn join_type_name(parts: &[&str]) -> [u8; GENERIC_TYPE_LEN] {
+    let mut name = [0; GENERIC_TYPE_LEN];
+    let mut len = 0;
+    let mut part = 0;
+    while part < parts.len() {
+        let bytes = parts[part].as_bytes();
+        assert!(
+            len + (part > 0) as usize + bytes.len() <= GENERIC_TYPE_LEN,
+            "the TYPE of a generic structure can't be longer than 128 bytes"
+        );
+        if part > 0 {
+            name[len] = b'_';
+            len += 1;
+        }
+        let mut index = 0;
+        while index < bytes.len() {
+            name[len] = bytes[index];
+            len += 1;
+            index += 1;
+        }
+        part += 1;
+    }
+    name
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The generic name of an array, the name of its element followed by `x` and the length,
+/// like `float64x2`. The rest of the array is zeroed like in [`join_type_name`].
+#[doc(hidden)]
+#[must_use]
+pub const fn array_type_name(element: &str, len: usize) -> [u8; GENERIC_TYPE_LEN] {
+    let mut name = join_type_name(&[element]);
+    let mut digits = 1;
+    let mut rest = len / 10;
+    while rest > 0 {
+        digits += 1;
+        rest /= 10;
+    }
+    assert!(
+        element.len() + 1 + digits <= GENERIC_TYPE_LEN,
+        "the TYPE of a generic structure can't be longer than 128 bytes"
+    );
+    name[element.len()] = b'x';
+    // the digits are written from the last one back
+    let mut end = element.len() + digits;
+    rest = len;
+    while end > element.len() {
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            name[end] = b'0' + (rest % 10) as u8;
+        }
+        rest /= 10;
+        end -= 1;
+    }
+    name
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Used by the derive macro, the name built by [`join_type_name`] without its zero padding.
+#[doc(hidden)]
+#[must_use]
+pub const fn type_name_str(name: &'static [u8; GENERIC_TYPE_LEN]) -> &'static str {
+    let mut len = 0;
+    while len < name.len() && name[len] != 0 {
+        len += 1;
+    }
+    match core::str::from_utf8(name.split_at(len).0) {
+        Ok(name) => name,
+        Err(_) => "",
+    }
+}
+
 /// A description of a structure, used for serialization and deserialization
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Descriptions are compared by [`type_str`](FrcStructDesc::type_str), [`size`](FrcStructDesc::size)
+/// and the schema their [`schema_supplier`](FrcStructDesc::schema_supplier) produces,
+/// so the same structure described by a derive in one crate and by hand in another is equal.
+#[derive(Debug, Clone, Copy)]
 pub struct FrcStructDesc {
     /// A function that returns the schema of the structure,
     /// this is needed because the schema cannot be made in a const context
@@ -24,62 +228,437 @@ pub struct FrcStructDesc {
     pub size: usize,
 }
 
+#[cfg(feature = "inventory")]
 inventory::collect!(FrcStructDesc);
 
+impl FrcStructDesc {
+    /// Compares the schemas produced by both suppliers,
+    /// without caching them for descriptions that are only compared
+    #[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
+    fn cmp_supplied_schema(&self, other: &Self) -> Ordering {
+        if self.schema_supplier == other.schema_supplier {
+            return Ordering::Equal;
+        }
+        (self.schema_supplier)().cmp(&(other.schema_supplier)())
+    }
+}
+
+impl PartialEq for FrcStructDesc {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FrcStructDesc {}
+
+impl PartialOrd for FrcStructDesc {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrcStructDesc {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.type_str
+            .cmp(other.type_str)
+            .then(self.size.cmp(&other.size))
+            .then_with(|| self.cmp_supplied_schema(other))
+    }
+}
+
+impl FrcStructDesc {
+    /// Returns the schema of the structure,
+    /// [`schema_supplier`](FrcStructDesc::schema_supplier) is only called the first time
+    /// a schema is requested for a description.
+    ///
+    /// The cache is keyed by the type, size and supplier of the description,
+    /// so two descriptions that only share a [`type_str`](FrcStructDesc::type_str)
+    /// each get their own schema.
+    #[must_use]
+    pub fn schema(&self) -> &'static str {
+        type SchemaKey = (&'static str, usize, usize);
+        static SCHEMAS: Lock<BTreeMap<SchemaKey, &'static str>> = Lock::new(BTreeMap::new());
+        let key = (self.type_str, self.size, self.schema_supplier as usize);
+        if let Some(schema) = SCHEMAS.read().get(&key) {
+            return schema;
+        }
+        SCHEMAS
+            .write()
+            .entry(key)
+            .or_insert_with(|| Box::leak((self.schema_supplier)().into_boxed_str()))
+    }
+
+    /// A stable hash of the fully expanded schema, used to check that
+    /// two sides agree on the layout of a structure.
+    ///
+    /// Nested structures are expanded so changing a sub-structure
+    /// also changes the hash of every structure containing it.
+    /// The hash only depends on the name, type and count of each primitive field,
+    /// so it is the same across builds and platforms.
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or the schema of a nested structure, can't be parsed
+    pub fn schema_hash(&self) -> Result<u64, SchemaParseError> {
+        schema::schema_hash(self.type_str, self.schema())
+    }
+
+    /// Checks that a schema from elsewhere, like a `NetworkTables` struct topic,
+    /// describes the same layout as this structure.
+    ///
+    /// Both schemas are fully expanded before comparing, so whitespace, enum annotations
+    /// and type aliases like `float`/`float32` don't matter, but the name, type, count
+    /// and order of every field do.
+    /// Structures nested in `foreign` are looked up in the [`FrcStructDescDB`],
+    /// a schema that can't be parsed never matches.
+    #[must_use]
+    pub fn schema_matches(&self, foreign: &str) -> bool {
+        match (parse_schema(self.schema()), parse_schema(foreign)) {
+            (Ok(local), Ok(foreign)) => local == foreign,
+            _ => false,
+        }
+    }
+
+    /// Like [`schema_matches`](Self::schema_matches) but also accepts a `foreign` schema
+    /// with more fields appended, the leading bytes of such a structure can still be
+    /// unpacked as this one.
+    #[must_use]
+    pub fn schema_compatible(&self, foreign: &str) -> bool {
+        match (parse_schema(self.schema()), parse_schema(foreign)) {
+            (Ok(local), Ok(foreign)) => foreign.starts_with(&local),
+            _ => false,
+        }
+    }
+
+    /// The fully expanded schema as JSON, for consumers that can't parse the schema language.
+    ///
+    /// The output is an object with the `type` and total `size` of the structure
+    /// and a `fields` array of `{name, type, offset, count}` objects,
+    /// nested fields are named like [`parse_schema`] names them.
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or the schema of a nested structure, can't be parsed
+    pub fn schema_json(&self) -> Result<String, SchemaParseError> {
+        schema::schema_json(self.type_str, self.size, self.schema())
+    }
+
+    /// The byte offset and type of every primitive field of the structure,
+    /// named like [`parse_schema`] names them
+    ///
+    /// A primitive type with an empty schema has a single field with an empty name.
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or the schema of a nested structure, can't be parsed
+    pub fn field_layout(&self) -> Result<Vec<SchemaField>, SchemaParseError> {
+        let schema = self.schema();
+        if schema.is_empty() {
+            return Ok(schema::packed_fields(self.type_str, schema, self.size)
+                .into_iter()
+                .map(|(offset, field_type, bit)| SchemaField {
+                    name: String::new(),
+                    offset,
+                    field_type,
+                    bit,
+                })
+                .collect());
+        }
+        parse_schema(schema)
+    }
+
+    /// The byte offset and type of a primitive field by name,
+    /// returns None if the structure has no such field or its schema can't be parsed
+    #[must_use]
+    pub fn field_offset(&self, name: &str) -> Option<(usize, StructureFieldTypes)> {
+        self.field_layout()
+            .ok()?
+            .into_iter()
+            .find(|field| field.name == name)
+            .map(|field| (field.offset, field.field_type))
+    }
+
+    /// Renders little-endian packed bytes of this structure for debugging,
+    /// like `Pose2d { x: 1.5, y: -0.2, theta: 0.78 }`
+    ///
+    /// Fields are named like [`parse_schema`] names them, arrays are written as lists
+    /// and `char` arrays as strings.
+    /// Fields past the end of `bytes` are written as `<missing>`.
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or the schema of a nested structure, can't be parsed
+    pub fn pretty_decode(&self, bytes: &[u8]) -> Result<String, SchemaParseError> {
+        schema::pretty_decode(self.type_str, self.schema(), bytes)
+    }
+}
+
+/// The global database of structure descriptions by type
+type DescIndex = BTreeMap<&'static str, &'static FrcStructDesc>;
+
+/// The index of the global database, built from the inventory on first use
+#[cfg(all(feature = "inventory", not(feature = "runtime-registry")))]
+fn desc_index() -> &'static Lock<DescIndex> {
+    use core::sync::atomic::{AtomicBool, Ordering::Acquire, Ordering::Release};
+
+    static INDEX: Lock<DescIndex> = Lock::new(BTreeMap::new());
+    static COLLECTED: AtomicBool = AtomicBool::new(false);
+    if !COLLECTED.load(Acquire) {
+        let mut index = INDEX.write();
+        // another thread may have collected while this one waited for the lock
+        if !COLLECTED.load(Acquire) {
+            for desc in inventory::iter::<FrcStructDesc> {
+                let _ = index.entry(desc.type_str).or_insert(desc);
+            }
+            COLLECTED.store(true, Release);
+        }
+        drop(index);
+    }
+    &INDEX
+}
+
+/// The index of the global database, only filled by explicit registration
+#[cfg(not(all(feature = "inventory", not(feature = "runtime-registry"))))]
+fn desc_index() -> &'static Lock<DescIndex> {
+    static INDEX: Lock<DescIndex> = Lock::new(BTreeMap::new());
+    &INDEX
+}
+
 /// A global database of structure descriptions
+///
+/// With the `inventory` feature, which is on by default, derived structures are collected at link time
+/// with `inventory`, which doesn't work on every target (e.g. WASM).
+/// Without it, or with the `runtime-registry` feature, nothing is collected at link time,
+/// every structure has to be added with [`register`](FrcStructDescDB::register) before it is looked up.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FrcStructDescDB;
 
 impl FrcStructDescDB {
     /// Adds a structure description to the global database,
-    /// this is a runtime equivalent of the [`inventory::submit!`] macro.
+    /// this is a runtime equivalent of the `inventory::submit!` macro.
     #[cold]
     pub fn add(desc: FrcStructDesc) {
-        if Self::contains_type(desc.type_str) {
+        if let Some(existing) = Self::get(desc.type_str) {
+            trace_skipped(&desc, existing);
             return;
         }
-        let static_desc_ref = Box::leak(Box::new(desc));
-        let node = inventory::Node {
-            value: static_desc_ref,
-            next: ::inventory::core::cell::UnsafeCell::new(::inventory::core::option::Option::None),
-        };
-        unsafe { inventory::ErasedNode::submit(node.value, Box::leak(Box::new(node))) }
+        Self::add_ref(Box::leak(Box::new(desc)));
     }
 
     /// Adds a structure description to the global database,
-    /// this is a runtime equivalent of the [`inventory::submit!`] macro.
+    /// this is a runtime equivalent of the `inventory::submit!` macro.
+    ///
+    /// This is safe to call from multiple threads,
+    /// only the first description submitted for a type is kept.
     #[cold]
     pub fn add_ref(desc: &'static FrcStructDesc) {
-        if Self::contains_type(desc.type_str) {
+        // the write lock is held across the check and the submit so concurrent
+        // registrations can neither race the inventory list nor duplicate a type
+        let mut index = desc_index().write();
+        if let Some(existing) = index.get(desc.type_str).copied() {
+            drop(index);
+            trace_skipped(desc, existing);
             return;
         }
-        let node = inventory::Node {
-            value: desc,
-            next: ::inventory::core::cell::UnsafeCell::new(::inventory::core::option::Option::None),
-        };
-        unsafe { inventory::ErasedNode::submit(node.value, Box::leak(Box::new(node))) }
+        #[cfg(all(feature = "inventory", not(feature = "runtime-registry")))]
+        {
+            let node = inventory::Node {
+                value: desc,
+                next: core::cell::UnsafeCell::new(None),
+            };
+            unsafe { inventory::ErasedNode::submit(node.value, Box::leak(Box::new(node))) }
+        }
+        let _ = index.insert(desc.type_str, desc);
+        drop(index);
+        trace_registered(desc);
+    }
+
+    /// Adds the description of `T` to the global database,
+    /// structures nested in `T` have to be registered separately.
+    ///
+    /// This is only needed without the `inventory` feature or with the `runtime-registry` feature,
+    /// otherwise derived structures are registered at link time and this does nothing new.
+    ///
+    /// # Example
+    /// ```
+    /// use frclib_core::structure::{FrcStructDescDB, FrcStructure};
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Clone, Copy, FrcStructure)]
+    /// struct Pose {
+    ///     x: f64,
+    ///     y: f64,
+    /// }
+    ///
+    /// FrcStructDescDB::register::<Pose>();
+    /// assert!(FrcStructDescDB::contains_type("Pose"));
+    /// ```
+    pub fn register<T: FrcStructure>() {
+        Self::add_ref(&T::DESCRIPTION);
+    }
+
+    /// Gets the description of `T` from the global database, registering it first if it's missing
+    ///
+    /// Like [`register`](Self::register) this is safe to call from multiple threads
+    /// and only the first description of a type is kept,
+    /// so every call returns the same description.
+    ///
+    /// # Errors
+    /// Returns [`FrcStructureError::ConflictingDescription`] if another description with the same `TYPE`
+    /// came first and its size or schema differs from `T`
+    pub fn get_or_register<T: FrcStructure>() -> Result<&'static FrcStructDesc, FrcStructureError> {
+        let desc = Self::get(T::TYPE).unwrap_or_else(|| {
+            Self::register::<T>();
+            Self::get(T::TYPE).unwrap_or(&T::DESCRIPTION)
+        });
+        if desc.size == T::SIZE && desc.schema() == T::DESCRIPTION.schema() {
+            Ok(desc)
+        } else {
+            Err(FrcStructureError::ConflictingDescription(T::TYPE))
+        }
     }
 
     /// Checks if the global database contains a structure description for a given type
     #[must_use]
     pub fn contains_type(type_str: &str) -> bool {
-        inventory::iter::<FrcStructDesc>
-            .into_iter()
-            .any(|desc| desc.type_str == type_str)
+        desc_index().read().contains_key(type_str)
     }
 
     /// Gets a structure description from the global database for a given type,
     /// returns None if the type is not found
     #[must_use]
     pub fn get(type_str: &str) -> Option<&'static FrcStructDesc> {
-        inventory::iter::<FrcStructDesc>
-            .into_iter()
-            .find(|desc| desc.type_str == type_str)
+        desc_index().read().get(type_str).copied()
+    }
+
+    /// Gets a structure description from the global database for a given type
+    ///
+    /// # Errors
+    /// Returns [`FrcStructureError::UnknownType`] if the type is not found
+    pub fn try_get(type_str: &str) -> Result<&'static FrcStructDesc, FrcStructureError> {
+        Self::get(type_str).ok_or_else(|| FrcStructureError::UnknownType(type_str.to_owned()))
+    }
+
+    /// Iterates over every structure description in the global database,
+    /// ordered by [`type_str`](FrcStructDesc::type_str)
+    pub fn iter() -> impl Iterator<Item = &'static FrcStructDesc> {
+        let mut descs = desc_index().read().values().copied().collect::<Vec<_>>();
+        descs.sort_unstable_by_key(|desc| desc.type_str);
+        descs.into_iter()
+    }
+
+    /// Every structure description in the global database as a single JSON document,
+    /// for checking the compatibility of a whole program's structures at once.
+    ///
+    /// The output is an array ordered like [`iter`](Self::iter) of
+    /// `{type, size, schema, hash}` objects, where `hash` is the
+    /// [`schema_hash`](FrcStructDesc::schema_hash) as 16 hex digits,
+    /// as a JSON number would lose precision in most consumers,
+    /// or `null` if the schema can't be parsed.
+    #[must_use]
+    pub fn catalog_json() -> String {
+        schema::catalog_json(Self::iter())
     }
+
+    /// The number of structure descriptions in the global database
+    #[must_use]
+    pub fn len() -> usize {
+        desc_index().read().len()
+    }
+}
+
+/// Logs a description added to the global database
+#[cfg(feature = "tracing")]
+fn trace_registered(desc: &FrcStructDesc) {
+    tracing::debug!(
+        type_str = desc.type_str,
+        size = desc.size,
+        "registered structure description"
+    );
 }
 
+/// Logs a registration that was skipped because the type is already registered,
+/// a different schema usually means another crate has a structure with the same name.
+/// The skipped schema is built from its supplier so it isn't cached for a description that is dropped.
+#[cfg(feature = "tracing")]
+fn trace_skipped(desc: &FrcStructDesc, existing: &FrcStructDesc) {
+    tracing::debug!(
+        type_str = desc.type_str,
+        same_schema =
+            existing.size == desc.size && (existing.schema_supplier)() == (desc.schema_supplier)(),
+        "skipped structure description, the type is already registered"
+    );
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+const fn trace_registered(_: &FrcStructDesc) {}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+const fn trace_skipped(_: &FrcStructDesc, _: &FrcStructDesc) {}
+
+/// A structure can't contain itself, the derive rejects it with a compile error.
+/// rustc already rejects `Option<Node>` as infinitely sized,
+/// the derive also rejects the structure in the generic arguments of any field
+/// as it can't tell whether the parameter ends up in the schema
+///
+/// ```compile_fail
+/// use frclib_core::structure::{FrcStructure, FrcStructureBytes};
+/// use std::io::Cursor;
+/// use std::marker::PhantomData;
+///
+/// #[derive(Clone, Copy, FrcStructure)]
+/// struct Tagged<T: FrcStructure> {
+///     id: u8,
+///     #[frc(skip)]
+///     marker: PhantomData<T>,
+/// }
+///
+/// #[derive(Clone, Copy, FrcStructure)]
+/// struct Node {
+///     value: f64,
+///     tag: Tagged<Node>,
+/// }
+/// ```
+///
+/// `#[frc(assert_size = N)]` fails to compile when the packed size drifts from `N`
+///
+/// ```compile_fail
+/// use frclib_core::structure::{FrcStructure, FrcStructureBytes};
+/// use std::io::Cursor;
+///
+/// #[derive(Clone, Copy, FrcStructure)]
+/// #[frc(assert_size = 16)]
+/// struct Reading {
+///     value: f64,
+///     timestamp: u64,
+///     valid: bool,
+/// }
+/// ```
+///
+/// Enum discriminants have to be integer literals, a const expression is a compile error
+/// on the discriminant instead of a panic in the derive
+///
+/// ```compile_fail
+/// use frclib_core::structure::{FrcStructure, FrcStructureBytes};
+/// use std::io::Cursor;
+///
+/// const BASE: u8 = 4;
+///
+/// #[derive(Clone, Copy, Default, FrcStructure)]
+/// #[repr(u8)]
+/// enum Mode {
+///     #[default]
+///     Idle = 0,
+///     Run = BASE + 1,
+/// }
+/// ```
 pub use frclib_structure_macros::FrcStructure;
 
+/// Derive macro generating an impl of the trait [`FrcDynStructure`].
+///
+/// Named and tuple structs are supported, every field has to be a [`FrcStructure`]
+/// or a `Vec` of one, fields are packed in declaration order.
+/// See [`FrcDynStructure`] for the encoding and why these can't be used as fixed-size structures.
+pub use frclib_structure_macros::FrcDynStructure;
+
 /// A trait that allows serialization and deserialization of arbitrary structures
 /// to and from a [``FrcValue``](crate::value::FrcValue)
 pub trait FrcStructure
@@ -103,13 +682,348 @@ where
         type_str: Self::TYPE,
         size: Self::SIZE,
     };
+    /// Whether the in-memory bytes of the structure are its packed little-endian bytes,
+    /// letting [`FrcStructureBytes::pack_many`] copy a slice of them in one go.
+    ///
+    /// The derive sets this for `#[repr(C)]` structs of numeric primitives without padding.
+    /// Setting it by hand takes an `unsafe` promise, see [`TriviallyCopyable::new_unchecked`].
+    const TRIVIALLY_COPYABLE: TriviallyCopyable<Self> = TriviallyCopyable::NO;
+    /// The byte order [`pack`](FrcStructure::pack) and [`unpack`](FrcStructure::unpack) use.
+    ///
+    /// This is little-endian, the order of the `WPIlib` struct spec,
+    /// except for structures derived with `#[frc(endian = "big")]` and arrays or options of them.
+    /// Schema driven code like [`FrcStructureBytes::validate`] and
+    /// [`DynamicStructure`](crate::structure::DynamicStructure) always reads the spec order,
+    /// so [`FrcStructureBytes`] and structures containing a big-endian one
+    /// pack it with [`pack_with_endian`](FrcStructure::pack_with_endian) in little-endian.
+    const ENDIAN: Endianness = Endianness::Little;
+    /// NOT FOR DIRECT USE
+    ///
+    /// The name of the structure as a type parameter of a derived generic structure,
+    /// arrays and strings append their length so `Bounded<[f64; 2]>` isn't named like `Bounded<f64>`.
+    #[doc(hidden)]
+    const GENERIC_NAME: &'static str = Self::TYPE;
 
     /// Packs the structure into a buffer
     fn pack(&self, buffer: &mut Vec<u8>);
 
     /// Unpacks the structure from a buffer
+    ///
+    /// This assumes the buffer holds a valid structure,
+    /// use [`try_unpack`](FrcStructure::try_unpack) for untrusted data.
     fn unpack(buffer: &mut Cursor<&[u8]>) -> Self;
 
+    /// Unpacks the structure from the start of a slice without going through a [`Cursor`],
+    /// bytes missing from a short slice read as zero.
+    ///
+    /// The default implementation unpacks through a cursor,
+    /// primitives, arrays and derived structures override it to read each field at its offset.
+    #[inline]
+    #[must_use]
+    fn unpack_slice(bytes: &[u8]) -> Self {
+        Self::unpack(&mut Cursor::new(bytes))
+    }
+
+    /// Packs the structure into a buffer with the given byte order
+    ///
+    /// The default implementation packs with [`pack`](FrcStructure::pack)
+    /// and flips every multi-byte field of the schema when `endian` is big,
+    /// implementations only need to override it to avoid parsing the schema.
+    fn pack_with_endian(&self, buffer: &mut Vec<u8>, endian: Endianness) {
+        let start = buffer.len();
+        self.pack(buffer);
+        if endian == Endianness::Big {
+            if let Some(packed) = buffer.get_mut(start..) {
+                endian::flip_packed(packed, Self::TYPE, Self::DESCRIPTION.schema());
+            }
+        }
+    }
+
+    /// Unpacks the structure from a buffer with the given byte order
+    ///
+    /// The default implementation flips every multi-byte field of the schema when `endian` is big
+    /// and unpacks the result with [`unpack`](FrcStructure::unpack),
+    /// implementations only need to override it to avoid parsing the schema.
+    fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, endian: Endianness) -> Self {
+        if endian == Endianness::Little {
+            return Self::unpack(buffer);
+        }
+        let start = usize::try_from(buffer.position()).unwrap_or(usize::MAX);
+        let end = start.saturating_add(Self::SIZE).min(buffer.get_ref().len());
+        let mut bytes = buffer
+            .get_ref()
+            .get(start..end)
+            .unwrap_or_default()
+            .to_vec();
+        endian::flip_packed(&mut bytes, Self::TYPE, Self::DESCRIPTION.schema());
+        buffer.set_position(u64::try_from(end).unwrap_or(u64::MAX));
+        Self::unpack(&mut Cursor::new(&bytes))
+    }
+
+    /// Unpacks the structure from a buffer,
+    /// checking that enough bytes remain and that any enum discriminants are valid
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too short or contains an invalid enum discriminant
+    fn try_unpack(buffer: &mut Cursor<&[u8]>) -> Result<Self, StructUnpackError> {
+        let available = remaining(buffer);
+        if available < Self::SIZE {
+            return Err(StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
+        }
+        Ok(Self::unpack(buffer))
+    }
+
+    /// Unpacks the structure from a buffer with the given byte order,
+    /// checking it like [`try_unpack`](FrcStructure::try_unpack)
+    ///
+    /// The default implementation flips every multi-byte field of the schema when `endian` is big
+    /// and checks the result with [`try_unpack`](FrcStructure::try_unpack),
+    /// containers and derived structures override it to check each field in the given order.
+    ///
+    /// # Errors
+    /// Returns an error if the buffer is too short or contains an invalid enum discriminant
+    fn try_unpack_with_endian(
+        buffer: &mut Cursor<&[u8]>,
+        endian: Endianness,
+    ) -> Result<Self, StructUnpackError> {
+        if endian == Endianness::Little {
+            return Self::try_unpack(buffer);
+        }
+        let available = remaining(buffer);
+        if available < Self::SIZE {
+            return Err(StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
+        }
+        let start = usize::try_from(buffer.position()).unwrap_or(usize::MAX);
+        let mut bytes = buffer
+            .get_ref()
+            .get(start..start.saturating_add(Self::SIZE))
+            .unwrap_or_default()
+            .to_vec();
+        endian::flip_packed(&mut bytes, Self::TYPE, Self::DESCRIPTION.schema());
+        let value = Self::try_unpack(&mut Cursor::new(&bytes))?;
+        buffer.set_position(u64::try_from(start.saturating_add(bytes.len())).unwrap_or(u64::MAX));
+        Ok(value)
+    }
+
+    /// Unpacks the structure from bytes packed by another version of it,
+    /// `foreign` is the description the sender packed them with.
+    ///
+    /// The fully expanded fields of one schema have to be a prefix of the other's,
+    /// see [`FrcStructDesc::schema_compatible`]:
+    /// - fields the sender appended are skipped
+    /// - fields the sender doesn't have yet are taken from [`Default::default`]
+    /// - identical layouts unpack like [`try_unpack`](FrcStructure::try_unpack)
+    ///
+    /// Either way the buffer is advanced by `foreign.size` bytes.
+    ///
+    /// # Errors
+    /// Returns an error if neither schema is a prefix of the other, either schema can't be parsed,
+    /// the buffer is shorter than `foreign.size` or contains an invalid enum discriminant
+    fn try_unpack_compat(
+        buffer: &mut Cursor<&[u8]>,
+        foreign: &FrcStructDesc,
+    ) -> Result<Self, StructUnpackError>
+    where
+        Self: Default,
+    {
+        let incompatible = || StructUnpackError::IncompatibleSchema {
+            type_str: Self::TYPE,
+            foreign: foreign.type_str,
+        };
+        let (Ok(local), Ok(sent)) = (
+            parse_schema(Self::DESCRIPTION.schema()),
+            parse_schema(foreign.schema()),
+        ) else {
+            return Err(incompatible());
+        };
+        let available = remaining(buffer);
+        if available < foreign.size {
+            return Err(StructUnpackError::InsufficientBytes {
+                type_str: foreign.type_str,
+                needed: foreign.size,
+                available,
+            });
+        }
+        let start = usize::try_from(buffer.position()).unwrap_or(usize::MAX);
+        let bytes = buffer
+            .get_ref()
+            .get(start..start + foreign.size)
+            .unwrap_or_default();
+        let value = if sent.starts_with(&local) {
+            try_unpack_spec(&mut Cursor::new(bytes))?
+        } else if local.starts_with(&sent) {
+            let mut padded = bytes.to_vec();
+            let mut defaults = Vec::with_capacity(Self::SIZE);
+            pack_spec(&Self::default(), &mut defaults);
+            padded.extend_from_slice(defaults.get(foreign.size..).unwrap_or_default());
+            try_unpack_spec(&mut Cursor::new(&padded))?
+        } else {
+            return Err(incompatible());
+        };
+        buffer.set_position(u64::try_from(start + foreign.size).unwrap_or(u64::MAX));
+        Ok(value)
+    }
+
+    /// Reads exactly [`SIZE`](FrcStructure::SIZE) bytes from an async reader and unpacks them,
+    /// so frames can be decoded straight off a socket without buffering them first
+    ///
+    /// # Errors
+    /// Returns [`FrcStructureError::Io`] if the reader fails or ends before a whole structure
+    /// and [`FrcStructureError::Unpack`] if the bytes contain an invalid enum discriminant
+    #[cfg(feature = "tokio")]
+    fn unpack_async<R>(
+        reader: &mut R,
+    ) -> impl std::future::Future<Output = Result<Self, FrcStructureError>> + Send
+    where
+        R: tokio::io::AsyncRead + Unpin + Send,
+    {
+        async move {
+            let mut bytes = vec![0; Self::SIZE];
+            let _ = tokio::io::AsyncReadExt::read_exact(reader, &mut bytes)
+                .await
+                .map_err(|err| FrcStructureError::Io(err.kind()))?;
+            Ok(Self::try_unpack(&mut Cursor::new(&bytes))?)
+        }
+    }
+
+    /// A stable hash of the fully expanded schema of the structure,
+    /// see [`FrcStructDesc::schema_hash`]
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or the schema of a nested structure, can't be parsed
+    fn schema_hash() -> Result<u64, SchemaParseError> {
+        Self::DESCRIPTION.schema_hash()
+    }
+
+    /// The fully expanded schema of the structure as JSON,
+    /// see [`FrcStructDesc::schema_json`]
+    ///
+    /// # Errors
+    /// Returns an error if the schema, or the schema of a nested structure, can't be parsed
+    fn schema_json() -> Result<String, SchemaParseError> {
+        Self::DESCRIPTION.schema_json()
+    }
+
+    /// Checks that a schema from elsewhere describes the same layout as this structure,
+    /// see [`FrcStructDesc::schema_matches`]
+    #[must_use]
+    fn schema_matches(foreign: &str) -> bool {
+        Self::DESCRIPTION.schema_matches(foreign)
+    }
+
+    /// Checks that this structure can be unpacked from the start of a structure
+    /// described by a schema from elsewhere, see [`FrcStructDesc::schema_compatible`]
+    #[must_use]
+    fn schema_compatible(foreign: &str) -> bool {
+        Self::DESCRIPTION.schema_compatible(foreign)
+    }
+
+    /// Converts the structure into a [`FrcValue::Struct`](crate::value::FrcValue::Struct)
+    #[cfg(feature = "value-union")]
+    #[must_use]
+    fn to_frc_value(&self) -> crate::value::FrcValue {
+        crate::value::FrcValue::from_struct(self)
+    }
+
+    /// Unpacks the structure from a [`FrcValue::Struct`](crate::value::FrcValue::Struct)
+    ///
+    /// # Errors
+    /// Returns an error if the value is not a struct or doesn't hold a valid structure
+    #[cfg(feature = "value-union")]
+    fn from_frc_value(
+        value: &crate::value::FrcValue,
+    ) -> Result<Self, crate::value::FrcValueCastError> {
+        value.try_as_struct()
+    }
+
+    /// Packs the structure into a new buffer of exactly [`SIZE`](FrcStructure::SIZE) bytes
+    #[must_use]
+    fn pack_to_vec(&self) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(Self::SIZE);
+        self.pack(&mut buffer);
+        buffer
+    }
+
+    /// Packs the structure into a new boxed slice of exactly [`SIZE`](FrcStructure::SIZE) bytes
+    #[must_use]
+    fn pack_to_boxed(&self) -> Box<[u8]> {
+        self.pack_to_vec().into_boxed_slice()
+    }
+
+    /// Packs the structure into an array of exactly [`SIZE`](FrcStructure::SIZE) bytes,
+    /// without any of the `WPIlib` framing.
+    ///
+    /// `N` has to be [`SIZE`](FrcStructure::SIZE),
+    /// a mismatch fails to compile once the method is used.
+    ///
+    /// # Example
+    /// ```
+    /// use frclib_core::structure::FrcStructure;
+    ///
+    /// let bytes: [u8; 8] = 1.5f64.pack_fixed();
+    /// assert_eq!(bytes, 1.5f64.to_le_bytes());
+    /// assert_eq!(f64::unpack_fixed(&bytes), 1.5);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use frclib_core::structure::FrcStructure;
+    ///
+    /// let bytes: [u8; 4] = 1.5f64.pack_fixed();
+    /// ```
+    #[must_use]
+    fn pack_fixed<const N: usize>(&self) -> [u8; N] {
+        let () = FixedSize::<Self, N>::ASSERT;
+        let mut buffer = Vec::with_capacity(N);
+        self.pack(&mut buffer);
+        let mut bytes = [0u8; N];
+        for (byte, packed) in bytes.iter_mut().zip(buffer) {
+            *byte = packed;
+        }
+        bytes
+    }
+
+    /// Unpacks the structure from an array of exactly [`SIZE`](FrcStructure::SIZE) bytes,
+    /// the inverse of [`pack_fixed`](FrcStructure::pack_fixed)
+    #[must_use]
+    fn unpack_fixed<const N: usize>(bytes: &[u8; N]) -> Self {
+        Self::unpack_array(bytes)
+    }
+
+    /// Unpacks the structure from an array of exactly [`SIZE`](FrcStructure::SIZE) bytes
+    /// with [`unpack_slice`](FrcStructure::unpack_slice).
+    ///
+    /// `N` has to be [`SIZE`](FrcStructure::SIZE), a mismatch fails to compile once the method is used.
+    /// As the length is known every field offset is in bounds,
+    /// which lets the compiler drop the bounds checks of a decode loop.
+    ///
+    /// # Example
+    /// ```
+    /// use frclib_core::structure::FrcStructure;
+    ///
+    /// let bytes = [1u8, 0, 2, 0];
+    /// assert_eq!(<[u16; 2]>::unpack_array(&bytes), [1, 2]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use frclib_core::structure::FrcStructure;
+    ///
+    /// let value = u32::unpack_array(&[0u8; 2]);
+    /// ```
+    #[inline]
+    #[must_use]
+    fn unpack_array<const N: usize>(bytes: &[u8; N]) -> Self {
+        let () = FixedSize::<Self, N>::ASSERT;
+        Self::unpack_slice(bytes)
+    }
+
     #[must_use]
     #[doc(hidden)]
     fn format_field(field: &str) -> String {
@@ -117,10 +1031,169 @@ where
     }
 }
 
+/// Marks whether the in-memory bytes of `T` are its packed little-endian bytes,
+/// the value of [`FrcStructure::TRIVIALLY_COPYABLE`].
+///
+/// A marker saying yes can only be made with the `unsafe` [`new_unchecked`](Self::new_unchecked),
+/// and it is tied to `T` so one type's marker can't be reused for another.
+///
+/// ```compile_fail
+/// use frclib_core::structure::{FrcStructure, TriviallyCopyable};
+/// use std::io::Cursor;
+///
+/// #[derive(Clone, Copy)]
+/// struct Padded {
+///     flag: u8,
+///     value: u16,
+/// }
+///
+/// impl FrcStructure for Padded {
+///     const TYPE: &'static str = "Padded";
+///     const SIZE: usize = 4;
+///     const SCHEMA_SUPPLIER: fn() -> String = || "uint8 flag; uint8 pad; uint16 value".to_owned();
+///     const TRIVIALLY_COPYABLE: TriviallyCopyable<Self> = <u32 as FrcStructure>::TRIVIALLY_COPYABLE;
+///
+///     fn pack(&self, buffer: &mut Vec<u8>) {
+///         buffer.extend_from_slice(&[self.flag, 0]);
+///         self.value.pack(buffer);
+///     }
+///
+///     fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
+///         let [flag, _] = <[u8; 2]>::unpack(buffer);
+///         Self { flag, value: u16::unpack(buffer) }
+///     }
+/// }
+/// ```
+pub struct TriviallyCopyable<T>(bool, PhantomData<fn() -> T>);
+
+impl<T> TriviallyCopyable<T> {
+    /// The memory of `T` is not its packed bytes, structures are packed field by field.
+    pub const NO: Self = Self(false, PhantomData);
+
+    /// Marks the memory of `T` as its packed little-endian bytes.
+    ///
+    /// # Safety
+    /// `T` must have no padding or other uninitialized bytes,
+    /// and on a little-endian target the memory of every value must be exactly
+    /// the [`FrcStructure::SIZE`] bytes [`FrcStructure::pack`] writes for it.
+    /// [`FrcStructureBytes::pack_many`] reads the memory of a slice of `T` as bytes.
+    #[must_use]
+    pub const unsafe fn new_unchecked() -> Self {
+        Self(true, PhantomData)
+    }
+
+    /// Whether the memory of `T` is its packed bytes
+    #[must_use]
+    pub const fn get(self) -> bool {
+        self.0
+    }
+}
+
+impl<T> Clone for TriviallyCopyable<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TriviallyCopyable<T> {}
+
+impl<T> core::fmt::Debug for TriviallyCopyable<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("TriviallyCopyable").field(&self.0).finish()
+    }
+}
+
+/// Checks at compile time that an array length matches the size of a structure
+struct FixedSize<T, const N: usize>(core::marker::PhantomData<T>);
+
+impl<T: FrcStructure, const N: usize> FixedSize<T, N> {
+    const ASSERT: () = assert!(
+        N == T::SIZE,
+        "the array length doesn't match the size of the structure"
+    );
+}
+
+/// Copies the first `N` bytes of a slice, a shorter slice gives all zeroes
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub fn read_array<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut array = [0u8; N];
+    if let Some(head) = bytes.get(..N) {
+        array.copy_from_slice(head);
+    }
+    array
+}
+
+/// The rest of a slice after `offset`, empty if the slice is shorter
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub fn slice_from(bytes: &[u8], offset: usize) -> &[u8] {
+    bytes.get(offset..).unwrap_or_default()
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Packs `value` in the little-endian order of the struct spec,
+/// whatever [`ENDIAN`](FrcStructure::ENDIAN) it packs in by default.
+#[doc(hidden)]
+#[inline]
+pub fn pack_spec<T: FrcStructure>(value: &T, buffer: &mut Vec<u8>) {
+    if T::ENDIAN == Endianness::Little {
+        value.pack(buffer);
+    } else {
+        value.pack_with_endian(buffer, Endianness::Little);
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Unpacks a structure packed with [`pack_spec`].
+#[doc(hidden)]
+#[inline]
+pub fn unpack_spec<T: FrcStructure>(buffer: &mut Cursor<&[u8]>) -> T {
+    if T::ENDIAN == Endianness::Little {
+        T::unpack(buffer)
+    } else {
+        T::unpack_with_endian(buffer, Endianness::Little)
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Unpacks and checks a structure packed with [`pack_spec`].
+///
+/// # Errors
+/// Returns an error if the buffer is too short or contains an invalid enum discriminant
+#[doc(hidden)]
+#[inline]
+pub fn try_unpack_spec<T: FrcStructure>(
+    buffer: &mut Cursor<&[u8]>,
+) -> Result<T, StructUnpackError> {
+    if T::ENDIAN == Endianness::Little {
+        T::try_unpack(buffer)
+    } else {
+        T::try_unpack_with_endian(buffer, Endianness::Little)
+    }
+}
+
+/// The number of bytes left to read in a cursor
+#[doc(hidden)]
+#[must_use]
+pub fn remaining(buffer: &Cursor<&[u8]>) -> usize {
+    let position = usize::try_from(buffer.position()).unwrap_or(usize::MAX);
+    buffer.get_ref().len().saturating_sub(position)
+}
+
 /// A way of defining any number of same typed [``FrcStructure``]s
 /// in a single binary heap.
 ///
 /// The type information and struct count is also coupled with the binary data
+///
+/// With the `serde` feature it serializes as its `type_str`, `count` and `data`,
+/// deserializing looks the description up in the [`FrcStructDescDB`]
+/// so the type has to be registered first.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FrcStructureBytes {
     /// The description of the structure types and layout
@@ -132,252 +1205,415 @@ pub struct FrcStructureBytes {
 }
 impl FrcStructureBytes {
     /// Creates a new [``FrcStructureBytes``] from a description, count, and data
+    /// without checking that `data` is `count` structures long,
+    /// use [`try_from_parts`](FrcStructureBytes::try_from_parts) for untrusted data.
+    #[must_use]
+    pub const fn from_parts(desc: &'static FrcStructDesc, count: usize, data: Box<[u8]>) -> Self {
+        Self { desc, count, data }
+    }
+
+    /// Packs every item into a single [``FrcStructureBytes``]
+    ///
+    /// The buffer is sized for all of the items up front,
+    /// so building a large frame doesn't reallocate as it grows.
+    ///
+    /// [`TRIVIALLY_COPYABLE`](FrcStructure::TRIVIALLY_COPYABLE) structures
+    /// are copied straight from memory on little-endian targets instead of packed field by field.
+    #[must_use]
+    pub fn pack_many<T: FrcStructure>(items: &[T]) -> Self {
+        if T::TRIVIALLY_COPYABLE.get()
+            && Endianness::NATIVE == Endianness::Little
+            && core::mem::size_of::<T>() == T::SIZE
+        {
+            // SAFETY: a `TriviallyCopyable` marker saying yes can only be made through
+            // `TriviallyCopyable::new_unchecked`, whose contract is that `T` has no padding or
+            // uninitialized bytes and that its memory on a little-endian target is its packed bytes,
+            // so every byte of the slice is initialized and the pointer and length come from `items`
+            let bytes = unsafe {
+                core::slice::from_raw_parts(
+                    items.as_ptr().cast::<u8>(),
+                    core::mem::size_of_val(items),
+                )
+            };
+            return Self::from_parts(&T::DESCRIPTION, items.len(), bytes.into());
+        }
+        let mut buffer = Vec::with_capacity(T::SIZE.saturating_mul(items.len()));
+        for item in items {
+            pack_spec(item, &mut buffer);
+        }
+        Self::from_parts(&T::DESCRIPTION, items.len(), buffer.into_boxed_slice())
+    }
+
+    /// Creates a new [``FrcStructureBytes``] from a description, count, and data
+    ///
+    /// # Errors
+    /// Returns an error if the length of `data` is not the size of the structure multiplied by `count`
+    pub fn try_from_parts(
+        desc: &'static FrcStructDesc,
+        count: usize,
+        data: Box<[u8]>,
+    ) -> Result<Self, FrcStructureError> {
+        let expected = desc.size.saturating_mul(count);
+        if data.len() != expected {
+            return Err(FrcStructureError::LengthMismatch {
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(Self::from_parts(desc, count, data))
+    }
+
+    /// Borrows the structures as a [`FrcStructureBytesRef`]
+    #[must_use]
+    pub fn as_borrowed(&self) -> FrcStructureBytesRef<'_> {
+        FrcStructureBytesRef::from_parts(self.desc, self.count, &self.data)
+    }
+
+    /// Iterates over the stored structures
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type or the data is not `count` structures long
+    #[allow(clippy::iter_not_returning_iterator)]
+    pub fn iter<T: FrcStructure>(&self) -> Result<impl Iterator<Item = T> + '_, FrcStructureError> {
+        self.as_borrowed().iter()
+    }
+
+    /// Unpacks all of the stored structures
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type, the data is not `count` structures long
+    /// or a structure could not be unpacked
+    pub fn to_vec<T: FrcStructure>(&self) -> Result<Vec<T>, FrcStructureError> {
+        self.as_borrowed().to_vec()
+    }
+
+    /// Unpacks all of the stored structures in parallel with [`rayon`],
+    /// every structure is unpacked from its own chunk of the data so the result matches [`to_vec`](Self::to_vec).
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type, the data is not `count` structures long
+    /// or a structure could not be unpacked
+    #[cfg(feature = "rayon")]
+    pub fn par_to_vec<T: FrcStructure + Send>(&self) -> Result<Vec<T>, FrcStructureError> {
+        self.as_borrowed().par_to_vec()
+    }
+
+    /// Checks the structural invariants of the data without unpacking it,
+    /// see [`FrcStructureBytesRef::validate`]
+    ///
+    /// # Errors
+    /// Returns the first invariant that doesn't hold
+    pub fn validate(&self) -> Result<(), FrcStructureError> {
+        self.as_borrowed().validate()
+    }
+
+    /// Compares the fields of the structures instead of their raw bytes,
+    /// bytes the schema doesn't cover are ignored and every non-zero `bool` is `true`
+    ///
+    /// The derived [`PartialEq`] is still a byte-exact comparison.
+    /// Structures whose schema can't be parsed are compared byte for byte.
     #[must_use]
-    pub fn from_parts(desc: &'static FrcStructDesc, count: usize, data: Box<[u8]>) -> Self {
+    pub fn semantic_eq(&self, other: &Self) -> bool {
+        if self.desc.type_str != other.desc.type_str
+            || self.count != other.count
+            || self.data.len() != other.data.len()
+        {
+            return false;
+        }
+        let size = self.desc.size;
+        let fields = schema::packed_fields(self.desc.type_str, self.desc.schema(), size);
+        if fields.is_empty() || size == 0 {
+            return self.data == other.data;
+        }
+        self.data
+            .chunks(size)
+            .zip(other.data.chunks(size))
+            .all(|(this, that)| {
+                fields.iter().all(|(offset, field_type, bit)| {
+                    let range = *offset..offset + field_type.size();
+                    match (this.get(range.clone()), that.get(range), bit) {
+                        (Some([this]), Some([that]), Some(bit)) => {
+                            (this >> bit & 1) == (that >> bit & 1)
+                        }
+                        (Some(this), Some(that), _)
+                            if matches!(field_type, StructureFieldTypes::Bool(_)) =>
+                        {
+                            this.iter()
+                                .zip(that)
+                                .all(|(this, that)| (*this != 0) == (*that != 0))
+                        }
+                        (this, that, _) => this == that,
+                    }
+                })
+            })
+    }
+
+    /// Unpacks the structure at `index` without unpacking the others
+    ///
+    /// Returns `None` if `T` is not the stored type, `index` is not less than `count`
+    /// or the structure could not be unpacked.
+    #[must_use]
+    pub fn get<T: FrcStructure>(&self, index: usize) -> Option<T> {
+        self.as_borrowed().get(index)
+    }
+
+    /// Splits the structures into one [`FrcStructureBytes`] per structure,
+    /// each with a `count` of 1
+    ///
+    /// Structures past the end of `data` are left out.
+    #[must_use]
+    pub fn split(&self) -> Vec<Self> {
+        let size = self.desc.size;
+        (0..self.count)
+            .map_while(|index| self.data.get(index * size..(index + 1) * size))
+            .map(|bytes| Self::from_parts(self.desc, 1, bytes.into()))
+            .collect()
+    }
+
+    /// Appends the structures of `other` to the end of these structures
+    ///
+    /// `data` is a boxed slice so every append reallocates it,
+    /// collect into a `Vec` first when building up many structures at once.
+    ///
+    /// # Errors
+    /// Returns an error if `other` holds a different type of structure
+    pub fn try_append(&mut self, other: &Self) -> Result<(), FrcStructureError> {
+        if self.desc.type_str != other.desc.type_str || self.desc.size != other.desc.size {
+            return Err(FrcStructureError::TypeMismatch {
+                expected: self.desc.type_str,
+                actual: other.desc.type_str,
+            });
+        }
+        self.extend(&other.data, other.count);
+        Ok(())
+    }
+
+    /// Packs `value` onto the end of these structures
+    ///
+    /// Like [`try_append`](FrcStructureBytes::try_append) this reallocates `data`.
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type
+    pub fn push<T: FrcStructure>(&mut self, value: &T) -> Result<(), FrcStructureError> {
+        if T::TYPE != self.desc.type_str || T::SIZE != self.desc.size {
+            return Err(FrcStructureError::TypeMismatch {
+                expected: self.desc.type_str,
+                actual: T::TYPE,
+            });
+        }
+        let mut packed = Vec::with_capacity(T::SIZE);
+        pack_spec(value, &mut packed);
+        self.extend(&packed, 1);
+        Ok(())
+    }
+
+    fn extend(&mut self, bytes: &[u8], count: usize) {
+        let mut data = core::mem::take(&mut self.data).into_vec();
+        data.extend_from_slice(bytes);
+        self.data = data.into_boxed_slice();
+        self.count += count;
+    }
+}
+
+/// Structures packed like [`FrcStructureBytes`] in a borrowed buffer,
+/// for decoding frames in place without copying them into a `Box` first.
+///
+/// # Example
+/// ```
+/// use frclib_core::structure::{FrcStructure, FrcStructureBytesRef};
+///
+/// let frame = [1.5f64.to_le_bytes(), (-2.0f64).to_le_bytes()].concat();
+/// let bytes = FrcStructureBytesRef::try_from_parts(&f64::DESCRIPTION, 2, &frame)
+///     .expect("two float64s");
+/// assert_eq!(bytes.to_vec::<f64>(), Ok(vec![1.5, -2.0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrcStructureBytesRef<'a> {
+    /// The description of the structure types and layout
+    pub desc: &'static FrcStructDesc,
+    /// The number of structs packed into `data`
+    pub count: usize,
+    /// The binary data of the structs
+    pub data: &'a [u8],
+}
+
+impl<'a> FrcStructureBytesRef<'a> {
+    /// Creates a new [``FrcStructureBytesRef``] from a description, count, and data
+    /// without checking that `data` is `count` structures long,
+    /// use [`try_from_parts`](FrcStructureBytesRef::try_from_parts) for untrusted data.
+    #[must_use]
+    pub const fn from_parts(desc: &'static FrcStructDesc, count: usize, data: &'a [u8]) -> Self {
         Self { desc, count, data }
     }
+
+    /// Creates a new [``FrcStructureBytesRef``] from a description, count, and data
+    ///
+    /// # Errors
+    /// Returns an error if the length of `data` is not the size of the structure multiplied by `count`
+    pub const fn try_from_parts(
+        desc: &'static FrcStructDesc,
+        count: usize,
+        data: &'a [u8],
+    ) -> Result<Self, FrcStructureError> {
+        let expected = desc.size.saturating_mul(count);
+        if data.len() != expected {
+            return Err(FrcStructureError::LengthMismatch {
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(Self::from_parts(desc, count, data))
+    }
+
+    /// Copies the structures into an owned [`FrcStructureBytes`]
+    #[must_use]
+    pub fn to_owned_bytes(self) -> FrcStructureBytes {
+        FrcStructureBytes::from_parts(self.desc, self.count, self.data.into())
+    }
+
+    /// Checks that `T` is the stored type and that the data holds `count` of them
+    fn check_type<T: FrcStructure>(self) -> Result<(), FrcStructureError> {
+        if T::TYPE != self.desc.type_str || T::SIZE != self.desc.size {
+            return Err(FrcStructureError::TypeMismatch {
+                expected: T::TYPE,
+                actual: self.desc.type_str,
+            });
+        }
+        // `count` is public, a crafted one must not wrap around to the length of the data
+        let expected = T::SIZE.checked_mul(self.count);
+        if expected != Some(self.data.len()) {
+            return Err(FrcStructureError::LengthMismatch {
+                expected: expected.unwrap_or(usize::MAX),
+                actual: self.data.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Iterates over the stored structures
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type or the data is not `count` structures long
+    #[allow(clippy::iter_not_returning_iterator)]
+    pub fn iter<T: FrcStructure>(self) -> Result<impl Iterator<Item = T> + 'a, FrcStructureError> {
+        self.check_type::<T>()?;
+        let mut cursor = Cursor::new(self.data);
+        Ok((0..self.count).map(move |_| unpack_spec(&mut cursor)))
+    }
+
+    /// Unpacks all of the stored structures
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type, the data is not `count` structures long
+    /// or a structure could not be unpacked
+    pub fn to_vec<T: FrcStructure>(self) -> Result<Vec<T>, FrcStructureError> {
+        self.check_type::<T>()?;
+        let mut cursor = Cursor::new(self.data);
+        (0..self.count)
+            .map(|_| try_unpack_spec(&mut cursor).map_err(FrcStructureError::from))
+            .collect()
+    }
+
+    /// Unpacks all of the stored structures in parallel with [`rayon`],
+    /// every structure is unpacked from its own chunk of the data so the result matches [`to_vec`](Self::to_vec).
+    ///
+    /// # Errors
+    /// Returns an error if `T` is not the stored type, the data is not `count` structures long
+    /// or a structure could not be unpacked
+    #[cfg(feature = "rayon")]
+    pub fn par_to_vec<T: FrcStructure + Send>(self) -> Result<Vec<T>, FrcStructureError> {
+        use rayon::prelude::*;
+
+        self.check_type::<T>()?;
+        // zero sized structures can't be chunked
+        if T::SIZE == 0 {
+            return self.to_vec();
+        }
+        // collecting into an indexed vec writes every result in place,
+        // collecting into a `Result` would go through a list of per-thread vecs
+        let mut unpacked = Vec::with_capacity(self.count);
+        self.data
+            .par_chunks_exact(T::SIZE)
+            .map(|chunk| try_unpack_spec(&mut Cursor::new(chunk)))
+            .collect_into_vec(&mut unpacked);
+        unpacked
+            .into_iter()
+            .map(|result| result.map_err(FrcStructureError::from))
+            .collect()
+    }
+
+    /// Checks the structural invariants of the data without unpacking it,
+    /// a cheap check of frames from untrusted peers before decoding them.
+    ///
+    /// The data has to be `count` structures long and every integer field annotated with
+    /// `enum {..}` in the schema, including those of nested structures, has to hold one of its variants.
+    /// Annotations without variants don't restrict the field.
+    ///
+    /// # Errors
+    /// Returns [`FrcStructureError::LengthMismatch`] if the data isn't `count` structures long,
+    /// [`FrcStructureError::InvalidEnumValue`] with the field and offset of the first field
+    /// that isn't one of its variants, or an error if the schema can't be parsed
+    /// or describes a different size than the description
+    pub fn validate(self) -> Result<(), FrcStructureError> {
+        let _ = Self::try_from_parts(self.desc, self.count, self.data)?;
+        let schema = self.desc.schema();
+        if schema.is_empty() || self.desc.size == 0 {
+            return Ok(());
+        }
+        let (size, enums) = schema::enum_fields(schema)?;
+        if size != self.desc.size {
+            return Err(SchemaParseError::SizeMismatch {
+                type_str: self.desc.type_str.to_owned(),
+                expected: self.desc.size,
+                actual: size,
+            }
+            .into());
+        }
+        for (index, structure) in self.data.chunks_exact(self.desc.size).enumerate() {
+            for (field, values) in &enums {
+                let field_type = field.field_type;
+                for element in 0..field_type.count() {
+                    let offset = field.offset + element * field_type.base_size();
+                    let Some(value) =
+                        schema::read_integer(field_type, slice_from(structure, offset))
+                    else {
+                        continue;
+                    };
+                    if values.iter().any(|variant| i128::from(*variant) == value) {
+                        continue;
+                    }
+                    return Err(FrcStructureError::InvalidEnumValue {
+                        field: if field_type.count() == 1 {
+                            field.name.clone()
+                        } else {
+                            format!("{}[{element}]", field.name)
+                        },
+                        offset: index * self.desc.size + offset,
+                        value,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Unpacks the structure at `index` without unpacking the others
+    ///
+    /// Returns `None` if `T` is not the stored type, `index` is not less than `count`
+    /// or the structure could not be unpacked.
+    #[must_use]
+    pub fn get<T: FrcStructure>(self, index: usize) -> Option<T> {
+        if T::TYPE != self.desc.type_str || T::SIZE != self.desc.size || index >= self.count {
+            return None;
+        }
+        let start = index.checked_mul(T::SIZE)?;
+        let bytes = self.data.get(start..start.checked_add(T::SIZE)?)?;
+        try_unpack_spec(&mut Cursor::new(bytes)).ok()
+    }
 }
 
-/// A set length string of characters
-pub type StructString<const N: usize> = [char; N];
-
-// #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-// pub(crate) enum StructureFieldTypes {
-//     Bool(usize),
-//     Char(usize),
-//     Int8(usize),
-//     Int16(usize),
-//     Int32(usize),
-//     Int64(usize),
-//     UInt8(usize),
-//     UInt16(usize),
-//     UInt32(usize),
-//     UInt64(usize),
-//     Float32(usize),
-//     Float64(usize),
-// }
-
-// impl StructureFieldTypes {
-//     #[allow(clippy::match_same_arms)]
-//     const fn base_size(&self) -> usize {
-//         match self {
-//             Self::Bool(_) => 1,
-//             Self::Char(_) => 1,
-//             Self::Int8(_) => 1,
-//             Self::Int16(_) => 2,
-//             Self::Int32(_) => 4,
-//             Self::Int64(_) => 8,
-//             Self::UInt8(_) => 1,
-//             Self::UInt16(_) => 2,
-//             Self::UInt32(_) => 4,
-//             Self::UInt64(_) => 8,
-//             Self::Float32(_) => 4,
-//             Self::Float64(_) => 8,
-//         }
-//     }
-
-//     const fn count(&self) -> usize {
-//         match self {
-//             Self::Bool(c)
-//             | Self::Char(c)
-//             | Self::Int8(c)
-//             | Self::Int16(c)
-//             | Self::Int32(c)
-//             | Self::Int64(c)
-//             | Self::UInt8(c)
-//             | Self::UInt16(c)
-//             | Self::UInt32(c)
-//             | Self::UInt64(c)
-//             | Self::Float32(c)
-//             | Self::Float64(c) => *c,
-//         }
-//     }
-
-//     const fn size(&self) -> usize {
-//         self.base_size() * self.count()
-//     }
-
-//     fn from_type(type_name: &str, count: usize) -> Option<Self> {
-//         match type_name {
-//             "bool" => Some(Self::Bool(count)),
-//             "char" => Some(Self::Char(count)),
-//             "int8" => Some(Self::Int8(count)),
-//             "int16" => Some(Self::Int16(count)),
-//             "int32" => Some(Self::Int32(count)),
-//             "int64" => Some(Self::Int64(count)),
-//             "uint8" => Some(Self::UInt8(count)),
-//             "uint16" => Some(Self::UInt16(count)),
-//             "uint32" => Some(Self::UInt32(count)),
-//             "uint64" => Some(Self::UInt64(count)),
-//             "float" | "float32" => Some(Self::Float32(count)),
-//             "double" | "float64" => Some(Self::Float64(count)),
-//             _ => None,
-//         }
-//     }
-// }
-
-// #[derive(Default, Debug, Clone, PartialEq)]
-// pub(crate) enum LexingError {
-//     ParseNumberError,
-//     EnumVariantError,
-//     #[default]
-//     Other,
-// }
-// impl From<std::num::ParseIntError> for LexingError {
-//     fn from(_: std::num::ParseIntError) -> Self {
-//         Self::ParseNumberError
-//     }
-// }
-
-// #[derive(logos::Logos, Debug, PartialEq, Eq, PartialOrd, Ord)]
-// #[logos(error = LexingError)]
-// #[logos(skip r"[ \t\n\f]+")]
-// pub(crate) enum Token<'a> {
-//     #[regex(
-//         r"bool|char|int8|int16|int32|int64|uint8|uint16|uint32|uint64|float32|float64|float|double",
-//         |lex| lex.slice(), priority = 3)]
-//     TypeName(&'a str),
-
-//     #[token("enum")]
-//     EnumKeyword,
-
-//     #[regex(
-//         r"[-a-zA-Z_][a-zA-Z0-9_-]*=-?[0-9]+",
-//         |lex| {
-//             let split = lex.slice().split('=').collect::<Vec<_>>();
-//             Ok::<_, LexingError>((
-//                 *split.first().ok_or(LexingError::EnumVariantError)?,
-//                 split.get(1).ok_or(LexingError::EnumVariantError)?.parse::<i8>()?
-//             ))
-//         }, priority = 3)]
-//     EnumVariant((&'a str, i8)),
-
-//     #[regex(r"[0-9]+", |lex| lex.slice().parse(), priority = 2)]
-//     Integer(u32),
-
-//     #[regex(r"[-a-zA-Z_][a-zA-Z0-9_-]*", |lex| lex.slice())]
-//     Ident(&'a str),
-
-//     #[token("{")]
-//     OpenBrace,
-//     #[token("}")]
-//     CloseBrace,
-//     #[token("[")]
-//     OpenBracket,
-//     #[token("]")]
-//     CloseBracket,
-//     #[token(",")]
-//     Comma,
-//     #[token(";")]
-//     Semicolon,
-//     #[token(":")]
-//     Colon,
-// }
-
-// #[allow(dead_code)]
-// pub(crate) fn parse_schema_toplevel(
-//     schema: &str,
-// ) -> Vec<(String, usize, StructureFieldTypes)> {
-//     parse_schema(schema, "", 0)
-// }
-
-// #[allow(dead_code)]
-// pub(crate) fn parse_schema(
-//     schema: &str,
-//     prefix: &str,
-//     offset: usize,
-// ) -> Vec<(String, usize, StructureFieldTypes)> {
-//     let lexer = Token::lexer(schema);
-//     let tokens_collect: Vec<_> = lexer.collect();
-//     for tok in &tokens_collect {
-//         if tok.is_err() {
-//             return vec![];
-//         }
-//     }
-//     let tokens = tokens_collect.into_iter();
-//     let mut cursor = offset;
-//     tokens
-//         .map(|token| token.expect("Lexing Token Slipped Past"))
-//         .filter(|token| {
-//             matches!(
-//                 token,
-//                 Token::Ident(_) | Token::Integer(_) | Token::TypeName(_) | Token::Semicolon
-//             )
-//         })
-//         .collect::<Vec<_>>()
-//         .split(|token| token == &Token::Semicolon)
-//         .filter_map(|field_tokens| {
-//             if field_tokens.len() < 2 || field_tokens.len() > 3 {
-//                 return None;
-//             }
-
-//             let Token::Ident(ident) = field_tokens[1] else {
-//                 return None;
-//             };
-
-//             match field_tokens[0] {
-//                 Token::Ident(sub_struct) => {
-//                     if let Some(desc) = FrcStructDescDB::get(sub_struct) {
-//                         let ret = parse_schema(
-//                             &(desc.schema_supplier)(),
-//                             format!("{ident}.").as_str(),
-//                             cursor,
-//                         );
-//                         cursor += desc.size;
-//                         return Some(ret);
-//                     }
-//                 }
-//                 Token::TypeName(type_name) => {
-//                     let count = match field_tokens.get(2) {
-//                         Some(Token::Integer(int)) => *int as usize,
-//                         _ => 1,
-//                     };
-//                     if let Some(stype) = StructureFieldTypes::from_type(type_name, count) {
-//                         let ret = vec![(format!("{prefix}{ident}"), cursor, stype)];
-//                         cursor += stype.size();
-//                         return Some(ret);
-//                     }
-//                 }
-//                 _ => {}
-//             }
-//             None::<Vec<(String, usize, StructureFieldTypes)>>
-//         })
-//         .flatten()
-//         .collect()
-// }
-
-// pub struct DynamicStructure {
-//     desc: &'static FrcStructDesc,
-//     buffer: BytesMut,
-//     _map: HashMap<String, (usize, StructureFieldTypes), fxhash::FxBuildHasher>,
-// }
-
-// impl DynamicStructure {
-//     pub fn try_new(desc: &'static FrcStructDesc, buffer: BytesMut) -> Result<Self, String> {
-//         if buffer.len() != desc.size {
-//             return Err(format!(
-//                 "Buffer size ({}) does not match structure size ({})",
-//                 buffer.len(),
-//                 desc.size
-//             ));
-//         }
-//         let mut map = HashMap::with_hasher(fxhash::FxBuildHasher::default());
-//         for field in parse_schema_toplevel(desc.schema) {
-//             map.insert(field.0, (field.1, field.2));
-//         }
-//         Ok(DynamicStructure {
-//             desc,
-//             buffer,
-//             _map: map,
-//         })
-//     }
-
-//     pub fn description(&self) -> &'static FrcStructDesc {
-//         self.desc
-//     }
-
-//     pub fn update(&mut self, new: Box<Bytes>) {
-//         debug_assert!(new.len() == self.buffer.len());
-//         self.buffer[..].copy_from_slice(&new[..]);
-//     }
-// }
+impl<'a> From<&'a FrcStructureBytes> for FrcStructureBytesRef<'a> {
+    fn from(bytes: &'a FrcStructureBytes) -> Self {
+        bytes.as_borrowed()
+    }
+}
@@ -1,7 +1,30 @@
-use std::io::{Cursor, Read};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io::Read;
 
 use num::traits::{FromBytes, ToBytes};
 
+use super::{Cursor, Endianness};
+
+/// Builds the `GENERIC_NAME` of `[T; N]` at compile time
+struct ArrayName<T, const N: usize>(PhantomData<T>);
+
+impl<T: super::FrcStructure, const N: usize> ArrayName<T, N> {
+    const NAME: &'static [u8; super::GENERIC_TYPE_LEN] =
+        &super::array_type_name(T::GENERIC_NAME, N);
+}
+
+/// Builds the `TYPE` of `Option<T>` at compile time
+struct OptionName<T>(PhantomData<T>);
+
+impl<T: super::FrcStructure> OptionName<T> {
+    const NAME: &'static [u8; super::GENERIC_TYPE_LEN] =
+        &super::join_type_name(&["Option", T::GENERIC_NAME]);
+}
+
 fn empty_schema_supplier() -> String {
     String::with_capacity(0)
 }
@@ -12,6 +35,10 @@ macro_rules! prim_structure {
             const SCHEMA_SUPPLIER: fn() -> String = empty_schema_supplier;
             const TYPE: &'static str = $string;
             const SIZE: usize = core::mem::size_of::<Self>();
+            // SAFETY: numeric primitives have no padding
+            // and their memory on a little-endian target is their little-endian bytes
+            const TRIVIALLY_COPYABLE: super::TriviallyCopyable<Self> =
+                unsafe { super::TriviallyCopyable::new_unchecked() };
 
             #[inline]
             fn pack(&self, buffer: &mut Vec<u8>) {
@@ -24,6 +51,33 @@ macro_rules! prim_structure {
                 let _ = buffer.read_exact(&mut value_buffer);
                 <Self as FromBytes>::from_le_bytes(&value_buffer)
             }
+
+            #[inline]
+            fn unpack_slice(bytes: &[u8]) -> Self {
+                <Self as FromBytes>::from_le_bytes(&super::read_array(bytes))
+            }
+
+            #[inline]
+            fn pack_with_endian(&self, buffer: &mut Vec<u8>, endian: Endianness) {
+                match endian {
+                    Endianness::Little => self.pack(buffer),
+                    Endianness::Big => {
+                        buffer.extend_from_slice(&<Self as ToBytes>::to_be_bytes(self));
+                    }
+                }
+            }
+
+            #[inline]
+            fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, endian: Endianness) -> Self {
+                match endian {
+                    Endianness::Little => Self::unpack(buffer),
+                    Endianness::Big => {
+                        let mut value_buffer = [0u8; Self::SIZE];
+                        let _ = buffer.read_exact(&mut value_buffer);
+                        <Self as FromBytes>::from_be_bytes(&value_buffer)
+                    }
+                }
+            }
         }
     };
 }
@@ -39,6 +93,7 @@ prim_structure!(u32, "uint32");
 prim_structure!(u16, "uint16");
 prim_structure!(u8, "uint8");
 
+/// Packed as a single byte of `0` or `1`, any non-zero byte unpacks as `true`
 impl super::FrcStructure for bool {
     const SCHEMA_SUPPLIER: fn() -> String = empty_schema_supplier;
     const TYPE: &'static str = "bool";
@@ -53,21 +108,60 @@ impl super::FrcStructure for bool {
         let _ = buffer.read_exact(&mut value_buffer);
         value_buffer[0] != 0
     }
+    #[inline]
+    fn unpack_slice(bytes: &[u8]) -> Self {
+        bytes.first().is_some_and(|byte| *byte != 0)
+    }
+    #[inline]
+    fn pack_with_endian(&self, buffer: &mut Vec<u8>, _: Endianness) {
+        self.pack(buffer);
+    }
+    #[inline]
+    fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, _: Endianness) -> Self {
+        Self::unpack(buffer)
+    }
 }
 
+/// Packed as a single byte like the struct spec's `char`,
+/// only ASCII fits in one byte so any other character is packed as `?`.
+/// Bytes that aren't ASCII unpack as [`char::REPLACEMENT_CHARACTER`].
 impl super::FrcStructure for char {
     const SCHEMA_SUPPLIER: fn() -> String = empty_schema_supplier;
     const TYPE: &'static str = "char";
     const SIZE: usize = 1;
     #[inline]
     fn pack(&self, buffer: &mut Vec<u8>) {
-        buffer.push(*self as u8);
+        buffer.push(
+            u8::try_from(*self)
+                .ok()
+                .filter(u8::is_ascii)
+                .unwrap_or(b'?'),
+        );
     }
     #[inline]
     fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
         let mut value_buffer = [0u8; Self::SIZE];
         let _ = buffer.read_exact(&mut value_buffer);
-        value_buffer[0] as Self
+        if value_buffer[0].is_ascii() {
+            Self::from(value_buffer[0])
+        } else {
+            Self::REPLACEMENT_CHARACTER
+        }
+    }
+    #[inline]
+    fn unpack_slice(bytes: &[u8]) -> Self {
+        match bytes.first() {
+            Some(byte) if !byte.is_ascii() => Self::REPLACEMENT_CHARACTER,
+            byte => Self::from(byte.copied().unwrap_or_default()),
+        }
+    }
+    #[inline]
+    fn pack_with_endian(&self, buffer: &mut Vec<u8>, _: Endianness) {
+        self.pack(buffer);
+    }
+    #[inline]
+    fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, _: Endianness) -> Self {
+        Self::unpack(buffer)
     }
 }
 
@@ -78,6 +172,15 @@ where
     const TYPE: &'static str = T::TYPE;
     const SIZE: usize = T::SIZE * N;
     const SCHEMA_SUPPLIER: fn() -> String = empty_schema_supplier;
+    const GENERIC_NAME: &'static str = super::type_name_str(ArrayName::<T, N>::NAME);
+    const ENDIAN: Endianness = T::ENDIAN;
+    const TRIVIALLY_COPYABLE: super::TriviallyCopyable<Self> = if T::TRIVIALLY_COPYABLE.get() {
+        // SAFETY: arrays have no padding between elements
+        // and every element is laid out like its packed bytes
+        unsafe { super::TriviallyCopyable::new_unchecked() }
+    } else {
+        super::TriviallyCopyable::NO
+    };
 
     #[inline]
     fn pack(&self, buffer: &mut Vec<u8>) {
@@ -88,19 +191,167 @@ where
 
     #[inline]
     fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
-        let mut arr = [T::unpack(buffer); N];
-        let mut is_first = true;
-        for item in &mut arr {
-            if is_first {
-                is_first = false;
-            } else {
-                *item = T::unpack(buffer);
-            }
+        // elements are unpacked in index order
+        core::array::from_fn(|_| T::unpack(buffer))
+    }
+
+    #[inline]
+    fn unpack_slice(bytes: &[u8]) -> Self {
+        core::array::from_fn(|index| T::unpack_slice(super::slice_from(bytes, index * T::SIZE)))
+    }
+
+    #[inline]
+    fn pack_with_endian(&self, buffer: &mut Vec<u8>, endian: Endianness) {
+        for item in self {
+            item.pack_with_endian(buffer, endian);
+        }
+    }
+
+    #[inline]
+    fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, endian: Endianness) -> Self {
+        core::array::from_fn(|_| T::unpack_with_endian(buffer, endian))
+    }
+
+    fn try_unpack(buffer: &mut Cursor<&[u8]>) -> Result<Self, super::StructUnpackError> {
+        let available = super::remaining(buffer);
+        if available < Self::SIZE {
+            return Err(super::StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
+        }
+        // validate every element before unpacking so invalid enums are reported
+        let start = buffer.position();
+        for _ in 0..N {
+            let _ = T::try_unpack(buffer)?;
         }
-        arr
+        buffer.set_position(start);
+        Ok(Self::unpack(buffer))
+    }
+
+    fn try_unpack_with_endian(
+        buffer: &mut Cursor<&[u8]>,
+        endian: Endianness,
+    ) -> Result<Self, super::StructUnpackError> {
+        let available = super::remaining(buffer);
+        if available < Self::SIZE {
+            return Err(super::StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
+        }
+        let start = buffer.position();
+        for _ in 0..N {
+            let _ = T::try_unpack_with_endian(buffer, endian)?;
+        }
+        buffer.set_position(start);
+        Ok(Self::unpack_with_endian(buffer, endian))
     }
 
     fn format_field(field: &str) -> String {
         format!("{} {}[{}]", T::TYPE, field, N)
     }
 }
+
+/// An optional structure is packed as a `bool` presence flag followed by the structure,
+/// the structure's bytes are zeroed when it is absent.
+///
+/// This is an frclib extension, the `WPIlib` struct spec has no optional fields.
+/// In a schema the flag is named after the field with a `_present` suffix.
+/// On its own, like in [`FrcStructureBytes`](super::FrcStructureBytes), it is its own type
+/// named `Option_` followed by the wrapped type with the schema `bool present; T value`.
+impl<T> super::FrcStructure for Option<T>
+where
+    T: super::FrcStructure + Copy,
+{
+    const TYPE: &'static str = super::type_name_str(OptionName::<T>::NAME);
+    const SIZE: usize = 1 + T::SIZE;
+    const SCHEMA_SUPPLIER: fn() -> String =
+        || format!("bool present; {}", T::format_field("value"));
+    const ENDIAN: Endianness = T::ENDIAN;
+
+    #[inline]
+    fn pack(&self, buffer: &mut Vec<u8>) {
+        self.pack_with_endian(buffer, Endianness::Little);
+    }
+
+    #[inline]
+    fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
+        Self::unpack_with_endian(buffer, Endianness::Little)
+    }
+
+    #[inline]
+    fn unpack_slice(bytes: &[u8]) -> Self {
+        if bool::unpack_slice(bytes) {
+            Some(T::unpack_slice(super::slice_from(bytes, 1)))
+        } else {
+            None
+        }
+    }
+
+    fn pack_with_endian(&self, buffer: &mut Vec<u8>, endian: Endianness) {
+        self.is_some().pack(buffer);
+        match self {
+            Some(value) => value.pack_with_endian(buffer, endian),
+            None => buffer.resize(buffer.len() + T::SIZE, 0),
+        }
+    }
+
+    fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, endian: Endianness) -> Self {
+        if bool::unpack(buffer) {
+            Some(T::unpack_with_endian(buffer, endian))
+        } else {
+            skip(buffer, T::SIZE);
+            None
+        }
+    }
+
+    fn try_unpack(buffer: &mut Cursor<&[u8]>) -> Result<Self, super::StructUnpackError> {
+        let available = super::remaining(buffer);
+        if available < Self::SIZE {
+            return Err(super::StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
+        }
+        if bool::unpack(buffer) {
+            T::try_unpack(buffer).map(Some)
+        } else {
+            skip(buffer, T::SIZE);
+            Ok(None)
+        }
+    }
+
+    fn try_unpack_with_endian(
+        buffer: &mut Cursor<&[u8]>,
+        endian: Endianness,
+    ) -> Result<Self, super::StructUnpackError> {
+        let available = super::remaining(buffer);
+        if available < Self::SIZE {
+            return Err(super::StructUnpackError::InsufficientBytes {
+                type_str: Self::TYPE,
+                needed: Self::SIZE,
+                available,
+            });
+        }
+        if bool::unpack(buffer) {
+            T::try_unpack_with_endian(buffer, endian).map(Some)
+        } else {
+            skip(buffer, T::SIZE);
+            Ok(None)
+        }
+    }
+
+    fn format_field(field: &str) -> String {
+        format!("bool {}_present; {}", field, T::format_field(field))
+    }
+}
+
+/// Advances the cursor by `count` bytes
+fn skip(buffer: &mut Cursor<&[u8]>, count: usize) {
+    let count = u64::try_from(count).unwrap_or(u64::MAX);
+    buffer.set_position(buffer.position().saturating_add(count));
+}
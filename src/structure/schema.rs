@@ -0,0 +1,792 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use logos::Logos;
+
+use super::{FrcStructDesc, FrcStructDescDB, SchemaParseError};
+
+/// The primitive type of a field in a parsed schema along with its array count,
+/// a count of 1 is used for fields that are not arrays
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StructureFieldTypes {
+    /// A `bool`, 1 byte
+    Bool(usize),
+    /// A `char`, 1 byte
+    Char(usize),
+    /// An `int8`, 1 byte
+    Int8(usize),
+    /// An `int16`, 2 bytes
+    Int16(usize),
+    /// An `int32`, 4 bytes
+    Int32(usize),
+    /// An `int64`, 8 bytes
+    Int64(usize),
+    /// A `uint8`, 1 byte
+    UInt8(usize),
+    /// A `uint16`, 2 bytes
+    UInt16(usize),
+    /// A `uint32`, 4 bytes
+    UInt32(usize),
+    /// A `uint64`, 8 bytes
+    UInt64(usize),
+    /// A `float32` or `float`, 4 bytes
+    Float32(usize),
+    /// A `float64` or `double`, 8 bytes
+    Float64(usize),
+}
+
+impl StructureFieldTypes {
+    /// The size of a single element of this type in bytes
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub const fn base_size(&self) -> usize {
+        match self {
+            Self::Bool(_) => 1,
+            Self::Char(_) => 1,
+            Self::Int8(_) => 1,
+            Self::Int16(_) => 2,
+            Self::Int32(_) => 4,
+            Self::Int64(_) => 8,
+            Self::UInt8(_) => 1,
+            Self::UInt16(_) => 2,
+            Self::UInt32(_) => 4,
+            Self::UInt64(_) => 8,
+            Self::Float32(_) => 4,
+            Self::Float64(_) => 8,
+        }
+    }
+
+    /// The number of elements of this type
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        match self {
+            Self::Bool(c)
+            | Self::Char(c)
+            | Self::Int8(c)
+            | Self::Int16(c)
+            | Self::Int32(c)
+            | Self::Int64(c)
+            | Self::UInt8(c)
+            | Self::UInt16(c)
+            | Self::UInt32(c)
+            | Self::UInt64(c)
+            | Self::Float32(c)
+            | Self::Float64(c) => *c,
+        }
+    }
+
+    /// The total size of the field in bytes,
+    /// saturating at [`usize::MAX`] for counts too large to fit in memory
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.base_size().saturating_mul(self.count())
+    }
+
+    /// The canonical schema name of this type
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Bool(_) => "bool",
+            Self::Char(_) => "char",
+            Self::Int8(_) => "int8",
+            Self::Int16(_) => "int16",
+            Self::Int32(_) => "int32",
+            Self::Int64(_) => "int64",
+            Self::UInt8(_) => "uint8",
+            Self::UInt16(_) => "uint16",
+            Self::UInt32(_) => "uint32",
+            Self::UInt64(_) => "uint64",
+            Self::Float32(_) => "float32",
+            Self::Float64(_) => "float64",
+        }
+    }
+
+    /// Gets the field type for a schema type name,
+    /// returns None if the name is not a primitive type
+    #[must_use]
+    pub fn from_type(type_name: &str, count: usize) -> Option<Self> {
+        match type_name {
+            "bool" => Some(Self::Bool(count)),
+            "char" => Some(Self::Char(count)),
+            "int8" => Some(Self::Int8(count)),
+            "int16" => Some(Self::Int16(count)),
+            "int32" => Some(Self::Int32(count)),
+            "int64" => Some(Self::Int64(count)),
+            "uint8" => Some(Self::UInt8(count)),
+            "uint16" => Some(Self::UInt16(count)),
+            "uint32" => Some(Self::UInt32(count)),
+            "uint64" => Some(Self::UInt64(count)),
+            "float" | "float32" => Some(Self::Float32(count)),
+            "double" | "float64" => Some(Self::Float64(count)),
+            _ => None,
+        }
+    }
+}
+
+/// The Rust type that packs as a single element of a primitive field,
+/// like `f64` for `float64`
+///
+/// # Example
+/// ```
+/// use frclib_core::structure::{rust_type_for, StructureFieldTypes};
+///
+/// assert_eq!(rust_type_for(&StructureFieldTypes::Int32(1)), "i32");
+/// assert_eq!(rust_type_for(&StructureFieldTypes::Float64(3)), "f64");
+/// ```
+#[must_use]
+pub const fn rust_type_for(field: &StructureFieldTypes) -> &'static str {
+    match field {
+        StructureFieldTypes::Bool(_) => "bool",
+        StructureFieldTypes::Char(_) => "char",
+        StructureFieldTypes::Int8(_) => "i8",
+        StructureFieldTypes::Int16(_) => "i16",
+        StructureFieldTypes::Int32(_) => "i32",
+        StructureFieldTypes::Int64(_) => "i64",
+        StructureFieldTypes::UInt8(_) => "u8",
+        StructureFieldTypes::UInt16(_) => "u16",
+        StructureFieldTypes::UInt32(_) => "u32",
+        StructureFieldTypes::UInt64(_) => "u64",
+        StructureFieldTypes::Float32(_) => "f32",
+        StructureFieldTypes::Float64(_) => "f64",
+    }
+}
+
+/// A primitive field of a flattened schema
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SchemaField {
+    /// The path of the field, fields of sub-structures are joined with a `.`
+    /// and elements of sub-structure arrays are indexed like `arr[0].x`
+    pub name: String,
+    /// The offset of the field in bytes from the start of the structure
+    pub offset: usize,
+    /// The type and array count of the field
+    pub field_type: StructureFieldTypes,
+    /// The bit of the byte at `offset` that holds the field for `bool name:1` bit-fields,
+    /// `None` for fields that take whole bytes
+    pub bit: Option<u8>,
+}
+
+#[derive(Logos, Debug, Clone, Copy, PartialEq, Eq)]
+#[logos(skip r"[ \t\r\n\f]+")]
+enum Token<'a> {
+    #[token("enum")]
+    EnumKeyword,
+
+    #[regex(r"-?[0-9]+", |lex| lex.slice().parse().ok())]
+    Integer(i64),
+
+    #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", |lex| lex.slice())]
+    Ident(&'a str),
+
+    #[token("{")]
+    OpenBrace,
+    #[token("}")]
+    CloseBrace,
+    #[token("[")]
+    OpenBracket,
+    #[token("]")]
+    CloseBracket,
+    #[token(",")]
+    Comma,
+    #[token(";")]
+    Semicolon,
+    #[token(":")]
+    Colon,
+    #[token("=")]
+    Equals,
+}
+
+/// Parses a [WPIlib struct schema](https://github.com/wpilibsuite/allwpilib/blob/main/wpiutil/doc/struct.adoc)
+/// into its primitive fields with their offsets.
+///
+/// Fields that are structures are looked up in the [`FrcStructDescDB`] and flattened recursively.
+/// Consecutive `bool name:1` bit-fields share a byte, up to 8 per byte,
+/// other bit-fields are not supported.
+///
+/// # Errors
+/// Returns a [`SchemaParseError`] if the schema is malformed
+/// or references a structure that is not registered,
+/// and [`SchemaParseError::TooLarge`] if it flattens to more than 65536 nested structures
+///
+/// # Example
+/// ```
+/// use frclib_core::structure::{parse_schema, StructureFieldTypes};
+///
+/// let fields = parse_schema("enum {a=1, b=2} int8 val; double arr[3]").expect("valid schema");
+/// assert_eq!(fields[0].name, "val");
+/// assert_eq!(fields[1].offset, 1);
+/// assert_eq!(fields[1].field_type, StructureFieldTypes::Float64(3));
+/// ```
+pub fn parse_schema(schema: &str) -> Result<Vec<SchemaField>, SchemaParseError> {
+    let mut fields = Vec::new();
+    let _size = parse_schema_into(schema, "", 0, &mut Vec::new(), &mut fields, &mut Vec::new())?;
+    Ok(fields)
+}
+
+type EnumFields = Vec<(SchemaField, Vec<i64>)>;
+
+/// The size of the schema and the primitive fields annotated with `enum {..}` along with the values of their variants,
+/// annotations without variants don't restrict the field and are left out
+pub(super) fn enum_fields(schema: &str) -> Result<(usize, EnumFields), SchemaParseError> {
+    let (mut fields, mut enums) = (Vec::new(), Vec::new());
+    let size = parse_schema_into(schema, "", 0, &mut Vec::new(), &mut fields, &mut enums)?;
+    let enums = enums
+        .into_iter()
+        .filter(|(_, values)| !values.is_empty())
+        .filter_map(|(index, values)| Some((fields.get(index)?.clone(), values)))
+        .collect();
+    Ok((size, enums))
+}
+
+/// Reads a single little-endian element of an integer type,
+/// returns None for other types or if `bytes` is too short
+pub(super) fn read_integer(field_type: StructureFieldTypes, bytes: &[u8]) -> Option<i128> {
+    let element = bytes.get(..field_type.base_size())?;
+    match field_type {
+        StructureFieldTypes::Int8(_) => le_bytes(element).map(i8::from_le_bytes).map(i128::from),
+        StructureFieldTypes::Int16(_) => le_bytes(element).map(i16::from_le_bytes).map(i128::from),
+        StructureFieldTypes::Int32(_) => le_bytes(element).map(i32::from_le_bytes).map(i128::from),
+        StructureFieldTypes::Int64(_) => le_bytes(element).map(i64::from_le_bytes).map(i128::from),
+        StructureFieldTypes::UInt8(_) => le_bytes(element).map(u8::from_le_bytes).map(i128::from),
+        StructureFieldTypes::UInt16(_) => le_bytes(element).map(u16::from_le_bytes).map(i128::from),
+        StructureFieldTypes::UInt32(_) => le_bytes(element).map(u32::from_le_bytes).map(i128::from),
+        StructureFieldTypes::UInt64(_) => le_bytes(element).map(u64::from_le_bytes).map(i128::from),
+        _ => None,
+    }
+}
+
+/// Generates the source of a Rust struct deriving [`FrcStructure`](super::FrcStructure)
+/// that packs like `schema`, for structures only known by a received schema.
+///
+/// Primitive fields use the type given by [`rust_type_for`], arrays become `[T; N]`,
+/// `bool name:1` bit-fields become `#[frc(bits)]` fields and `enum {..}` prefixes are dropped.
+/// Fields that are structures reference the structure by its type name,
+/// which has to be generated or defined separately.
+/// Fields named after a Rust keyword become raw identifiers like `r#type`,
+/// `self`, `Self`, `super`, `crate` and `_` get a trailing `_` and a `#[frc(rename = "..")]`.
+///
+/// The derive expects [`FrcStructure`](super::FrcStructure) and [`Cursor`](super::Cursor)
+/// to be in scope where the source is used.
+///
+/// # Errors
+/// Returns a [`SchemaParseError`] if the schema is malformed
+///
+/// # Example
+/// ```
+/// use frclib_core::structure::rust_source;
+///
+/// let source = rust_source("Pose", "Translation2d trans; double rot[2]; bool flag:1")
+///     .expect("valid schema");
+/// assert_eq!(
+///     source,
+///     "#[derive(FrcStructure)]\n\
+///      pub struct Pose {\n    \
+///          pub trans: Translation2d,\n    \
+///          pub rot: [f64; 2],\n    \
+///          #[frc(bits)]\n    \
+///          pub flag: bool,\n\
+///      }\n"
+/// );
+/// ```
+pub fn rust_source(type_str: &str, schema: &str) -> Result<String, SchemaParseError> {
+    let mut source = format!("#[derive(FrcStructure)]\npub struct {type_str} {{\n");
+    for (declaration, text) in declarations(schema)? {
+        let (type_name, name, count) = parse_declaration(&declaration, text)?;
+        let mut attributes = Vec::new();
+        let field_name = if matches!(name, "self" | "Self" | "super" | "crate" | "_") {
+            // these can't be raw identifiers so the field is renamed back in the schema
+            attributes.push(format!("rename = \"{name}\""));
+            format!("{name}_")
+        } else if RUST_KEYWORDS.contains(&name) {
+            format!("r#{name}")
+        } else {
+            name.to_owned()
+        };
+        let is_bits = declaration.contains(&Token::Colon);
+        if is_bits {
+            attributes.insert(0, "bits".to_owned());
+        }
+        if !attributes.is_empty() {
+            let _ = writeln!(source, "    #[frc({})]", attributes.join(", "));
+        }
+        if is_bits {
+            let _ = writeln!(source, "    pub {field_name}: bool,");
+            continue;
+        }
+        let rust_type = StructureFieldTypes::from_type(type_name, count)
+            .map_or(type_name, |field_type| rust_type_for(&field_type));
+        if declaration.contains(&Token::OpenBracket) {
+            let _ = writeln!(source, "    pub {field_name}: [{rust_type}; {count}],");
+        } else {
+            let _ = writeln!(source, "    pub {field_name}: {rust_type},");
+        }
+    }
+    source.push_str("}\n");
+    Ok(source)
+}
+
+/// The strict and reserved keywords of every Rust edition,
+/// field names matching them are written as raw identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// The offset, type and bit of every primitive field in `len` bytes of packed structures,
+/// structures with an empty schema are treated as an array of the primitive named by `type_str`.
+///
+/// A schema that can't be parsed has no fields.
+pub(super) fn packed_fields(
+    type_str: &str,
+    schema: &str,
+    len: usize,
+) -> Vec<(usize, StructureFieldTypes, Option<u8>)> {
+    if schema.is_empty() {
+        StructureFieldTypes::from_type(type_str, 1)
+            .and_then(|field_type| {
+                StructureFieldTypes::from_type(type_str, len / field_type.base_size())
+            })
+            .map(|field_type| (0, field_type, None))
+            .into_iter()
+            .collect()
+    } else {
+        parse_schema(schema)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|field| (field.offset, field.field_type, field.bit))
+            .collect()
+    }
+}
+
+/// A 64 bit FNV-1a hash of the flattened schema,
+/// structures with an empty schema are hashed by their type
+pub(super) fn schema_hash(type_str: &str, schema: &str) -> Result<u64, SchemaParseError> {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut flattened = String::new();
+    if schema.is_empty() {
+        flattened.push_str(type_str);
+    } else {
+        for field in parse_schema(schema)? {
+            let _ = write!(
+                flattened,
+                "{} {}[{}]",
+                field.field_type.type_name(),
+                field.name,
+                field.field_type.count()
+            );
+            if let Some(bit) = field.bit {
+                let _ = write!(flattened, ":{bit}");
+            }
+            flattened.push(';');
+        }
+    }
+    Ok(flattened.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    }))
+}
+
+/// The flattened schema as a JSON object with the type, size and fields of the structure
+pub(super) fn schema_json(
+    type_str: &str,
+    size: usize,
+    schema: &str,
+) -> Result<String, SchemaParseError> {
+    let mut json = String::from("{\"type\":");
+    write_json_str(&mut json, type_str);
+    let _ = write!(json, ",\"size\":{size},\"fields\":[");
+    for (index, field) in parse_schema(schema)?.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"name\":");
+        write_json_str(&mut json, &field.name);
+        let _ = write!(
+            json,
+            ",\"type\":\"{}\",\"offset\":{},\"count\":{}",
+            field.field_type.type_name(),
+            field.offset,
+            field.field_type.count()
+        );
+        if let Some(bit) = field.bit {
+            let _ = write!(json, ",\"bit\":{bit}");
+        }
+        json.push('}');
+    }
+    json.push_str("]}");
+    Ok(json)
+}
+
+/// Every description as a JSON array of `{type, size, schema, hash}` objects,
+/// the hash is written as hex or `null` when the schema can't be parsed
+pub(super) fn catalog_json(descs: impl Iterator<Item = &'static FrcStructDesc>) -> String {
+    let mut json = String::from("[");
+    for (index, desc) in descs.enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"type\":");
+        write_json_str(&mut json, desc.type_str);
+        let _ = write!(json, ",\"size\":{},\"schema\":", desc.size);
+        write_json_str(&mut json, desc.schema());
+        match desc.schema_hash() {
+            Ok(hash) => {
+                let _ = write!(json, ",\"hash\":\"{hash:016x}\"}}");
+            }
+            Err(_) => json.push_str(",\"hash\":null}"),
+        }
+    }
+    json.push(']');
+    json
+}
+
+/// Renders packed bytes as `Type { field: value, .. }`,
+/// structures with an empty schema are rendered as the primitive named by `type_str`
+pub(super) fn pretty_decode(
+    type_str: &str,
+    schema: &str,
+    bytes: &[u8],
+) -> Result<String, SchemaParseError> {
+    let mut out = String::new();
+    if schema.is_empty() {
+        if let Some(field_type) = StructureFieldTypes::from_type(type_str, 1) {
+            let count = bytes.len() / field_type.base_size();
+            let field_type = StructureFieldTypes::from_type(type_str, count).unwrap_or(field_type);
+            write_field_value(&mut out, field_type, bytes);
+            return Ok(out);
+        }
+    }
+    out.push_str(type_str);
+    out.push_str(" {");
+    for (index, field) in parse_schema(schema)?.iter().enumerate() {
+        out.push_str(if index == 0 { " " } else { ", " });
+        let _ = write!(out, "{}: ", field.name);
+        match (
+            bytes.get(field.offset..field.offset + field.field_type.size()),
+            field.bit,
+        ) {
+            (Some([byte]), Some(bit)) => {
+                let _ = write!(out, "{}", (byte >> bit) & 1 != 0);
+            }
+            (Some(field_bytes), _) => write_field_value(&mut out, field.field_type, field_bytes),
+            (None, _) => out.push_str("<missing>"),
+        }
+    }
+    out.push_str(" }");
+    Ok(out)
+}
+
+/// Writes a field as a single value, a list for arrays or a string for `char`s
+fn write_field_value(out: &mut String, field_type: StructureFieldTypes, bytes: &[u8]) {
+    if let StructureFieldTypes::Char(_) = field_type {
+        let end = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(bytes.len());
+        let text = String::from_utf8_lossy(bytes.get(..end).unwrap_or_default());
+        let _ = write!(out, "{text:?}");
+        return;
+    }
+    let elements = bytes.chunks_exact(field_type.base_size());
+    if field_type.count() == 1 {
+        elements
+            .take(1)
+            .for_each(|element| write_element(out, field_type, element));
+        return;
+    }
+    out.push('[');
+    for (index, element) in elements.enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        write_element(out, field_type, element);
+    }
+    out.push(']');
+}
+
+/// Writes a single little-endian element of a primitive type
+fn write_element(out: &mut String, field_type: StructureFieldTypes, bytes: &[u8]) {
+    let _ = match field_type {
+        StructureFieldTypes::Bool(_) => write!(out, "{}", bytes.iter().any(|byte| *byte != 0)),
+        StructureFieldTypes::Char(_) => write!(
+            out,
+            "{:?}",
+            char::from(bytes.first().copied().unwrap_or_default())
+        ),
+        StructureFieldTypes::Int8(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(i8::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::Int16(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(i16::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::Int32(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(i32::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::Int64(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(i64::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::UInt8(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(u8::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::UInt16(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(u16::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::UInt32(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(u32::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::UInt64(_) => write!(
+            out,
+            "{}",
+            le_bytes(bytes).map(u64::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::Float32(_) => write!(
+            out,
+            "{:?}",
+            le_bytes(bytes).map(f32::from_le_bytes).unwrap_or_default()
+        ),
+        StructureFieldTypes::Float64(_) => write!(
+            out,
+            "{:?}",
+            le_bytes(bytes).map(f64::from_le_bytes).unwrap_or_default()
+        ),
+    };
+}
+
+/// Converts a slice into a fixed size array for `from_le_bytes`
+fn le_bytes<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+    bytes.try_into().ok()
+}
+
+/// Writes `value` as a quoted and escaped JSON string
+fn write_json_str(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// The most structure elements a schema may flatten to across every nesting level,
+/// each one is parsed separately so a peer's `Sub arr[1000000000000]` must fail before the loop
+const MAX_STRUCTURE_ELEMENTS: usize = 1 << 16;
+
+/// Parses `schema` appending its fields to `fields` and returns the size of the schema,
+/// the index in `fields` and the variant values of every `enum {..}` field are appended to `enums`
+fn parse_schema_into(
+    schema: &str,
+    prefix: &str,
+    offset: usize,
+    parents: &mut Vec<&'static str>,
+    fields: &mut Vec<SchemaField>,
+    enums: &mut Vec<(usize, Vec<i64>)>,
+) -> Result<usize, SchemaParseError> {
+    parse_schema_elements(schema, prefix, offset, parents, fields, enums, &mut 0)
+}
+
+/// [`parse_schema_into`] counting the structure elements flattened so far in `elements`
+fn parse_schema_elements(
+    schema: &str,
+    prefix: &str,
+    offset: usize,
+    parents: &mut Vec<&'static str>,
+    fields: &mut Vec<SchemaField>,
+    enums: &mut Vec<(usize, Vec<i64>)>,
+    elements: &mut usize,
+) -> Result<usize, SchemaParseError> {
+    let mut cursor = offset;
+    // schemas come from peers, a huge array must not overflow the offsets
+    let too_large = |name: &str| SchemaParseError::TooLarge(format!("{prefix}{name}"));
+    // the byte and next free bit of the bit-fields declared right before
+    let mut bits: Option<(usize, u8)> = None;
+    for (declaration, source) in declarations(schema)? {
+        let (type_name, name, count) = parse_declaration(&declaration, source)?;
+        if declaration.contains(&Token::Colon) {
+            let (byte, bit) = match bits {
+                Some((byte, bit)) if bit < 8 => (byte, bit),
+                _ => {
+                    let byte = cursor;
+                    cursor = cursor.checked_add(1).ok_or_else(|| too_large(name))?;
+                    (byte, 0)
+                }
+            };
+            fields.push(SchemaField {
+                name: format!("{prefix}{name}"),
+                offset: byte,
+                field_type: StructureFieldTypes::Bool(1),
+                bit: Some(bit),
+            });
+            bits = Some((byte, bit + 1));
+            continue;
+        }
+        bits = None;
+        if let Some(field_type) = StructureFieldTypes::from_type(type_name, count) {
+            let end = field_type
+                .base_size()
+                .checked_mul(count)
+                .and_then(|size| cursor.checked_add(size))
+                .ok_or_else(|| too_large(name))?;
+            if let [Token::EnumKeyword, Token::OpenBrace, variants @ ..] = declaration.as_slice() {
+                enums.push((fields.len(), enum_values(variants)));
+            }
+            fields.push(SchemaField {
+                name: format!("{prefix}{name}"),
+                offset: cursor,
+                field_type,
+                bit: None,
+            });
+            cursor = end;
+            continue;
+        }
+
+        let desc = FrcStructDescDB::get(type_name)
+            .ok_or_else(|| SchemaParseError::UnknownType(type_name.to_owned()))?;
+        if parents.contains(&desc.type_str) {
+            return Err(SchemaParseError::Recursive(desc.type_str.to_owned()));
+        }
+        // checked up front so a huge array of structures fails before parsing every element
+        if desc
+            .size
+            .checked_mul(count)
+            .and_then(|size| cursor.checked_add(size))
+            .is_none()
+        {
+            return Err(too_large(name));
+        }
+        *elements = elements
+            .checked_add(count)
+            .filter(|elements| *elements <= MAX_STRUCTURE_ELEMENTS)
+            .ok_or_else(|| too_large(name))?;
+        parents.push(desc.type_str);
+        let sub_schema = desc.schema();
+        let is_array = declaration.contains(&Token::OpenBracket);
+        for index in 0..count {
+            let sub_prefix = if is_array {
+                format!("{prefix}{name}[{index}].")
+            } else {
+                format!("{prefix}{name}.")
+            };
+            let size = parse_schema_elements(
+                sub_schema,
+                &sub_prefix,
+                cursor,
+                parents,
+                fields,
+                enums,
+                elements,
+            )?;
+            if size != desc.size {
+                return Err(SchemaParseError::SizeMismatch {
+                    type_str: desc.type_str.to_owned(),
+                    expected: desc.size,
+                    actual: size,
+                });
+            }
+            cursor = cursor.checked_add(size).ok_or_else(|| too_large(name))?;
+        }
+        let _ = parents.pop();
+    }
+    Ok(cursor - offset)
+}
+
+/// Splits `schema` into the tokens of each non-empty declaration along with its source text
+fn declarations(schema: &str) -> Result<Vec<(Vec<Token<'_>>, &str)>, SchemaParseError> {
+    let mut tokens = Vec::new();
+    let mut lexer = Token::lexer(schema);
+    while let Some(token) = lexer.next() {
+        let token = token.map_err(|()| SchemaParseError::InvalidToken(lexer.span().start))?;
+        tokens.push((token, lexer.span()));
+    }
+
+    Ok(tokens
+        .split(|(token, _)| token == &Token::Semicolon)
+        .filter_map(|declaration| {
+            let ((_, first), (_, last)) = (declaration.first()?, declaration.last()?);
+            Some((
+                declaration.iter().map(|(token, _)| *token).collect(),
+                &schema[first.start..last.end],
+            ))
+        })
+        .collect())
+}
+
+/// The values of the variants of an `enum {..}` annotation, starting after its `{`
+fn enum_values(variants: &[Token<'_>]) -> Vec<i64> {
+    variants
+        .iter()
+        .take_while(|token| token != &&Token::CloseBrace)
+        .filter_map(|token| match token {
+            Token::Integer(value) => Some(*value),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses `[enum {..}] type name [[count]]` into the type, name and count,
+/// the only bit-field it accepts is `bool name:1`
+fn parse_declaration<'a>(
+    declaration: &[Token<'a>],
+    source: &str,
+) -> Result<(&'a str, &'a str, usize), SchemaParseError> {
+    let malformed = || SchemaParseError::MalformedField(source.to_owned());
+
+    let mut rest = declaration;
+    if let [Token::EnumKeyword, Token::OpenBrace, tail @ ..] = rest {
+        let close = tail
+            .iter()
+            .position(|token| token == &Token::CloseBrace)
+            .ok_or_else(malformed)?;
+        for variant in tail[..close].split(|token| token == &Token::Comma) {
+            if !matches!(
+                variant,
+                [] | [Token::Ident(_), Token::Equals, Token::Integer(_)]
+            ) {
+                return Err(malformed());
+            }
+        }
+        rest = &tail[close + 1..];
+    }
+
+    match rest {
+        [Token::Ident(type_name), Token::Ident(name)] => Ok((type_name, name, 1)),
+        [Token::Ident(type_name), Token::Ident(name), Token::OpenBracket, Token::Integer(count), Token::CloseBracket] =>
+        {
+            let count = usize::try_from(*count).map_err(|_| malformed())?;
+            Ok((type_name, name, count))
+        }
+        [Token::Ident("bool"), Token::Ident(name), Token::Colon, Token::Integer(1)] => {
+            Ok(("bool", name, 1))
+        }
+        [Token::Ident(_), Token::Ident(name), Token::Colon, ..] => {
+            Err(SchemaParseError::BitField((*name).to_owned()))
+        }
+        _ => Err(malformed()),
+    }
+}
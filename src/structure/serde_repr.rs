@@ -0,0 +1,171 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use super::{FrcStructDescDB, FrcStructureBytes, StructString};
+
+const FIELDS: &[&str] = &["type_str", "count", "data"];
+
+/// Serializes as `{"type_str": .., "count": .., "data": ..}` with `data` written as bytes,
+/// the description itself is looked up again by `type_str` when deserializing.
+impl Serialize for FrcStructureBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("FrcStructureBytes", FIELDS.len())?;
+        state.serialize_field("type_str", self.desc.type_str)?;
+        state.serialize_field("count", &self.count)?;
+        state.serialize_field("data", &Bytes(&self.data))?;
+        state.end()
+    }
+}
+
+/// Deserializes structures written by the [`Serialize`] impl,
+/// `type_str` has to be registered in the [`FrcStructDescDB`]
+/// and `data` has to be `count` structures long.
+impl<'de> Deserialize<'de> for FrcStructureBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("FrcStructureBytes", FIELDS, BytesVisitor)
+    }
+}
+
+/// Looks up the description of `type_str` and checks the data against it
+fn from_serialized<E: de::Error>(
+    type_str: &str,
+    count: usize,
+    data: Vec<u8>,
+) -> Result<FrcStructureBytes, E> {
+    let desc = FrcStructDescDB::try_get(type_str).map_err(E::custom)?;
+    FrcStructureBytes::try_from_parts(desc, count, data.into_boxed_slice()).map_err(E::custom)
+}
+
+/// Writes a byte slice with [`Serializer::serialize_bytes`] instead of as a sequence
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Reads bytes written by [`Bytes`], formats without a byte type give back a sequence
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_byte_buf(ByteBufVisitor)
+            .map(ByteBuf)
+    }
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("structure bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = FrcStructureBytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a struct with a type_str, count and data")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FrcStructureBytes, A::Error> {
+        let type_str: String = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let count: usize = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let ByteBuf(data) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        from_serialized(&type_str, count, data)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<FrcStructureBytes, A::Error> {
+        let mut type_str = None;
+        let mut count = None;
+        let mut data = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "type_str" => {
+                    if type_str.is_some() {
+                        return Err(de::Error::duplicate_field("type_str"));
+                    }
+                    type_str = Some(map.next_value::<String>()?);
+                }
+                "count" => {
+                    if count.is_some() {
+                        return Err(de::Error::duplicate_field("count"));
+                    }
+                    count = Some(map.next_value::<usize>()?);
+                }
+                "data" => {
+                    if data.is_some() {
+                        return Err(de::Error::duplicate_field("data"));
+                    }
+                    data = Some(map.next_value::<ByteBuf>()?.0);
+                }
+                other => return Err(de::Error::unknown_field(other, FIELDS)),
+            }
+        }
+        let type_str = type_str.ok_or_else(|| de::Error::missing_field("type_str"))?;
+        let count = count.ok_or_else(|| de::Error::missing_field("count"))?;
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+        from_serialized(&type_str, count, data)
+    }
+}
+
+/// Serializes as the string up to the first `\0`
+impl<const N: usize> Serialize for StructString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes any string, truncating it like [`StructString::new`]
+impl<'de, const N: usize> Deserialize<'de> for StructString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StructStringVisitor)
+    }
+}
+
+struct StructStringVisitor<const N: usize>;
+
+impl<const N: usize> Visitor<'_> for StructStringVisitor<N> {
+    type Value = StructString<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<StructString<N>, E> {
+        Ok(StructString::new(v))
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use super::{Cursor, Endianness, FrcStructure, TriviallyCopyable};
+
+/// A fixed length string, packed as `char name[N]` in a schema
+///
+/// The struct spec's `char` is a single byte so every character takes one byte,
+/// characters that aren't ASCII are stored as `?` like a packed [`char`].
+/// Strings longer than `N` are truncated and shorter ones are padded with `\0`.
+/// Reading it back as a string stops at the first `\0`, so the padding is never part of it.
+///
+/// With the `serde` feature it serializes as a plain string without the padding,
+/// and deserializes from any string with the same truncation as [`new`](Self::new).
+///
+/// # Example
+/// ```
+/// use frclib_core::structure::{FrcStructure, StructString};
+///
+/// let name = StructString::<8>::new("intake");
+/// assert_eq!(name.as_str(), "intake");
+/// assert_eq!(name.pack_to_vec(), b"intake\0\0");
+/// assert_eq!(StructString::<4>::new("shooter").as_str(), "shoo");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct StructString<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> StructString<N> {
+    /// Creates a string from `value`, truncating it to `N` bytes
+    /// and replacing characters that aren't ASCII with `?`
+    #[must_use]
+    pub fn new(value: &str) -> Self {
+        let mut bytes = [0u8; N];
+        for (byte, c) in bytes.iter_mut().zip(value.chars()) {
+            *byte = u8::try_from(c).ok().filter(u8::is_ascii).unwrap_or(b'?');
+        }
+        Self(bytes)
+    }
+
+    /// The string up to the first `\0`
+    ///
+    /// Unpacked bytes aren't checked, so this stops early at the first byte that isn't valid UTF-8.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        let end = self.0.iter().position(|byte| *byte == 0).unwrap_or(N);
+        let bytes = self.0.get(..end).unwrap_or_default();
+        core::str::from_utf8(bytes).unwrap_or_else(|error| {
+            core::str::from_utf8(bytes.get(..error.valid_up_to()).unwrap_or_default())
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl<const N: usize> Default for StructString<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> From<&str> for StructString<N> {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> From<&String> for StructString<N> {
+    fn from(value: &String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> From<String> for StructString<N> {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl<const N: usize> From<StructString<N>> for String {
+    fn from(value: StructString<N>) -> Self {
+        value.as_str().to_owned()
+    }
+}
+
+impl<const N: usize> fmt::Debug for StructString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Display for StructString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> StructString<N> {
+    /// The generic name of the string built at compile time, `charxN`
+    const GENERIC_NAME_BYTES: &'static [u8; super::GENERIC_TYPE_LEN] =
+        &super::array_type_name("char", N);
+}
+
+impl<const N: usize> FrcStructure for StructString<N> {
+    const TYPE: &'static str = "char";
+    const SIZE: usize = N;
+    const SCHEMA_SUPPLIER: fn() -> String = String::new;
+    const GENERIC_NAME: &'static str = super::type_name_str(Self::GENERIC_NAME_BYTES);
+    // SAFETY: `repr(transparent)` over a byte array, which is exactly the packed bytes
+    const TRIVIALLY_COPYABLE: TriviallyCopyable<Self> =
+        unsafe { TriviallyCopyable::new_unchecked() };
+
+    #[inline]
+    fn pack(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.0);
+    }
+
+    #[inline]
+    fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
+        let mut bytes = [0u8; N];
+        let _ = buffer.read_exact(&mut bytes);
+        Self(bytes)
+    }
+
+    #[inline]
+    fn unpack_slice(bytes: &[u8]) -> Self {
+        Self(super::read_array(bytes))
+    }
+
+    #[inline]
+    fn pack_with_endian(&self, buffer: &mut Vec<u8>, _: Endianness) {
+        self.pack(buffer);
+    }
+
+    #[inline]
+    fn unpack_with_endian(buffer: &mut Cursor<&[u8]>, _: Endianness) -> Self {
+        Self::unpack(buffer)
+    }
+
+    fn format_field(field: &str) -> String {
+        format!("char {field}[{N}]")
+    }
+}
@@ -13,14 +13,14 @@ impl FrcStructure for SubStruct {
         self.value.pack(buffer);
     }
 
-    fn unpack(buffer: &mut std::io::Cursor<&[u8]>) -> Self {
+    fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
         Self {
             value: <f64 as FrcStructure>::unpack(buffer),
         }
     }
 }
 
-inventory::submit! { SubStruct::DESCRIPTION }
+crate::structure_submit!(SubStruct);
 
 #[test]
 #[cfg(feature = "value-union")]
@@ -71,59 +71,2570 @@ fn test_structures() {
     });
 
     //iterate through all inventory values of FrcStructureDescription and print type_str
+    #[cfg(feature = "inventory")]
     for struct_desc in inventory::iter::<FrcStructDesc> {
-        println!(
-            "{} {{{}}}",
-            struct_desc.type_str,
-            (struct_desc.schema_supplier)()
+        println!("{} {{{}}}", struct_desc.type_str, struct_desc.schema());
+    }
+}
+
+#[test]
+fn test_nested_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct NestInner {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct NestMiddle {
+        inner: NestInner,
+        flag: bool,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct NestOuter {
+        id: i32,
+        middle: NestMiddle,
+    }
+
+    FrcStructDescDB::register::<NestInner>();
+    FrcStructDescDB::register::<NestMiddle>();
+    assert_eq!(NestMiddle::SIZE, 17);
+    assert_eq!(NestOuter::SIZE, 21);
+    assert_eq!(
+        NestOuter::DESCRIPTION.schema(),
+        "int32 id; NestMiddle middle"
+    );
+
+    let outer = NestOuter {
+        id: -4,
+        middle: NestMiddle {
+            inner: NestInner { x: 1.5, y: -2.0 },
+            flag: true,
+        },
+    };
+    let bytes = outer.pack_to_vec();
+    assert_eq!(bytes.len(), NestOuter::SIZE);
+    assert_eq!(
+        NestOuter::try_unpack(&mut Cursor::new(&bytes[..])),
+        Ok(outer)
+    );
+
+    let fields = parse_schema(NestOuter::DESCRIPTION.schema())
+        .expect("Failed to parse schema")
+        .into_iter()
+        .map(|field| (field.name, field.offset))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            ("id".to_owned(), 0),
+            ("middle.inner.x".to_owned(), 4),
+            ("middle.inner.y".to_owned(), 12),
+            ("middle.flag".to_owned(), 20),
+        ]
+    );
+}
+
+#[test]
+fn test_array_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct ArrayElem {
+        speed: f64,
+        angle: f32,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct ArrayHolder {
+        arr: [f64; 3],
+        modules: [ArrayElem; 4],
+        empty: [u8; 0],
+    }
+
+    FrcStructDescDB::register::<ArrayElem>();
+    assert_eq!(ArrayHolder::SIZE, 3 * 8 + 4 * 12);
+    assert_eq!(
+        ArrayHolder::DESCRIPTION.schema(),
+        "float64 arr[3]; ArrayElem modules[4]; uint8 empty[0]"
+    );
+
+    let holder = ArrayHolder {
+        arr: [1.0, 2.0, 3.0],
+        modules: [
+            ArrayElem {
+                speed: 0.5,
+                angle: 0.0,
+            },
+            ArrayElem {
+                speed: 1.5,
+                angle: 1.0,
+            },
+            ArrayElem {
+                speed: -2.5,
+                angle: 2.0,
+            },
+            ArrayElem {
+                speed: 3.5,
+                angle: -3.0,
+            },
+        ],
+        empty: [],
+    };
+    let bytes = holder.pack_to_vec();
+    assert_eq!(bytes.len(), ArrayHolder::SIZE);
+    assert_eq!(&bytes[8..16], &2.0f64.to_le_bytes());
+    assert_eq!(
+        ArrayHolder::try_unpack(&mut Cursor::new(&bytes[..])),
+        Ok(holder)
+    );
+
+    let fields = parse_schema(ArrayHolder::DESCRIPTION.schema())
+        .expect("Failed to parse schema")
+        .into_iter()
+        .map(|field| (field.name, field.offset))
+        .collect::<Vec<_>>();
+    assert_eq!(fields[0], ("arr".to_owned(), 0));
+    assert_eq!(fields[1], ("modules[0].speed".to_owned(), 24));
+    assert_eq!(fields[8], ("modules[3].angle".to_owned(), 24 + 3 * 12 + 8));
+}
+
+#[test]
+fn test_skip_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct SkipTest {
+        x: f64,
+        #[frc(skip)]
+        cache: [f64; 4],
+        y: i16,
+    }
+
+    assert_eq!(SkipTest::SIZE, 10);
+    assert_eq!(SkipTest::DESCRIPTION.schema(), "float64 x; int16 y");
+
+    let value = SkipTest {
+        x: 1.0,
+        cache: [2.0; 4],
+        y: -7,
+    };
+    let bytes = value.pack_to_vec();
+    assert_eq!(bytes.len(), SkipTest::SIZE);
+    assert_eq!(
+        SkipTest::try_unpack(&mut Cursor::new(&bytes[..])),
+        Ok(SkipTest {
+            cache: [0.0; 4],
+            ..value
+        })
+    );
+}
+
+#[test]
+fn test_rename_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    #[frc(assert_size = 16)]
+    struct RenameTest {
+        #[frc(rename = "x")]
+        x_meters: f64,
+        #[frc(rename = "theta")]
+        heading: [f32; 2],
+    }
+
+    assert_eq!(
+        RenameTest::DESCRIPTION.schema(),
+        "float64 x; float32 theta[2]"
+    );
+    let value = RenameTest {
+        x_meters: 1.0,
+        heading: [0.5, -0.5],
+    };
+    let mut expected = Vec::new();
+    value.x_meters.pack(&mut expected);
+    value.heading.pack(&mut expected);
+    assert_eq!(value.pack_to_vec(), expected);
+}
+
+#[test]
+fn test_tuple_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct TupleVec2(f64, #[frc(rename = "y")] f64, #[frc(skip)] u8);
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct NamedVec2 {
+        x: f64,
+        y: f64,
+    }
+
+    assert_eq!(TupleVec2::SIZE, NamedVec2::SIZE);
+    assert_eq!(TupleVec2::DESCRIPTION.schema(), "float64 _0; float64 y");
+    assert_eq!(
+        <TupleVec2 as FrcStructure>::format_field("pos"),
+        "TupleVec2 pos"
+    );
+
+    let tuple = TupleVec2(1.5, -3.0, 9);
+    let named = NamedVec2 { x: 1.5, y: -3.0 };
+    let bytes = tuple.pack_to_vec();
+    assert_eq!(bytes, named.pack_to_vec());
+    assert_eq!(
+        TupleVec2::try_unpack(&mut Cursor::new(&bytes[..])),
+        Ok(TupleVec2(1.5, -3.0, 0))
+    );
+}
+
+#[test]
+fn test_bool_char_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Flags {
+        enabled: bool,
+        grade: char,
+        tag: [char; 3],
+    }
+
+    assert_eq!(Flags::SIZE, 5);
+    assert_eq!(
+        Flags::DESCRIPTION.schema(),
+        "bool enabled; char grade; char tag[3]"
+    );
+
+    let flags = Flags {
+        enabled: true,
+        grade: 'A',
+        tag: ['f', 'r', 'c'],
+    };
+    let bytes = flags.pack_to_vec();
+    assert_eq!(bytes, [1, b'A', b'f', b'r', b'c']);
+    assert_eq!(Flags::unpack(&mut Cursor::new(&bytes[..])), flags);
+
+    assert_eq!(false.pack_to_vec(), [0]);
+    assert!(bool::unpack(&mut Cursor::new(&[2u8][..])));
+    assert_eq!('é'.pack_to_vec(), [b'?']);
+    assert_eq!('€'.pack_to_vec(), [b'?']);
+    assert_eq!(
+        char::unpack(&mut Cursor::new(&[0xE9u8][..])),
+        char::REPLACEMENT_CHARACTER
+    );
+}
+
+#[test]
+fn test_struct_string_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct DeviceStatus {
+        id: u8,
+        name: StructString<8>,
+    }
+
+    assert_eq!(DeviceStatus::SIZE, 9);
+    assert_eq!(DeviceStatus::DESCRIPTION.schema(), "uint8 id; char name[8]");
+
+    let status = DeviceStatus {
+        id: 3,
+        name: "climber".into(),
+    };
+    let bytes = status.pack_to_vec();
+    assert_eq!(bytes, b"\x03climber\0");
+    assert_eq!(DeviceStatus::unpack(&mut Cursor::new(&bytes[..])), status);
+    assert_eq!(status.name.as_str(), "climber");
+
+    assert_eq!(StructString::<8>::new("brasé").as_str(), "bras?");
+    assert_eq!(StructString::<3>::new("elevator").as_str(), "ele");
+    assert_eq!(StructString::<4>([b'o', b'k', 0xFF, 0]).as_str(), "ok");
+    assert_eq!(StructString::<4>::default().as_str(), "");
+}
+
+#[test]
+fn test_option_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct OptionInner {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct OptionTest {
+        id: u8,
+        pose: Option<OptionInner>,
+        latency: Option<f32>,
+    }
+
+    FrcStructDescDB::register::<OptionInner>();
+    assert_eq!(OptionTest::SIZE, 1 + 17 + 5);
+    assert_eq!(
+        OptionTest::DESCRIPTION.schema(),
+        "uint8 id; bool pose_present; OptionInner pose; bool latency_present; float32 latency"
+    );
+    assert_eq!(
+        parse_schema(OptionTest::DESCRIPTION.schema())
+            .expect("valid schema")
+            .iter()
+            .map(|field| field.offset)
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2, 10, 18, 19]
+    );
+
+    let present = OptionTest {
+        id: 3,
+        pose: Some(OptionInner { x: 1.0, y: 2.0 }),
+        latency: Some(0.25),
+    };
+    let absent = OptionTest {
+        id: 4,
+        pose: None,
+        latency: Some(0.5),
+    };
+    for value in [present, absent] {
+        let bytes = value.pack_to_vec();
+        assert_eq!(bytes.len(), OptionTest::SIZE);
+        assert_eq!(
+            OptionTest::try_unpack(&mut Cursor::new(&bytes[..])),
+            Ok(value)
+        );
+        let mut big = Vec::new();
+        value.pack_with_endian(&mut big, Endianness::Big);
+        assert_eq!(
+            OptionTest::unpack_with_endian(&mut Cursor::new(&big[..]), Endianness::Big),
+            value
+        );
+    }
+    assert_eq!(&absent.pack_to_vec()[1..18], &[0; 17]);
+
+    // on their own options are described as their own type, not as the wrapped one
+    assert_eq!(Option::<OptionInner>::TYPE, "Option_OptionInner");
+    assert_eq!(
+        Option::<OptionInner>::DESCRIPTION.schema(),
+        "bool present; OptionInner value"
+    );
+    assert_eq!(
+        Option::<[f32; 2]>::DESCRIPTION.schema(),
+        "bool present; float32 value[2]"
+    );
+    let poses = FrcStructureBytes::pack_many(&[present.pose, absent.pose]);
+    assert_eq!(poses.desc.type_str, "Option_OptionInner");
+    assert_eq!(poses.validate(), Ok(()));
+    assert_eq!(
+        poses.to_vec::<Option<OptionInner>>().ok(),
+        Some(vec![present.pose, None])
+    );
+    let mut inners = FrcStructureBytes::pack_many(&[OptionInner { x: 0.0, y: 0.0 }]);
+    assert!(
+        inners.push(&present.pose).is_err(),
+        "an option is not the wrapped type"
+    );
+    assert!(
+        inners.push(&[OptionInner { x: 0.0, y: 0.0 }; 2]).is_err(),
+        "neither is an array of it"
+    );
+    assert_eq!(inners.count, 1);
+}
+
+#[test]
+fn test_view_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct ViewInner {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct ViewTest {
+        flag: bool,
+        #[frc(skip)]
+        cache: u32,
+        inner: ViewInner,
+        timestamp: u64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct ViewTuple(i16, f32);
+
+    let value = ViewTest {
+        flag: true,
+        cache: 0,
+        inner: ViewInner { x: 1.0, y: -1.0 },
+        timestamp: 123_456,
+    };
+    let bytes = value.pack_to_vec();
+    let view = ViewTestView::new(&bytes).expect("enough bytes");
+    assert!(view.flag());
+    assert_eq!(view.inner(), value.inner);
+    assert_eq!(view.timestamp(), 123_456);
+    assert_eq!(view.as_bytes(), &bytes[..]);
+    assert_eq!(ViewTestView::new(&bytes[1..]), None);
+
+    let bytes = ViewTuple(-2, 0.5).pack_to_vec();
+    let view = ViewTupleView::new(&bytes).expect("enough bytes");
+    assert_eq!(view._0(), -2);
+    assert_eq!(view._1().to_bits(), 0.5f32.to_bits());
+}
+
+#[test]
+fn test_bits_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, Default, FrcStructure)]
+    #[allow(clippy::struct_excessive_bools)]
+    struct BitsStatus {
+        id: u8,
+        #[frc(bits)]
+        enabled: bool,
+        #[frc(bits)]
+        estopped: bool,
+        #[frc(bits)]
+        brownout: bool,
+        #[frc(bits)]
+        system_active: bool,
+        #[frc(bits)]
+        ds_attached: bool,
+        #[frc(bits)]
+        fms_attached: bool,
+        #[frc(bits, rename = "auto")]
+        autonomous: bool,
+        #[frc(bits)]
+        test: bool,
+        #[frc(bits)]
+        user_button: bool,
+        voltage: f32,
+        #[frc(bits)]
+        red_alliance: bool,
+        unpacked: bool,
+    }
+
+    assert_eq!(BitsStatus::SIZE, 1 + 2 + 4 + 1 + 1);
+    assert_eq!(
+        BitsStatus::DESCRIPTION.schema(),
+        "uint8 id; bool enabled:1; bool estopped:1; bool brownout:1; bool system_active:1; \
+        bool ds_attached:1; bool fms_attached:1; bool auto:1; bool test:1; \
+        bool user_button:1; float32 voltage; bool red_alliance:1; bool unpacked"
+    );
+
+    let status = BitsStatus {
+        id: 3,
+        enabled: true,
+        brownout: true,
+        autonomous: true,
+        test: true,
+        user_button: true,
+        voltage: 12.5,
+        red_alliance: true,
+        unpacked: true,
+        ..BitsStatus::default()
+    };
+    let bytes = status.pack_to_vec();
+    assert_eq!(bytes.len(), BitsStatus::SIZE);
+    assert_eq!(&bytes[..3], &[3, 0b1100_0101, 0b0000_0001]);
+    assert_eq!(&bytes[7..], &[1, 1]);
+    assert_eq!(BitsStatus::unpack(&mut Cursor::new(&bytes[..])), status);
+    assert_eq!(
+        BitsStatus::try_unpack(&mut Cursor::new(&bytes[..])),
+        Ok(status)
+    );
+    let mut big = Vec::new();
+    status.pack_with_endian(&mut big, Endianness::Big);
+    assert_eq!(
+        BitsStatus::unpack_with_endian(&mut Cursor::new(&big[..]), Endianness::Big),
+        status
+    );
+
+    let view = BitsStatusView::new(&bytes).expect("enough bytes");
+    assert!(view.autonomous() && view.user_button() && view.red_alliance());
+    assert!(!view.estopped());
+    assert_eq!(view.voltage().to_bits(), 12.5f32.to_bits());
+
+    let fields = parse_schema(BitsStatus::DESCRIPTION.schema())
+        .expect("Failed to parse schema")
+        .into_iter()
+        .map(|field| (field.name, field.offset, field.bit))
+        .collect::<Vec<_>>();
+    assert_eq!(fields[1], ("enabled".to_owned(), 1, Some(0)));
+    assert_eq!(fields[7], ("auto".to_owned(), 1, Some(6)));
+    assert_eq!(fields[9], ("user_button".to_owned(), 2, Some(0)));
+    assert_eq!(fields[10], ("voltage".to_owned(), 3, None));
+    assert_eq!(fields[11], ("red_alliance".to_owned(), 7, Some(0)));
+    assert_eq!(fields[12], ("unpacked".to_owned(), 8, None));
+    assert!(BitsStatus::DESCRIPTION
+        .pretty_decode(&bytes)
+        .expect("valid schema")
+        .contains("estopped: false, brownout: true"));
+    assert_eq!(
+        parse_schema("bool a:2"),
+        Err(SchemaParseError::BitField("a".to_owned()))
+    );
+}
+
+#[test]
+#[cfg(feature = "value-union")]
+fn test_frc_value_bridge() {
+    use crate as frclib_core;
+    use crate::value::FrcValue;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct BridgeTest {
+        id: i32,
+        sub: SubStruct,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct SameSize {
+        sub: SubStruct,
+        id: i32,
+    }
+
+    let value = BridgeTest {
+        id: 12,
+        sub: SubStruct { value: -0.5 },
+    };
+    let frc_value = value.to_frc_value();
+    assert!(matches!(&frc_value, FrcValue::Struct(bytes) if bytes.desc.type_str == "BridgeTest"));
+    assert_eq!(BridgeTest::from_frc_value(&frc_value).ok(), Some(value));
+    assert_eq!(frc_value.try_into_struct::<BridgeTest>().ok(), Some(value));
+
+    assert!(BridgeTest::from_frc_value(&FrcValue::Int(12)).is_err());
+    assert!(BridgeTest::from_frc_value(&SubStruct { value: 1.0 }.to_frc_value()).is_err());
+
+    let same_size_value = SameSize {
+        sub: SubStruct { value: -0.5 },
+        id: 12,
+    };
+    let same_size = same_size_value.to_frc_value();
+    assert_eq!(SameSize::SIZE, BridgeTest::SIZE);
+    assert!(
+        BridgeTest::from_frc_value(&same_size).is_err(),
+        "a different struct of the same size must be rejected"
+    );
+    assert!(
+        FrcValue::from_struct_array(&[same_size_value])
+            .try_into_struct_array::<BridgeTest>()
+            .is_err(),
+        "a different struct array of the same size must be rejected"
+    );
+}
+
+#[cfg(feature = "units")]
+#[test]
+fn test_endianness() {
+    use crate as frclib_core;
+    use crate::units::length::Meter;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure, Default)]
+    #[repr(i16)]
+    enum EndianEnum {
+        #[default]
+        A = 1,
+        B = 2,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct EndianTest {
+        flag: bool,
+        id: u16,
+        values: [f32; 2],
+        sub: SubStruct,
+        kind: EndianEnum,
+        distance: Meter,
+    }
+
+    let value = EndianTest {
+        flag: true,
+        id: 0x0102,
+        values: [1.0, -2.0],
+        sub: SubStruct { value: 3.0 },
+        kind: EndianEnum::B,
+        distance: Meter::new(4.0),
+    };
+    let mut expected = vec![1u8];
+    expected.extend_from_slice(&0x0102u16.to_be_bytes());
+    expected.extend_from_slice(&1.0f32.to_be_bytes());
+    expected.extend_from_slice(&(-2.0f32).to_be_bytes());
+    expected.extend_from_slice(&3.0f64.to_be_bytes());
+    expected.extend_from_slice(&2i16.to_be_bytes());
+    expected.extend_from_slice(&4.0f64.to_be_bytes());
+
+    let mut big = Vec::new();
+    value.pack_with_endian(&mut big, Endianness::Big);
+    assert_eq!(big, expected);
+    assert_eq!(
+        EndianTest::unpack_with_endian(&mut Cursor::new(&big[..]), Endianness::Big),
+        value
+    );
+
+    let mut little = Vec::new();
+    value.pack_with_endian(&mut little, Endianness::Little);
+    assert_eq!(little, value.pack_to_vec());
+    assert_eq!(
+        EndianTest::unpack_with_endian(&mut Cursor::new(&little[..]), Endianness::Little),
+        value
+    );
+}
+
+#[test]
+fn test_big_endian_struct() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure, Default)]
+    #[repr(i16)]
+    enum Status {
+        #[default]
+        Ok = 1,
+        Fault = 0x0203,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    #[frc(endian = "big")]
+    struct Coprocessor {
+        id: u8,
+        #[frc(bits)]
+        ready: bool,
+        counts: [u16; 2],
+        status: Status,
+        sub: SubStruct,
+        angle: f32,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Frame {
+        seq: u16,
+        device: Coprocessor,
+    }
+
+    let value = Coprocessor {
+        id: 7,
+        ready: true,
+        counts: [0x0102, 0x0304],
+        status: Status::Fault,
+        sub: SubStruct { value: 1.0 },
+        angle: -2.0,
+    };
+    let golden = [
+        0x07, 0x01, 0x01, 0x02, 0x03, 0x04, 0x02, 0x03, 0x3F, 0xF0, 0, 0, 0, 0, 0, 0, 0xC0, 0x00,
+        0x00, 0x00,
+    ];
+    assert_eq!(value.pack_to_vec(), golden);
+    assert_eq!(Coprocessor::unpack(&mut Cursor::new(&golden[..])), value);
+    assert_eq!(Coprocessor::unpack_slice(&golden), value);
+    assert_eq!(
+        Coprocessor::try_unpack(&mut Cursor::new(&golden[..])),
+        Ok(value)
+    );
+    let view = CoprocessorView::new(&golden).expect("long enough");
+    assert_eq!(view.counts(), [0x0102, 0x0304]);
+    assert_eq!(view.angle().to_bits(), (-2.0f32).to_bits());
+    assert_eq!(
+        Coprocessor::DESCRIPTION.schema(),
+        "uint8 id; bool ready:1; uint16 counts[2]; Status status; Meter sub; float32 angle"
+    );
+
+    let mut little = Vec::new();
+    value.pack_with_endian(&mut little, Endianness::Little);
+    assert_eq!(little[2..4], 0x0102u16.to_le_bytes());
+    assert_eq!(
+        Coprocessor::unpack_with_endian(&mut Cursor::new(&little[..]), Endianness::Little),
+        value
+    );
+
+    let mut invalid = golden;
+    invalid[6] = 0x09;
+    assert_eq!(
+        Coprocessor::try_unpack(&mut Cursor::new(&invalid[..])),
+        Err(StructUnpackError::InvalidDiscriminant {
+            type_str: "Status",
+            discriminant: "2307".to_owned()
+        })
+    );
+
+    //stored data stays in the spec order so it matches the schema
+    assert_eq!(Coprocessor::ENDIAN, Endianness::Big);
+    assert_eq!(<[Coprocessor; 2]>::ENDIAN, Endianness::Big);
+    FrcStructDescDB::register::<Status>();
+    FrcStructDescDB::register::<SubStruct>();
+    FrcStructDescDB::register::<Coprocessor>();
+    let bytes = FrcStructureBytes::pack_many(&[value]);
+    assert_eq!(bytes.data[..], little[..]);
+    assert_eq!(bytes.validate(), Ok(()));
+    assert_eq!(bytes.to_vec::<Coprocessor>(), Ok(vec![value]));
+
+    let frame = Frame {
+        seq: 0x0A0B,
+        device: value,
+    };
+    let packed = frame.pack_to_vec();
+    assert_eq!(packed[..2], 0x0A0Bu16.to_le_bytes());
+    assert_eq!(packed[2..], little[..]);
+    assert_eq!(Frame::unpack_slice(&packed), frame);
+    assert_eq!(Frame::try_unpack(&mut Cursor::new(&packed[..])), Ok(frame));
+    assert_eq!(
+        FrameView::new(&packed).expect("long enough").device(),
+        value
+    );
+}
+
+#[test]
+fn test_schema_hash() {
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "float64 x".to_owned(),
+        type_str: "HashInnerA",
+        size: 8,
+    });
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "int64 x".to_owned(),
+        type_str: "HashInnerB",
+        size: 8,
+    });
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "float64 x".to_owned(),
+        type_str: "HashInnerACopy",
+        size: 8,
+    });
+
+    // nested structures are hashed by their contents rather than their name
+    let hash = |schema: &str| schema::schema_hash("HashOuter", schema);
+    let expanded = hash("bool flag; HashInnerA inner").expect("valid schema");
+    assert_eq!(hash("bool flag; HashInnerACopy inner"), Ok(expanded));
+    assert_eq!(hash("bool flag; HashInnerA inner"), Ok(expanded));
+    assert_ne!(
+        hash("bool flag; HashInnerB inner").expect("valid schema"),
+        expanded
+    );
+    assert_ne!(
+        hash("bool flag; HashInnerA other").expect("valid schema"),
+        expanded
+    );
+    assert_eq!(
+        hash("bool flag; NotRegistered inner"),
+        Err(SchemaParseError::UnknownType("NotRegistered".to_owned()))
+    );
+
+    assert_eq!(
+        SubStruct::schema_hash(),
+        SubStruct::DESCRIPTION.schema_hash()
+    );
+    assert_ne!(f64::schema_hash(), f32::schema_hash());
+}
+
+#[test]
+fn test_schema_matches() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct MatchPose {
+        x: f32,
+        y: f32,
+        sub: SubStruct,
+    }
+
+    FrcStructDescDB::register::<SubStruct>();
+    assert!(MatchPose::schema_matches("float32 x; float32 y; Meter sub"));
+    assert!(MatchPose::schema_matches(
+        "enum {a=1} float x;\tfloat y; Meter sub;"
+    ));
+    assert!(!MatchPose::schema_matches(
+        "float32 y; float32 x; Meter sub"
+    ));
+    assert!(!MatchPose::schema_matches(
+        "float64 x; float32 y; Meter sub"
+    ));
+    assert!(!MatchPose::schema_matches("float32 x; float32 y"));
+    assert!(!MatchPose::schema_matches(
+        "float32 x; float32 y; NotRegistered sub"
+    ));
+    assert!(!MatchPose::schema_matches("float32 x y"));
+    assert!(f64::schema_matches(""));
+
+    assert!(MatchPose::schema_compatible(
+        "float32 x; float32 y; Meter sub"
+    ));
+    assert!(MatchPose::schema_compatible(
+        "float32 x; float32 y; Meter sub; bool extra"
+    ));
+    assert!(!MatchPose::schema_compatible("float32 x; float32 y"));
+    assert!(!MatchPose::schema_compatible(
+        "bool extra; float32 x; float32 y; Meter sub"
+    ));
+}
+
+#[test]
+fn test_desc_eq() {
+    fn derived_schema() -> String {
+        "float64 value".to_owned()
+    }
+    fn manual_schema() -> String {
+        String::from("float64 value")
+    }
+    fn other_schema() -> String {
+        "int64 value".to_owned()
+    }
+
+    let derived = FrcStructDesc {
+        schema_supplier: derived_schema,
+        type_str: "DescEq",
+        size: 8,
+    };
+    let manual = FrcStructDesc {
+        schema_supplier: manual_schema,
+        ..derived
+    };
+    let other = FrcStructDesc {
+        schema_supplier: other_schema,
+        ..derived
+    };
+    assert_eq!(derived, manual);
+    assert_eq!(derived.cmp(&manual), std::cmp::Ordering::Equal);
+    assert_ne!(derived, other);
+    assert!(derived < other, "descriptions are ordered by their schema");
+    assert_ne!(derived, FrcStructDesc { size: 4, ..manual });
+    assert_ne!(
+        derived,
+        FrcStructDesc {
+            type_str: "DescEqOther",
+            ..manual
+        }
+    );
+
+    // each description gets its own cached schema even when the `type_str` is shared
+    assert_eq!(derived.schema(), derived_schema());
+    assert_eq!(other.schema(), other_schema());
+    assert_ne!(derived, other);
+}
+
+#[test]
+fn test_unpack_compat() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, Default, FrcStructure)]
+    struct TelemetryV1 {
+        x: f64,
+        id: u8,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct TelemetryV2 {
+        x: f64,
+        id: u8,
+        current: f32,
+    }
+
+    impl Default for TelemetryV2 {
+        fn default() -> Self {
+            Self {
+                x: 0.0,
+                id: 0,
+                current: -1.0,
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, Default, FrcStructure)]
+    struct TelemetryOther {
+        id: u8,
+        x: f64,
+    }
+
+    let new = TelemetryV2 {
+        x: 1.5,
+        id: 4,
+        current: 20.0,
+    };
+    let mut bytes = new.pack_to_vec();
+    new.pack(&mut bytes);
+    let mut cursor = Cursor::new(&bytes[..]);
+    for _ in 0..2 {
+        assert_eq!(
+            TelemetryV1::try_unpack_compat(&mut cursor, &TelemetryV2::DESCRIPTION),
+            Ok(TelemetryV1 { x: 1.5, id: 4 })
+        );
+    }
+    assert_eq!(remaining(&cursor), 0);
+
+    let old = TelemetryV1 { x: -2.0, id: 9 }.pack_to_vec();
+    let mut cursor = Cursor::new(&old[..]);
+    assert_eq!(
+        TelemetryV2::try_unpack_compat(&mut cursor, &TelemetryV1::DESCRIPTION),
+        Ok(TelemetryV2 {
+            x: -2.0,
+            id: 9,
+            current: -1.0
+        })
+    );
+    assert_eq!(remaining(&cursor), 0);
+    assert_eq!(
+        TelemetryV2::try_unpack_compat(&mut Cursor::new(&bytes[..]), &TelemetryV2::DESCRIPTION),
+        Ok(new)
+    );
+
+    assert_eq!(
+        TelemetryOther::try_unpack_compat(&mut Cursor::new(&old[..]), &TelemetryV1::DESCRIPTION),
+        Err(StructUnpackError::IncompatibleSchema {
+            type_str: "TelemetryOther",
+            foreign: "TelemetryV1"
+        })
+    );
+    assert_eq!(
+        TelemetryV1::try_unpack_compat(&mut Cursor::new(&old[..]), &TelemetryV2::DESCRIPTION),
+        Err(StructUnpackError::InsufficientBytes {
+            type_str: "TelemetryV2",
+            needed: 13,
+            available: 9
+        })
+    );
+}
+
+#[test]
+fn test_schema_json() {
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "double x; double y".to_owned(),
+        type_str: "JsonInner",
+        size: 16,
+    });
+    let desc = FrcStructDesc {
+        schema_supplier: || "int8 id; JsonInner pos[2]".to_owned(),
+        type_str: "Json\"Outer",
+        size: 33,
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(&desc.schema_json().expect("valid schema")).expect("valid json");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "type": "Json\"Outer",
+            "size": 33,
+            "fields": [
+                {"name": "id", "type": "int8", "offset": 0, "count": 1},
+                {"name": "pos[0].x", "type": "float64", "offset": 1, "count": 1},
+                {"name": "pos[0].y", "type": "float64", "offset": 9, "count": 1},
+                {"name": "pos[1].x", "type": "float64", "offset": 17, "count": 1},
+                {"name": "pos[1].y", "type": "float64", "offset": 25, "count": 1},
+            ]
+        })
+    );
+    assert_eq!(
+        SubStruct::schema_json(),
+        Ok(r#"{"type":"Meter","size":8,"fields":[{"name":"value","type":"float64","offset":0,"count":1}]}"#.to_owned())
+    );
+}
+
+#[test]
+fn test_rust_source() {
+    assert_eq!(
+        rust_source(
+            "Received",
+            "enum {a=1, b=2} int8 mode; char name[4]; uint16 ids[1]; Unregistered inner[2]"
+        ),
+        Ok(concat!(
+            "#[derive(FrcStructure)]\n",
+            "pub struct Received {\n",
+            "    pub mode: i8,\n",
+            "    pub name: [char; 4],\n",
+            "    pub ids: [u16; 1],\n",
+            "    pub inner: [Unregistered; 2],\n",
+            "}\n"
+        )
+        .to_owned())
+    );
+    assert_eq!(
+        rust_source(
+            "Keywords",
+            "uint8 type; double match[2]; bool self:1; int32 crate"
+        ),
+        Ok(concat!(
+            "#[derive(FrcStructure)]\n",
+            "pub struct Keywords {\n",
+            "    pub r#type: u8,\n",
+            "    pub r#match: [f64; 2],\n",
+            "    #[frc(bits, rename = \"self\")]\n",
+            "    pub self_: bool,\n",
+            "    #[frc(rename = \"crate\")]\n",
+            "    pub crate_: i32,\n",
+            "}\n"
+        )
+        .to_owned())
+    );
+    assert_eq!(
+        rust_source("Broken", "double; int8 val"),
+        Err(SchemaParseError::MalformedField("double".to_owned()))
+    );
+}
+
+#[test]
+fn test_keyword_fields() {
+    use crate as frclib_core;
+
+    // what `rust_source` generates for "uint8 type; double match[2]; bool self:1; int32 crate"
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Keywords {
+        r#type: u8,
+        r#match: [f64; 2],
+        #[frc(bits, rename = "self")]
+        self_: bool,
+        #[frc(rename = "crate")]
+        crate_: i32,
+    }
+
+    assert_eq!(
+        Keywords::DESCRIPTION.schema(),
+        "uint8 type; float64 match[2]; bool self:1; int32 crate"
+    );
+}
+
+#[test]
+fn test_field_layout() {
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "float x; float y".to_owned(),
+        type_str: "LayoutInner",
+        size: 8,
+    });
+    let desc = FrcStructDesc {
+        schema_supplier: || "uint8 id; LayoutInner points[2]".to_owned(),
+        type_str: "LayoutOuter",
+        size: 17,
+    };
+    let layout = desc.field_layout().expect("valid schema");
+    let names = layout
+        .iter()
+        .map(|field| field.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "id",
+            "points[0].x",
+            "points[0].y",
+            "points[1].x",
+            "points[1].y"
+        ]
+    );
+    assert_eq!(
+        desc.field_offset("points[1].x"),
+        Some((9, StructureFieldTypes::Float32(1)))
+    );
+    assert_eq!(desc.field_offset("points"), None);
+    assert_eq!(
+        f64::DESCRIPTION.field_layout(),
+        Ok(vec![SchemaField {
+            name: String::new(),
+            offset: 0,
+            field_type: StructureFieldTypes::Float64(1),
+            bit: None
+        }])
+    );
+}
+
+#[test]
+fn test_pretty_decode() {
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "double x; double y".to_owned(),
+        type_str: "PrettyInner",
+        size: 16,
+    });
+    let desc = FrcStructDesc {
+        schema_supplier: || "bool on; char name[4]; int16 vals[2]; PrettyInner pos".to_owned(),
+        type_str: "PrettyOuter",
+        size: 25,
+    };
+    let mut bytes = vec![1u8];
+    bytes.extend_from_slice(b"ab\0\0");
+    bytes.extend_from_slice(&(-3i16).to_le_bytes());
+    bytes.extend_from_slice(&7i16.to_le_bytes());
+    bytes.extend_from_slice(&1.5f64.to_le_bytes());
+    bytes.extend_from_slice(&(-0.25f64).to_le_bytes());
+    assert_eq!(
+        desc.pretty_decode(&bytes).expect("valid schema"),
+        r#"PrettyOuter { on: true, name: "ab", vals: [-3, 7], pos.x: 1.5, pos.y: -0.25 }"#
+    );
+    assert_eq!(
+        desc.pretty_decode(&bytes[..17]).expect("valid schema"),
+        r#"PrettyOuter { on: true, name: "ab", vals: [-3, 7], pos.x: 1.5, pos.y: <missing> }"#
+    );
+    assert_eq!(
+        f64::DESCRIPTION.pretty_decode(&2.0f64.to_le_bytes()),
+        Ok("2.0".to_owned())
+    );
+}
+
+#[test]
+fn test_schema() {
+    const SCHEMA: &str = "enum {a=1, b=2} int8 val[3]";
+    let fields = parse_schema(SCHEMA).expect("Failed to parse schema");
+    assert_eq!(fields.len(), 1);
+    assert_eq!(
+        fields[0],
+        SchemaField {
+            name: "val".to_owned(),
+            offset: 0,
+            field_type: StructureFieldTypes::Int8(3),
+            bit: None
+        }
+    );
+}
+
+#[test]
+fn test_schema_advanced() {
+    const SCHEMA: &str = "Rotation2d rot; Translation2d trans[2]; bool flag;";
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "double value".to_owned(),
+        type_str: "Rotation2d",
+        size: 8,
+    });
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "double x; double y".to_owned(),
+        type_str: "Translation2d",
+        size: 16,
+    });
+    let fields = parse_schema(SCHEMA)
+        .expect("Failed to parse schema")
+        .into_iter()
+        .map(|field| (field.name, field.offset, field.field_type))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            ("rot.value".to_owned(), 0, StructureFieldTypes::Float64(1)),
+            ("trans[0].x".to_owned(), 8, StructureFieldTypes::Float64(1)),
+            ("trans[0].y".to_owned(), 16, StructureFieldTypes::Float64(1)),
+            ("trans[1].x".to_owned(), 24, StructureFieldTypes::Float64(1)),
+            ("trans[1].y".to_owned(), 32, StructureFieldTypes::Float64(1)),
+            ("flag".to_owned(), 40, StructureFieldTypes::Bool(1)),
+        ]
+    );
+}
+
+#[test]
+fn test_schema_errors() {
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "float x; SchemaLoop next".to_owned(),
+        type_str: "SchemaLoop",
+        size: 4,
+    });
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "float x".to_owned(),
+        type_str: "SchemaWrongSize",
+        size: 8,
+    });
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "Meter m[256]".to_owned(),
+        type_str: "SchemaNested",
+        size: 8 * 256,
+    });
+    assert_eq!(
+        parse_schema("int8 a; NotAStruct b"),
+        Err(SchemaParseError::UnknownType("NotAStruct".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("int8 a b"),
+        Err(SchemaParseError::MalformedField("int8 a b".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("int8 a:4"),
+        Err(SchemaParseError::BitField("a".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("int8 $a"),
+        Err(SchemaParseError::InvalidToken(5))
+    );
+    assert_eq!(
+        parse_schema("SchemaLoop root"),
+        Err(SchemaParseError::Recursive("SchemaLoop".to_owned()))
+    );
+    assert!(matches!(
+        parse_schema("SchemaWrongSize s"),
+        Err(SchemaParseError::SizeMismatch {
+            expected: 8,
+            actual: 4,
+            ..
+        })
+    ));
+    assert_eq!(
+        parse_schema("int64 a[2305843009213693952]"),
+        Err(SchemaParseError::TooLarge("a".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("uint8 a[9223372036854775807]; uint8 b[9223372036854775807]; uint8 c[10]"),
+        Err(SchemaParseError::TooLarge("c".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("SchemaWrongSize s[4611686018427387904]"),
+        Err(SchemaParseError::TooLarge("s".to_owned()))
+    );
+    // fits in memory but would flatten a trillion elements
+    assert_eq!(
+        parse_schema("Meter m[1000000000000]"),
+        Err(SchemaParseError::TooLarge("m".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("SchemaNested n[512]"),
+        Err(SchemaParseError::TooLarge("n[254].m".to_owned()))
+    );
+    assert_eq!(
+        parse_schema("Meter m[65536]").map(|fields| fields.len()),
+        Ok(65536)
+    );
+}
+
+#[test]
+fn test_concurrent_add() {
+    let handles = (0..8)
+        .map(|_| {
+            std::thread::spawn(|| {
+                FrcStructDescDB::add(FrcStructDesc {
+                    schema_supplier: || "int32 value".to_owned(),
+                    type_str: "ConcurrentAdd",
+                    size: 4,
+                });
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().expect("registration thread panicked");
+    }
+    assert!(
+        FrcStructDescDB::contains_type("ConcurrentAdd"),
+        "type should be registered"
+    );
+    #[cfg(all(feature = "inventory", not(feature = "runtime-registry")))]
+    assert_eq!(
+        inventory::iter::<FrcStructDesc>
+            .into_iter()
+            .filter(|desc| desc.type_str == "ConcurrentAdd")
+            .count(),
+        1,
+        "type should only be submitted once"
+    );
+}
+
+#[test]
+fn test_get_or_register() {
+    #[derive(Clone, Copy)]
+    struct Unsubmitted;
+    impl FrcStructure for Unsubmitted {
+        const TYPE: &'static str = "GetOrRegister";
+        const SIZE: usize = 0;
+        const SCHEMA_SUPPLIER: fn() -> String = String::new;
+
+        fn pack(&self, _: &mut Vec<u8>) {}
+
+        fn unpack(_: &mut Cursor<&[u8]>) -> Self {
+            Self
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct Conflicting;
+    impl FrcStructure for Conflicting {
+        const TYPE: &'static str = "GetOrRegister";
+        const SIZE: usize = 1;
+        const SCHEMA_SUPPLIER: fn() -> String = || "uint8 value".to_owned();
+
+        fn pack(&self, buffer: &mut Vec<u8>) {
+            buffer.push(0);
+        }
+
+        fn unpack(buffer: &mut Cursor<&[u8]>) -> Self {
+            let _ = u8::unpack(buffer);
+            Self
+        }
+    }
+
+    assert!(!FrcStructDescDB::contains_type("GetOrRegister"));
+    let descs = (0..8)
+        .map(|_| std::thread::spawn(FrcStructDescDB::get_or_register::<Unsubmitted>))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().expect("registration thread panicked"))
+        .collect::<Vec<_>>();
+    let registered = FrcStructDescDB::get("GetOrRegister").expect("registered");
+    assert!(
+        descs
+            .into_iter()
+            .all(|desc| desc.is_ok_and(|desc| std::ptr::eq(desc, registered))),
+        "every call should return the registered description"
+    );
+    assert!(
+        FrcStructDescDB::get_or_register::<Unsubmitted>()
+            .is_ok_and(|desc| std::ptr::eq(desc, registered)),
+        "later calls should return the same description"
+    );
+
+    assert_eq!(
+        FrcStructDescDB::get_or_register::<Conflicting>(),
+        Err(FrcStructureError::ConflictingDescription("GetOrRegister"))
+    );
+}
+
+#[test]
+#[cfg(feature = "tracing")]
+fn test_registration_tracing() {
+    use std::fmt::Write;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the fields of every event as `name=value` pairs
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let _ = write!(self.0, "{}={:?} ", field.name(), value);
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(String::new());
+            event.record(&mut fields);
+            self.0
+                .lock()
+                .expect("recorder lock poisoned")
+                .push(fields.0.trim_end().to_owned());
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    tracing::subscriber::with_default(Recorder(Arc::clone(&events)), || {
+        FrcStructDescDB::add(FrcStructDesc {
+            schema_supplier: || "int32 value".to_owned(),
+            type_str: "TracedRegistration",
+            size: 4,
+        });
+        FrcStructDescDB::add(FrcStructDesc {
+            schema_supplier: || "int32 value".to_owned(),
+            type_str: "TracedRegistration",
+            size: 4,
+        });
+        FrcStructDescDB::add(FrcStructDesc {
+            schema_supplier: || "uint32 value".to_owned(),
+            type_str: "TracedRegistration",
+            size: 4,
+        });
+        FrcStructDescDB::add(FrcStructDesc {
+            schema_supplier: || "float64 value".to_owned(),
+            type_str: "TracedRegistration",
+            size: 8,
+        });
+    });
+    assert_eq!(
+        *events.lock().expect("recorder lock poisoned"),
+        vec![
+            "message=registered structure description type_str=\"TracedRegistration\" size=4",
+            "message=skipped structure description, the type is already registered type_str=\"TracedRegistration\" same_schema=true",
+            "message=skipped structure description, the type is already registered type_str=\"TracedRegistration\" same_schema=false",
+            "message=skipped structure description, the type is already registered type_str=\"TracedRegistration\" same_schema=false",
+        ]
+    );
+}
+
+#[test]
+#[cfg(not(all(feature = "inventory", not(feature = "runtime-registry"))))]
+fn test_runtime_registry() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct RuntimeOnly {
+        value: f64,
+    }
+
+    assert!(!FrcStructDescDB::contains_type("RuntimeOnly"));
+    #[cfg(feature = "inventory")]
+    assert!(inventory::iter::<FrcStructDesc>
+        .into_iter()
+        .all(|desc| desc.type_str != "RuntimeOnly"));
+
+    FrcStructDescDB::register::<RuntimeOnly>();
+    FrcStructDescDB::register::<RuntimeOnly>();
+    assert_eq!(
+        FrcStructDescDB::get("RuntimeOnly"),
+        Some(&RuntimeOnly::DESCRIPTION)
+    );
+    assert_eq!(
+        FrcStructDescDB::iter()
+            .filter(|desc| desc.type_str == "RuntimeOnly")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_catalog_json() {
+    let listed = FrcStructDesc {
+        schema_supplier: || "int8 \"value\"".to_owned(),
+        type_str: "CatalogListed",
+        size: 1,
+    };
+    FrcStructDescDB::add(listed);
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "double value".to_owned(),
+        type_str: "CatalogHashed",
+        size: 8,
+    });
+    let catalog: Vec<serde_json::Value> =
+        serde_json::from_str(&FrcStructDescDB::catalog_json()).expect("valid json");
+    let entry = |type_str: &str| {
+        catalog
+            .iter()
+            .find(|entry| entry["type"] == type_str)
+            .cloned()
+    };
+    assert_eq!(
+        entry("CatalogListed"),
+        Some(serde_json::json!({
+            "type": "CatalogListed",
+            "size": 1,
+            "schema": "int8 \"value\"",
+            "hash": null,
+        }))
+    );
+    let hash = FrcStructDescDB::get("CatalogHashed")
+        .expect("registered")
+        .schema_hash()
+        .expect("valid schema");
+    assert_eq!(
+        entry("CatalogHashed").map(|entry| entry["hash"].clone()),
+        Some(serde_json::Value::String(format!("{hash:016x}")))
+    );
+    assert!(
+        catalog
+            .windows(2)
+            .all(|pair| matches!(pair, [a, b] if a["type"].as_str() < b["type"].as_str())),
+        "entries should be sorted by type"
+    );
+}
+
+#[test]
+fn test_db_iter() {
+    FrcStructDescDB::register::<SubStruct>();
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "int8 value".to_owned(),
+        type_str: "IterListed",
+        size: 1,
+    });
+    let types = FrcStructDescDB::iter()
+        .map(|desc| desc.type_str)
+        .collect::<Vec<_>>();
+    assert!(
+        types.contains(&"IterListed"),
+        "runtime types should be listed"
+    );
+    assert!(
+        types.contains(&SubStruct::TYPE),
+        "submitted types should be listed"
+    );
+    assert!(
+        types.windows(2).all(|pair| matches!(pair, [a, b] if a < b)),
+        "types should be sorted and unique"
+    );
+    assert!(
+        FrcStructDescDB::len() >= types.len(),
+        "other tests may register types concurrently"
+    );
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn test_arbitrary_bytes() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    FrcStructDescDB::register::<SubStruct>();
+    let seed = (0..=255u8).cycle().take(4096).collect::<Vec<_>>();
+    let mut u = Unstructured::new(&seed);
+    for _ in 0..16 {
+        let valid =
+            FrcStructureBytes::arbitrary_for(&SubStruct::DESCRIPTION, &mut u).expect("enough data");
+        assert!(
+            FrcStructureBytes::try_from_parts(valid.desc, valid.count, valid.data).is_ok(),
+            "generated bytes should match the description"
+        );
+        let malformed = FrcStructureBytes::arbitrary_malformed_for(&SubStruct::DESCRIPTION, &mut u)
+            .expect("enough data");
+        assert!(
+            FrcStructureBytes::try_from_parts(malformed.desc, malformed.count, malformed.data)
+                .is_err(),
+            "malformed bytes should not match the description"
+        );
+    }
+    let any = FrcStructureBytes::arbitrary(&mut u).expect("registered descriptions");
+    assert_eq!(any.data.len(), any.count * any.desc.size);
+}
+
+#[test]
+fn test_cached_schema() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static DESC: FrcStructDesc = FrcStructDesc {
+        schema_supplier: || {
+            let _ = CALLS.fetch_add(1, Ordering::SeqCst);
+            "double value".to_owned()
+        },
+        type_str: "CachedSchema",
+        size: 8,
+    };
+    assert_eq!(DESC.schema(), "double value");
+    assert_eq!(DESC.schema(), "double value");
+    assert_eq!(
+        CALLS.load(Ordering::SeqCst),
+        1,
+        "schema supplier should only be called once"
+    );
+}
+
+#[test]
+#[cfg(feature = "value-union")]
+fn test_dynamic_layout_cache() {
+    static DESC: FrcStructDesc = FrcStructDesc {
+        schema_supplier: || "int32 a; float b".to_owned(),
+        type_str: "DynamicCached",
+        size: 8,
+    };
+    static RESIZED: FrcStructDesc = FrcStructDesc {
+        schema_supplier: || "int32 a; float b".to_owned(),
+        type_str: "DynamicCached",
+        size: 12,
+    };
+
+    let first = dynamic::field_layout(&DESC).expect("valid schema");
+    let second = dynamic::field_layout(&DESC).expect("valid schema");
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+    assert!(matches!(
+        dynamic::field_layout(&RESIZED),
+        Err(SchemaParseError::SizeMismatch { .. })
+    ));
+    assert!(DynamicStructure::try_new(&DESC, vec![0; 8].into_boxed_slice()).is_ok());
+}
+
+#[test]
+#[cfg(feature = "value-union")]
+fn test_dynamic_structure() {
+    use crate::value::FrcValue;
+
+    static DESC: FrcStructDesc = FrcStructDesc {
+        schema_supplier: || "int16 id; char name[4]; double pos[2]; DynamicSub sub".to_owned(),
+        type_str: "DynamicTest",
+        size: 2 + 4 + 16 + 9,
+    };
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "bool enabled; uint64 ticks".to_owned(),
+        type_str: "DynamicSub",
+        size: 9,
+    });
+
+    assert_eq!(
+        DynamicStructure::try_new(&DESC, vec![0; 3].into_boxed_slice()),
+        Err(FrcStructureError::Dynamic(
+            DynamicStructureError::BufferSize {
+                expected: 31,
+                actual: 3
+            }
+        ))
+    );
+
+    let mut buffer = Vec::new();
+    (-3i16).pack(&mut buffer);
+    buffer.extend_from_slice(b"ab\0\0");
+    [1.5f64, -2.0].pack(&mut buffer);
+    true.pack(&mut buffer);
+    7u64.pack(&mut buffer);
+    let mut dynamic =
+        DynamicStructure::try_new(&DESC, buffer.into_boxed_slice()).expect("valid buffer");
+
+    assert_eq!(dynamic.get_field("id"), Some(FrcValue::Int(-3)));
+    assert_eq!(
+        dynamic.get_field("name"),
+        Some(FrcValue::String("ab".into()))
+    );
+    assert_eq!(
+        dynamic.get_field("pos"),
+        Some(FrcValue::DoubleArray(vec![1.5, -2.0].into_boxed_slice()))
+    );
+    assert_eq!(
+        dynamic.get_field("sub.enabled"),
+        Some(FrcValue::Boolean(true))
+    );
+    assert_eq!(dynamic.get_field("sub.ticks"), Some(FrcValue::Int(7)));
+    assert_eq!(dynamic.get_field("sub"), None);
+
+    dynamic
+        .set_field("id", &FrcValue::Int(300))
+        .expect("in range");
+    dynamic
+        .set_field("sub.ticks", &FrcValue::Int(9))
+        .expect("in range");
+    assert_eq!(dynamic.get_field("id"), Some(FrcValue::Int(300)));
+    assert_eq!(dynamic.get_field("sub.ticks"), Some(FrcValue::Int(9)));
+    assert_eq!(
+        dynamic.set_field("id", &FrcValue::Int(i64::MAX)),
+        Err(FrcStructureError::Dynamic(
+            DynamicStructureError::FieldTypeMismatch("id".to_owned())
+        ))
+    );
+    assert_eq!(
+        dynamic.set_field("pos", &FrcValue::Double(1.0)),
+        Err(FrcStructureError::Dynamic(
+            DynamicStructureError::FieldTypeMismatch("pos".to_owned())
+        ))
+    );
+    assert_eq!(
+        dynamic.set_field("missing", &FrcValue::Int(0)),
+        Err(FrcStructureError::Dynamic(
+            DynamicStructureError::UnknownField("missing".to_owned())
+        ))
+    );
+    assert_eq!(&dynamic.as_bytes()[..2], &300i16.to_le_bytes());
+}
+
+#[test]
+#[cfg(feature = "value-union")]
+fn test_try_unpack() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure, Default)]
+    #[repr(u8)]
+    enum Mode {
+        #[default]
+        Idle,
+        Running,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Packet {
+        mode: Mode,
+        modes: [Mode; 2],
+        value: f64,
+    }
+
+    let packet = Packet {
+        mode: Mode::Running,
+        modes: [Mode::Idle, Mode::Running],
+        value: 2.5,
+    };
+    let mut buffer = Vec::new();
+    packet.pack(&mut buffer);
+    assert_eq!(
+        Packet::try_unpack(&mut Cursor::new(buffer.as_slice())),
+        Ok(packet)
+    );
+
+    let mut short = Cursor::new(&buffer[..7]);
+    assert_eq!(
+        Packet::try_unpack(&mut short),
+        Err(StructUnpackError::InsufficientBytes {
+            type_str: "Packet",
+            needed: 11,
+            available: 7
+        })
+    );
+    assert_eq!(short.position(), 0);
+
+    buffer[2] = 7;
+    assert_eq!(
+        Packet::try_unpack(&mut Cursor::new(buffer.as_slice())),
+        Err(StructUnpackError::InvalidDiscriminant {
+            type_str: "Mode",
+            discriminant: "7".to_owned()
+        })
+    );
+}
+
+#[test]
+fn test_enum_discriminants() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure, Default)]
+    #[repr(i8)]
+    enum Direction {
+        Reverse = -2,
+        Coast,
+        #[default]
+        Stopped = 5,
+        Forward = 40,
+        Boost,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure, Default)]
+    #[repr(u64)]
+    enum Wide {
+        #[default]
+        Low = 1,
+        High = 18_446_744_073_709_551_615,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure, Default)]
+    #[repr(i64)]
+    enum Signed {
+        Min = -9_223_372_036_854_775_808,
+        #[default]
+        Zero = 0,
+        Max = 9_223_372_036_854_775_807,
+    }
+
+    assert_eq!(
+        Direction::DESCRIPTION.schema(),
+        "enum {Reverse=-2, Coast=-1, Stopped=5, Forward=40, Boost=41} int8 variant"
+    );
+    for direction in [
+        Direction::Reverse,
+        Direction::Coast,
+        Direction::Stopped,
+        Direction::Forward,
+        Direction::Boost,
+    ] {
+        let packed = direction.pack_to_vec();
+        assert_eq!(packed, [direction as u8]);
+        assert_eq!(
+            Direction::try_unpack(&mut Cursor::new(packed.as_slice())),
+            Ok(direction)
+        );
+    }
+    assert_eq!(
+        Direction::try_unpack(&mut Cursor::new([0u8].as_slice())),
+        Err(StructUnpackError::InvalidDiscriminant {
+            type_str: "Direction",
+            discriminant: "0".to_owned()
+        })
+    );
+    assert_eq!(Direction::unpack_slice(&[0xFF]), Direction::Coast);
+
+    for wide in [Wide::Low, Wide::High] {
+        assert_eq!(
+            Wide::try_unpack(&mut Cursor::new(wide.pack_to_vec().as_slice())),
+            Ok(wide)
+        );
+    }
+    assert_eq!(Wide::High.pack_to_vec(), u64::MAX.to_le_bytes());
+    for signed in [Signed::Min, Signed::Zero, Signed::Max] {
+        assert_eq!(
+            Signed::try_unpack(&mut Cursor::new(signed.pack_to_vec().as_slice())),
+            Ok(signed)
+        );
+    }
+    assert_eq!(Signed::Min.pack_to_vec(), i64::MIN.to_le_bytes());
+}
+
+#[test]
+fn test_pack_to_vec() {
+    let sub = SubStruct { value: 3.0 };
+    let packed = sub.pack_to_vec();
+    assert_eq!(packed.len(), SubStruct::SIZE);
+    assert_eq!(packed.capacity(), SubStruct::SIZE);
+    assert_eq!(packed, 3.0f64.to_le_bytes());
+    assert_eq!(sub.pack_to_boxed().as_ref(), packed.as_slice());
+}
+
+#[test]
+fn test_pack_fixed() {
+    let sub = SubStruct { value: 3.0 };
+    let bytes: [u8; 8] = sub.pack_fixed();
+    assert_eq!(bytes.as_slice(), sub.pack_to_vec().as_slice());
+    assert_eq!(SubStruct::unpack_fixed(&bytes), sub);
+
+    let pair = [sub, SubStruct { value: -1.0 }];
+    let bytes: [u8; 16] = pair.pack_fixed();
+    assert_eq!(<[SubStruct; 2]>::unpack_fixed(&bytes), pair);
+}
+
+#[test]
+fn test_unpack_array() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, Default, FrcStructure)]
+    #[repr(u8)]
+    enum ArrayMode {
+        #[default]
+        Idle,
+        Run = 7,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct ArrayPacket {
+        mode: ArrayMode,
+        #[frc(bits)]
+        enabled: bool,
+        #[frc(bits)]
+        homed: bool,
+        speeds: [i16; 3],
+        tag: char,
+        latency: Option<f32>,
+        sub: SubStruct,
+    }
+
+    let packet = ArrayPacket {
+        mode: ArrayMode::Run,
+        enabled: true,
+        homed: false,
+        speeds: [-3, 0, 500],
+        tag: 'z',
+        latency: Some(0.125),
+        sub: SubStruct { value: -2.5 },
+    };
+    let bytes: [u8; ArrayPacket::SIZE] = packet.pack_fixed();
+    assert_eq!(ArrayPacket::unpack_array(&bytes), packet);
+    assert_eq!(
+        ArrayPacket::unpack_slice(&bytes),
+        ArrayPacket::unpack(&mut Cursor::new(&bytes[..]))
+    );
+
+    // short slices read the missing bytes as zero like `unpack` does
+    for len in [0, 4, ArrayPacket::SIZE - 3] {
+        assert_eq!(
+            ArrayPacket::unpack_slice(&bytes[..len]),
+            ArrayPacket::unpack(&mut Cursor::new(&bytes[..len])),
+            "slice of {len} bytes"
         );
     }
 }
 
-// #[test]
-// fn test_schema() {
-//     const SCHEMA: &str = "enum {a=1, b=2} int8 val[3]";
-//     let fields = parse_schema_toplevel(SCHEMA);
-//     assert_eq!(fields.len(), 1);
-//     assert_eq!(
-//         fields[0],
-//         ("val".to_owned(), 0, StructureFieldTypes::Int8(3))
-//     );
-// }
-
-// #[test]
-// fn test_schema_advanced() {
-//     const SCHEMA: &str = "Rotation2d rot; Translation2d trans;";
-//     FrcStructDescDB::add(FrcStructDesc {
-//         schema_supplier: || "double value".to_owned(),
-//         type_str: "Rotation2d",
-//         size: 8,
-//     });
-//     FrcStructDescDB::add(FrcStructDesc {
-//         schema_supplier: || "double x; double y".to_owned(),
-//         type_str: "Translation2d",
-//         size: 16,
-//     });
-//     let fields = parse_schema_toplevel(SCHEMA);
-//     assert_eq!(fields.len(), 3);
-//     assert_eq!(
-//         fields,
-//         vec![
-//             (
-//                 "rot.value".to_owned(),
-//                 0usize,
-//                 StructureFieldTypes::Float64(1)
-//             ),
-//             (
-//                 "trans.x".to_owned(),
-//                 8usize,
-//                 StructureFieldTypes::Float64(1)
-//             ),
-//             (
-//                 "trans.y".to_owned(),
-//                 16usize,
-//                 StructureFieldTypes::Float64(1)
-//             )
-//         ]
-//     );
-// }
+#[test]
+fn test_pack_many() {
+    let items = (0..64)
+        .map(|value| SubStruct {
+            value: f64::from(value),
+        })
+        .collect::<Vec<_>>();
+    let bytes = FrcStructureBytes::pack_many(&items);
+    assert_eq!(bytes.desc, &SubStruct::DESCRIPTION);
+    assert_eq!(bytes.count, items.len());
+    assert_eq!(bytes.data.len(), SubStruct::SIZE * items.len());
+
+    let mut naive = Vec::new();
+    for item in &items {
+        item.pack(&mut naive);
+    }
+    assert_eq!(bytes.data.as_ref(), naive.as_slice());
+    assert_eq!(bytes.to_vec::<SubStruct>().ok(), Some(items));
+
+    let empty = FrcStructureBytes::pack_many::<SubStruct>(&[]);
+    assert_eq!(empty.count, 0);
+    assert!(empty.data.is_empty(), "no items should pack to no bytes");
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_par_to_vec() {
+    let items = (0..10_000)
+        .map(|value| SubStruct {
+            value: f64::from(value) * 0.5,
+        })
+        .collect::<Vec<_>>();
+    let bytes = FrcStructureBytes::pack_many(&items);
+    assert_eq!(bytes.par_to_vec::<SubStruct>().ok(), Some(items));
+    assert_eq!(
+        bytes.par_to_vec::<SubStruct>().ok(),
+        bytes.to_vec::<SubStruct>().ok()
+    );
+    assert!(
+        bytes.par_to_vec::<u8>().is_err(),
+        "the stored type has to match"
+    );
+
+    let empty = FrcStructureBytes::pack_many::<SubStruct>(&[]);
+    assert_eq!(empty.par_to_vec::<SubStruct>().ok(), Some(Vec::new()));
+}
+
+#[test]
+fn test_pack_many_trivially_copyable() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    #[repr(C)]
+    struct CopyTranslation {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    #[repr(C)]
+    struct CopyModuleState {
+        speeds: [f32; 2],
+        id: u32,
+        name: StructString<4>,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct UnorderedTranslation {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    #[repr(C)]
+    struct PaddedState {
+        enabled: bool,
+        speed: f64,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    #[repr(C)]
+    struct GappedState {
+        id: u8,
+        speed: u32,
+    }
+
+    const _: () = {
+        assert!(CopyTranslation::TRIVIALLY_COPYABLE.get());
+        assert!(CopyModuleState::TRIVIALLY_COPYABLE.get());
+        assert!(!UnorderedTranslation::TRIVIALLY_COPYABLE.get());
+        assert!(!PaddedState::TRIVIALLY_COPYABLE.get());
+        assert!(!GappedState::TRIVIALLY_COPYABLE.get());
+    };
+
+    let translations = (0..100)
+        .map(|i| CopyTranslation {
+            x: f64::from(i),
+            y: -f64::from(i) / 2.0,
+        })
+        .collect::<Vec<_>>();
+    let bytes = FrcStructureBytes::pack_many(&translations);
+    let mut naive = Vec::new();
+    for translation in &translations {
+        translation.pack(&mut naive);
+    }
+    assert_eq!(bytes.data.as_ref(), naive.as_slice());
+    assert_eq!(bytes.to_vec::<CopyTranslation>().ok(), Some(translations));
+
+    let states = [
+        CopyModuleState {
+            speeds: [1.5, -2.0],
+            id: 7,
+            name: "fl".into(),
+        },
+        CopyModuleState {
+            speeds: [0.0, 3.25],
+            id: 8,
+            name: "fr".into(),
+        },
+    ];
+    let bytes = FrcStructureBytes::pack_many(&states);
+    assert_eq!(bytes.data.as_ref(), states.pack_to_vec().as_slice());
+    assert_eq!(
+        bytes.to_vec::<CopyModuleState>().ok(),
+        Some(states.to_vec())
+    );
+}
+
+#[test]
+fn test_bytes_append() {
+    let mut bytes = FrcStructureBytes::from_parts(
+        &SubStruct::DESCRIPTION,
+        1,
+        SubStruct { value: 1.0 }.pack_to_boxed(),
+    );
+    let other = FrcStructureBytes::from_parts(
+        &SubStruct::DESCRIPTION,
+        2,
+        [SubStruct { value: 2.0 }, SubStruct { value: 3.0 }]
+            .pack_to_vec()
+            .into_boxed_slice(),
+    );
+    assert_eq!(bytes.try_append(&other), Ok(()));
+    assert_eq!(bytes.push(&SubStruct { value: 4.0 }), Ok(()));
+    assert_eq!(bytes.count, 4);
+    assert_eq!(
+        bytes.to_vec::<SubStruct>().ok(),
+        Some(vec![
+            SubStruct { value: 1.0 },
+            SubStruct { value: 2.0 },
+            SubStruct { value: 3.0 },
+            SubStruct { value: 4.0 },
+        ])
+    );
+
+    let floats = FrcStructureBytes::from_parts(&f64::DESCRIPTION, 1, 1.0f64.pack_to_boxed());
+    assert!(matches!(
+        bytes.try_append(&floats),
+        Err(FrcStructureError::TypeMismatch { .. })
+    ));
+    assert!(bytes.push(&1.0f64).is_err());
+    assert_eq!(bytes.count, 4);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_struct_string_serde() {
+    let name = StructString::<8>::new("intake");
+    assert_eq!(
+        serde_json::to_string(&name).expect("Failed to serialize"),
+        r#""intake""#
+    );
+    assert_eq!(
+        serde_json::from_str::<StructString<4>>(r#""shooter""#).expect("Failed to deserialize"),
+        StructString::new("shoo")
+    );
+    assert_eq!(
+        serde_json::from_str::<StructString<8>>(r#""intake""#).expect("Failed to deserialize"),
+        name
+    );
+    assert!(
+        serde_json::from_str::<StructString<8>>("8").is_err(),
+        "only strings deserialize"
+    );
+    assert_eq!(String::from(name), "intake");
+    assert_eq!(StructString::<8>::from("intake".to_owned()), name);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_bytes_serde() {
+    FrcStructDescDB::register::<SubStruct>();
+    let bytes =
+        FrcStructureBytes::pack_many(&[SubStruct { value: 1.0 }, SubStruct { value: -2.5 }]);
+    let json = serde_json::to_string(&bytes).expect("Failed to serialize");
+    let round_trip: FrcStructureBytes = serde_json::from_str(&json).expect("Failed to deserialize");
+    assert_eq!(round_trip, bytes);
+    assert!(
+        std::ptr::eq(
+            round_trip.desc,
+            FrcStructDescDB::get("Meter").expect("registered")
+        ),
+        "the description should come from the database"
+    );
+
+    let unregistered = serde_json::from_str::<FrcStructureBytes>(
+        r#"{"type_str": "NotRegistered", "count": 0, "data": []}"#,
+    )
+    .expect_err("type isn't registered");
+    assert!(
+        unregistered
+            .to_string()
+            .contains("`NotRegistered` is not registered"),
+        "{unregistered}"
+    );
+
+    let short = serde_json::from_str::<FrcStructureBytes>(
+        r#"{"type_str": "Meter", "count": 2, "data": [0, 0, 0, 0, 0, 0, 0, 0]}"#,
+    )
+    .expect_err("data is one structure short");
+    assert!(short.to_string().contains("Expected 16 bytes"), "{short}");
+}
+
+#[test]
+fn test_structure_error() {
+    fn decode(type_str: &str, data: &[u8]) -> Result<Vec<SubStruct>, FrcStructureError> {
+        let desc = FrcStructDescDB::try_get(type_str)?;
+        let _ = parse_schema(desc.schema())?;
+        let count = data.len() / desc.size.max(1);
+        FrcStructureBytes::try_from_parts(desc, count, data.into())?.to_vec()
+    }
+
+    FrcStructDescDB::register::<SubStruct>();
+
+    assert_eq!(
+        decode("Meter", &2.0f64.to_le_bytes()),
+        Ok(vec![SubStruct { value: 2.0 }])
+    );
+    let unknown = decode("NotRegistered", &[]);
+    assert_eq!(
+        unknown,
+        Err(FrcStructureError::UnknownType("NotRegistered".to_owned()))
+    );
+    assert_eq!(
+        unknown.map_err(|err| err.to_string()),
+        Err("Structure type `NotRegistered` is not registered in the `FrcStructDescDB`".to_owned())
+    );
+    assert_eq!(
+        decode("Meter", &[0; 9]),
+        Err(FrcStructureError::LengthMismatch {
+            expected: 8,
+            actual: 9
+        })
+    );
+    assert_eq!(
+        FrcStructureError::from(SchemaParseError::UnknownType("Foo".to_owned())).to_string(),
+        SchemaParseError::UnknownType("Foo".to_owned()).to_string()
+    );
+}
+
+#[test]
+fn test_bytes_get_split() {
+    let subs = [
+        SubStruct { value: 1.0 },
+        SubStruct { value: 2.0 },
+        SubStruct { value: 3.0 },
+        SubStruct { value: 4.0 },
+    ];
+    let bytes = FrcStructureBytes::from_parts(
+        &SubStruct::DESCRIPTION,
+        subs.len(),
+        subs.pack_to_vec().into_boxed_slice(),
+    );
+    assert_eq!(bytes.get::<SubStruct>(2), Some(SubStruct { value: 3.0 }));
+    assert_eq!(bytes.get::<SubStruct>(4), None);
+    assert_eq!(bytes.get::<f64>(0), None);
+
+    let split = bytes.split();
+    assert_eq!(split.len(), 4);
+    for (single, sub) in split.iter().zip(subs) {
+        assert_eq!(single.count, 1);
+        assert_eq!(single.to_vec::<SubStruct>().ok(), Some(vec![sub]));
+    }
+}
+
+#[test]
+fn test_validate() {
+    static FRAME: FrcStructDesc = FrcStructDesc {
+        schema_supplier: || {
+            "enum {} int8 free; ValidateMode modes[2]; enum {a=-1} int16 sign".to_owned()
+        },
+        type_str: "ValidateFrame",
+        size: 5,
+    };
+    static SHORT: FrcStructDesc = FrcStructDesc {
+        schema_supplier: || "uint8 id; enum {a=1} uint8 mode".to_owned(),
+        type_str: "ValidateShort",
+        size: 1,
+    };
+    FrcStructDescDB::add(FrcStructDesc {
+        schema_supplier: || "enum {idle=0, run=2} uint8 mode".to_owned(),
+        type_str: "ValidateMode",
+        size: 1,
+    });
+
+    let valid = [7, 0, 2, 0xFF, 0xFF, 1, 2, 2, 0xFF, 0xFF];
+    assert_eq!(
+        FrcStructureBytesRef::from_parts(&FRAME, 2, &valid).validate(),
+        Ok(())
+    );
+    assert_eq!(
+        FrcStructureBytesRef::from_parts(&FRAME, 3, &valid).validate(),
+        Err(FrcStructureError::LengthMismatch {
+            expected: 15,
+            actual: 10
+        })
+    );
+
+    let invalid = [7, 0, 2, 0xFF, 0xFF, 1, 2, 1, 0xFF, 0xFF];
+    assert_eq!(
+        FrcStructureBytes::from_parts(&FRAME, 2, invalid.into()).validate(),
+        Err(FrcStructureError::InvalidEnumValue {
+            field: "modes[1].mode".to_owned(),
+            offset: 7,
+            value: 1
+        })
+    );
+    let invalid = [7, 0, 2, 0xFE, 0xFF];
+    assert_eq!(
+        FrcStructureBytesRef::from_parts(&FRAME, 1, &invalid).validate(),
+        Err(FrcStructureError::InvalidEnumValue {
+            field: "sign".to_owned(),
+            offset: 3,
+            value: -2
+        })
+    );
+
+    // the enum past the registered size would otherwise be skipped
+    assert_eq!(
+        FrcStructureBytesRef::from_parts(&SHORT, 2, &[1, 9]).validate(),
+        Err(FrcStructureError::SchemaParse(
+            SchemaParseError::SizeMismatch {
+                type_str: "ValidateShort".to_owned(),
+                expected: 1,
+                actual: 2
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_bytes_ref() {
+    let subs = [
+        SubStruct { value: 1.0 },
+        SubStruct { value: -2.0 },
+        SubStruct { value: 3.5 },
+    ];
+    // a frame buffer that outlives the decode, with a header before the structures
+    let mut frame = vec![0xAA, 0xBB];
+    frame.extend(subs.pack_to_vec());
+
+    let borrowed = FrcStructureBytesRef::try_from_parts(&SubStruct::DESCRIPTION, 3, &frame[2..])
+        .expect("three structures");
+    assert_eq!(borrowed.to_vec::<SubStruct>().ok(), Some(subs.to_vec()));
+    assert_eq!(
+        borrowed
+            .iter::<SubStruct>()
+            .map(Iterator::collect::<Vec<_>>)
+            .ok(),
+        Some(subs.to_vec())
+    );
+    assert_eq!(borrowed.get::<SubStruct>(1), Some(subs[1]));
+    assert!(
+        borrowed.to_vec::<f64>().is_err(),
+        "the stored type has to match"
+    );
+    assert_eq!(
+        FrcStructureBytesRef::try_from_parts(&SubStruct::DESCRIPTION, 3, &frame),
+        Err(FrcStructureError::LengthMismatch {
+            expected: 24,
+            actual: 26
+        })
+    );
+
+    let owned = borrowed.to_owned_bytes();
+    assert_eq!(owned.data.as_ref(), &frame[2..]);
+    assert_eq!(owned.as_borrowed(), borrowed);
+    assert_eq!(FrcStructureBytesRef::from(&owned), borrowed);
+
+    // 8 * (2^61 + 1) wraps around to 8 bytes
+    let crafted =
+        FrcStructureBytesRef::from_parts(&SubStruct::DESCRIPTION, (1 << 61) + 1, &frame[2..10]);
+    assert_eq!(
+        crafted.to_vec::<SubStruct>(),
+        Err(FrcStructureError::LengthMismatch {
+            expected: usize::MAX,
+            actual: 8
+        })
+    );
+    assert!(
+        crafted.iter::<SubStruct>().is_err(),
+        "a wrapping count is rejected"
+    );
+}
+
+#[test]
+fn test_semantic_eq() {
+    let desc: &'static FrcStructDesc = Box::leak(Box::new(FrcStructDesc {
+        schema_supplier: || "bool flag; int16 value".to_owned(),
+        type_str: "PaddedSemantic",
+        size: 4,
+    }));
+    let bytes = |data: [u8; 8]| FrcStructureBytes::from_parts(desc, 2, Box::new(data));
+    let recorded = bytes([1, 2, 0, 0xAA, 0, 3, 0, 0]);
+    let replayed = bytes([7, 2, 0, 0x55, 0, 3, 0, 0xFF]);
+    assert_ne!(recorded, replayed);
+    assert!(recorded.semantic_eq(&replayed));
+    assert!(!recorded.semantic_eq(&bytes([1, 2, 1, 0xAA, 0, 3, 0, 0])));
+    assert!(!recorded.semantic_eq(&bytes([1, 2, 0, 0xAA, 1, 3, 0, 0])));
+
+    let float = FrcStructureBytes::from_parts(&f64::DESCRIPTION, 1, 1.0f64.pack_to_boxed());
+    assert!(float.semantic_eq(&float.clone()));
+    assert!(!float.semantic_eq(&recorded));
+}
+
+#[test]
+fn test_bytes_to_vec() {
+    let subs = [SubStruct { value: 1.0 }, SubStruct { value: -4.0 }];
+    let mut data = Vec::new();
+    subs.pack(&mut data);
+    let bytes = FrcStructureBytes::from_parts(&SubStruct::DESCRIPTION, 2, data.into_boxed_slice());
+
+    assert_eq!(bytes.to_vec::<SubStruct>(), Ok(subs.to_vec()));
+    assert_eq!(
+        bytes
+            .iter::<SubStruct>()
+            .expect("matching type")
+            .collect::<Vec<_>>(),
+        subs.to_vec()
+    );
+    assert_eq!(
+        bytes.to_vec::<f64>(),
+        Err(FrcStructureError::TypeMismatch {
+            expected: "float64",
+            actual: "Meter"
+        })
+    );
+
+    let short = FrcStructureBytes::from_parts(&SubStruct::DESCRIPTION, 3, bytes.data);
+    assert_eq!(
+        short.to_vec::<SubStruct>(),
+        Err(FrcStructureError::LengthMismatch {
+            expected: 24,
+            actual: 16
+        })
+    );
+}
+
+#[test]
+fn test_try_from_parts() {
+    let data = SubStruct { value: 1.0 }.pack_to_boxed();
+    assert!(FrcStructureBytes::try_from_parts(&SubStruct::DESCRIPTION, 1, data.clone()).is_ok());
+    assert_eq!(
+        FrcStructureBytes::try_from_parts(&SubStruct::DESCRIPTION, 2, data),
+        Err(FrcStructureError::LengthMismatch {
+            expected: 16,
+            actual: 8
+        })
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_unpack_async() {
+    use tokio::io::AsyncReadExt;
+
+    let first = SubStruct { value: 1.25 }.pack_to_vec();
+    let second = SubStruct { value: -8.0 }.pack_to_vec();
+    // split the frames unevenly so the second read has to be stitched together
+    let mut reader = (&first[..5])
+        .chain(&first[5..])
+        .chain(&second[..3])
+        .chain(&second[3..]);
+    assert_eq!(
+        SubStruct::unpack_async(&mut reader).await,
+        Ok(SubStruct { value: 1.25 })
+    );
+    assert_eq!(
+        SubStruct::unpack_async(&mut reader).await,
+        Ok(SubStruct { value: -8.0 })
+    );
+    assert_eq!(
+        SubStruct::unpack_async(&mut reader).await,
+        Err(FrcStructureError::Io(std::io::ErrorKind::UnexpectedEof))
+    );
+    assert_eq!(
+        SubStruct::unpack_async(&mut &first[..7]).await,
+        Err(FrcStructureError::Io(std::io::ErrorKind::UnexpectedEof))
+    );
+}
+
+#[test]
+fn test_generic_derive() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Bounded<T> {
+        min: T,
+        max: T,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Tagged<K, V> {
+        key: K,
+        #[frc(skip)]
+        cache: V,
+        value: V,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct Limits {
+        speed: Bounded<f32>,
+        position: Bounded<SubStruct>,
+    }
+
+    assert_eq!(Bounded::<f64>::TYPE, "Bounded_float64");
+    assert_eq!(Bounded::<i32>::TYPE, "Bounded_int32");
+    assert_eq!(Bounded::<SubStruct>::TYPE, "Bounded_Meter");
+    assert_eq!(Tagged::<u8, f32>::TYPE, "Tagged_uint8_float32");
+    assert_eq!(Bounded::<f64>::SIZE, 16);
+    assert_eq!(Bounded::<i32>::SIZE, 8);
+    assert_eq!(
+        Bounded::<SubStruct>::DESCRIPTION.schema(),
+        "Meter min; Meter max"
+    );
+    assert_eq!(
+        Limits::DESCRIPTION.schema(),
+        "Bounded_float32 speed; Bounded_Meter position"
+    );
+
+    FrcStructDescDB::register::<SubStruct>();
+    FrcStructDescDB::register::<Bounded<f64>>();
+    FrcStructDescDB::register::<Bounded<i32>>();
+    assert_eq!(
+        FrcStructDescDB::get("Bounded_float64").map(FrcStructDesc::schema),
+        Some("float64 min; float64 max")
+    );
+    assert_eq!(
+        FrcStructDescDB::get("Bounded_int32").map(FrcStructDesc::schema),
+        Some("int32 min; int32 max")
+    );
+
+    // arrays, options and strings of the same element still get their own registration
+    assert_eq!(Bounded::<[f64; 2]>::TYPE, "Bounded_float64x2");
+    assert_eq!(Bounded::<[[f64; 2]; 12]>::TYPE, "Bounded_float64x2x12");
+    assert_eq!(Bounded::<Option<f64>>::TYPE, "Bounded_Option_float64");
+    assert_eq!(Bounded::<StructString<8>>::TYPE, "Bounded_charx8");
+    FrcStructDescDB::register::<Bounded<[f64; 2]>>();
+    let (scalar, pair) = (
+        FrcStructDescDB::get("Bounded_float64").expect("registered above"),
+        FrcStructDescDB::get("Bounded_float64x2").expect("registered above"),
+    );
+    assert_eq!((scalar.size, pair.size), (16, 32));
+    assert_eq!(pair.schema(), "float64 min[2]; float64 max[2]");
+    assert_ne!(scalar.schema_hash(), pair.schema_hash());
+    let pairs = FrcStructureBytes::pack_many(&[Bounded {
+        min: [0.0, 1.0],
+        max: [2.0, 3.0],
+    }]);
+    assert_eq!(pairs.validate(), Ok(()));
+
+    FrcStructDescDB::register::<Bounded<f32>>();
+    FrcStructDescDB::register::<Bounded<SubStruct>>();
+    let limits = Limits {
+        speed: Bounded {
+            min: -4.5,
+            max: 4.5,
+        },
+        position: Bounded {
+            min: SubStruct { value: 0.0 },
+            max: SubStruct { value: 1.2 },
+        },
+    };
+    let bytes = limits.pack_to_vec();
+    assert_eq!(Limits::try_unpack(&mut Cursor::new(&bytes[..])), Ok(limits));
+    assert_eq!(
+        Limits::DESCRIPTION.field_offset("position.max.value"),
+        Some((16, StructureFieldTypes::Float64(1)))
+    );
+
+    let tagged = Tagged {
+        key: 7u8,
+        cache: 1.0f32,
+        value: 2.0f32,
+    };
+    let bytes = tagged.pack_to_vec();
+    assert_eq!(
+        Tagged::<u8, f32>::unpack(&mut Cursor::new(&bytes[..])),
+        Tagged {
+            cache: 0.0,
+            ..tagged
+        }
+    );
+    let view = TaggedView::<u8, f32>::new(&bytes).expect("enough bytes");
+    assert_eq!(view.key(), 7);
+    assert_eq!(view.value().to_bits(), 2.0f32.to_bits());
+}
+
+#[test]
+fn test_dyn_structure() {
+    use crate as frclib_core;
+
+    #[derive(Debug, PartialEq, FrcDynStructure)]
+    struct Trajectory {
+        id: u16,
+        samples: Vec<SubStruct>,
+        flags: Vec<bool>,
+    }
+
+    #[derive(Debug, PartialEq, FrcDynStructure)]
+    struct Wrapped(Trajectory, u8);
+
+    assert_eq!(
+        Trajectory::dyn_schema(),
+        "uint16 id; uint32 samples_len; Meter samples[]; uint32 flags_len; bool flags[]"
+    );
+    assert_eq!(Wrapped::dyn_schema(), "Trajectory _0; uint8 _1");
+
+    let empty = Trajectory {
+        id: 4,
+        samples: Vec::new(),
+        flags: Vec::new(),
+    };
+    let mut buffer = Vec::new();
+    empty.pack_dyn(&mut buffer);
+    assert_eq!(
+        buffer,
+        [4, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        "empty vecs are just a zero length"
+    );
+    assert_eq!(
+        Trajectory::try_unpack_dyn(&mut Cursor::new(&buffer[..])),
+        Ok(empty)
+    );
+
+    let wrapped = Wrapped(
+        Trajectory {
+            id: 9,
+            samples: vec![SubStruct { value: 1.5 }, SubStruct { value: -2.0 }],
+            flags: vec![true, false, true],
+        },
+        7,
+    );
+    let mut buffer = Vec::new();
+    wrapped.pack_dyn(&mut buffer);
+    assert_eq!(
+        buffer.len(),
+        2 + 4 + 2 * 8 + 4 + 3 + 1,
+        "packed size follows the lengths"
+    );
+    assert_eq!(buffer.get(2..6), Some(&2u32.to_le_bytes()[..]));
+    let mut cursor = Cursor::new(&buffer[..]);
+    assert_eq!(Wrapped::try_unpack_dyn(&mut cursor), Ok(wrapped));
+    assert_eq!(remaining(&cursor), 0, "the whole buffer was read");
+
+    // a length pointing past the end is rejected before allocating
+    let corrupt = [0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0];
+    assert_eq!(
+        Trajectory::try_unpack_dyn(&mut Cursor::new(&corrupt[..])),
+        Err(StructUnpackError::InsufficientBytes {
+            type_str: "Meter",
+            needed: 8 * 0xffff_ffff,
+            available: 2,
+        })
+    );
+}
+
+#[test]
+fn test_same_name_field() {
+    use crate as frclib_core;
+
+    mod other {
+        use crate as frclib_core;
+        use crate::structure::{Cursor, FrcStructure};
+
+        #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+        pub struct PathWaypoint {
+            pub heading: f32,
+        }
+    }
+
+    // `other::PathWaypoint` is a different type, only the structure's own path is rejected
+    #[derive(Debug, PartialEq, Clone, Copy, FrcStructure)]
+    struct PathWaypoint {
+        x: f32,
+        nested: other::PathWaypoint,
+    }
+
+    let waypoint = PathWaypoint {
+        x: 1.0,
+        nested: other::PathWaypoint { heading: 0.5 },
+    };
+    assert_eq!(PathWaypoint::SIZE, 8);
+    assert_eq!(
+        PathWaypoint::unpack(&mut Cursor::new(&waypoint.pack_to_vec()[..])),
+        waypoint
+    );
+}
+
+#[test]
+#[cfg(not(feature = "std"))]
+fn test_alloc_cursor() {
+    let bytes = [1u8, 2, 3, 4, 5];
+    let mut cursor = Cursor::new(&bytes[..]);
+    let mut pair = [0u8; 2];
+    assert_eq!(cursor.read_exact(&mut pair), Ok(()));
+    assert_eq!(pair, [1, 2]);
+    assert_eq!(cursor.position(), 2);
+    assert_eq!(remaining(&cursor), 3);
+
+    let mut quad = [0u8; 4];
+    assert_eq!(cursor.read_exact(&mut quad), Err(UnexpectedEof));
+    assert_eq!(cursor.position(), 5, "a short read moves to the end");
+
+    cursor.set_position(100);
+    assert_eq!(remaining(&cursor), 0);
+    assert_eq!(cursor.read_exact(&mut pair), Err(UnexpectedEof));
+    assert_eq!(u16::unpack(&mut Cursor::new(&bytes[3..])), 0x0504);
+    assert_eq!(cursor.into_inner(), &bytes[..]);
+}
@@ -53,12 +53,8 @@ impl Instant {
     /// Returns the amount of time elapsed from another instant to this one, or None if that
     /// instant is later than this one.
     #[must_use]
-    pub fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
-        if earlier.0 > self.0 {
-            None
-        } else {
-            Some(self.0 - earlier.0)
-        }
+    pub const fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
+        self.0.checked_sub(earlier.0)
     }
 
     /// Returns the amount of time elapsed from another instant to this one, or zero duration if
@@ -86,6 +82,8 @@ impl AddAssign<Duration> for Instant {
 impl Sub<Duration> for Instant {
     type Output = Self;
 
+    // panics on underflow like `std::time::Instant`, `checked_sub` is the non-panicking version
+    #[allow(unknown_lints, clippy::unchecked_time_subtraction)]
     fn sub(self, rhs: Duration) -> Self {
         Self(self.0 - rhs)
     }
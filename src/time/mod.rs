@@ -114,10 +114,11 @@ pub mod __private {
     /// - If called more than once
     pub unsafe fn set_time_implementation(time_imp: TimeImplementation) {
         use std::sync::atomic::Ordering;
+        let old_name = super::IMPLEMENTATION_NAME;
         assert!(
             !super::TIME_IMPL_FROZEN.swap(true, Ordering::SeqCst),
             "Cannot set time source after it has been used or previously set(old: {}, new: {})",
-            super::IMPLEMENTATION_NAME,
+            old_name,
             time_imp.implementation_name
         );
         super::UPTIME_SOURCE = time_imp.uptime;
@@ -149,16 +150,34 @@ mod test {
         use super::*;
         try_pause(true).expect("Pause Error");
         let start = uptime().as_micros();
-        thread::sleep(Duration::from_millis(1000));
+        thread::sleep(Duration::from_secs(1));
         let end = uptime().as_micros();
         // assert!(end + 5 - start < 100);
         assert!(end.saturating_sub(start) < 100);
         try_pause(false).expect("Pause Error");
-        thread::sleep(Duration::from_millis(1000));
+        thread::sleep(Duration::from_secs(1));
         let end = uptime().as_micros();
         assert!(end.saturating_sub(start) >= 1_000_000);
     }
 
+    #[test]
+    fn test_instant_arithmetic() {
+        use super::*;
+        let later = Instant::now() + Duration::from_secs(5);
+        let earlier = later - Duration::from_secs(2);
+        assert_eq!(
+            later.checked_duration_since(earlier),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(earlier.checked_duration_since(later), None);
+        assert_eq!(earlier.saturating_duration_since(later), Duration::ZERO);
+        assert_eq!(earlier.checked_sub(Duration::MAX), None);
+        assert!(
+            std::panic::catch_unwind(|| earlier - Duration::MAX).is_err(),
+            "subtracting past zero should panic like std::time::Instant"
+        );
+    }
+
     /// Tests all of the time functions in one thread to make it sequential
     /// and not parallel messing up global state
     #[test]
@@ -3,17 +3,21 @@ use crate::units::angular_acceleration::{
 };
 use crate::units::angular_velocity::{DegreePerSec, RadianPerSec, RotationPerMin, RotationPerSec};
 use crate::units::time::{Minute, Second};
-use crate::{unit, unit_conversion, unit_dim_analysis, unit_family};
+use crate::{unit, unit_angle, unit_conversion, unit_dim_analysis, unit_family};
 
-unit!(Degree | Degrees | Deg | Degs: float);
-unit!(Radian | Radians | Rad | Rads: float);
-unit!(Rotation | Rotations | Rot | Rots: float);
+unit!(Degree("deg") | Degrees | Deg | Degs: float);
+unit!(Radian("rad") | Radians | Rad | Rads: float);
+unit!(Rotation("rot") | Rotations | Rot | Rots: float);
 
 unit_conversion!(Degree(float) <-> Radian(float)   ~ |x| x.to_radians());
 unit_conversion!(Degree(float) <-> Rotation(float) ~ ratio 1.0/360.0);
-unit_conversion!(Radian(float) <-> Rotation(float) ~ |x| x / (std::f64::consts::PI * 2.0));
+unit_conversion!(Radian(float) <-> Rotation(float) ~ |x| x / (core::f64::consts::PI * 2.0));
 
-unit_family!(Angle(Radian): Degree, Rotation);
+unit_family!(Angle(Radian) periodic(core::f64::consts::TAU): Degree, Rotation);
+
+unit_angle!(Radian);
+unit_angle!(Degree as Radian);
+unit_angle!(Rotation as Radian);
 
 unit_dim_analysis!(DegreePerSec * Second = Degree);
 unit_dim_analysis!(RadianPerSec * Second = Radian);
@@ -1,9 +1,9 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(DegreePerSecSqr | DegreesPerSecSqr | DegsPerSecSqr: float);
-unit!(RadianPerSecSqr | RadiansPerSecSqr | RadsPerSecSqr: float);
-unit!(RotationPerSecSqr | RotationsPerSecSqr | RotsPerSecSqr: float);
-unit!(RotationPerMinSqr | RotationsPerMinSqr | RotsPerMinSqr: float);
+unit!(DegreePerSecSqr("deg/s^2") | DegreesPerSecSqr | DegsPerSecSqr: float);
+unit!(RadianPerSecSqr("rad/s^2") | RadiansPerSecSqr | RadsPerSecSqr: float);
+unit!(RotationPerSecSqr("rot/s^2") | RotationsPerSecSqr | RotsPerSecSqr: float);
+unit!(RotationPerMinSqr("rot/min^2") | RotationsPerMinSqr | RotsPerMinSqr: float);
 
 unit_conversion!(DegreePerSecSqr(float) <-> RadianPerSecSqr(float) ~ degree_per_second_squared_to_radian_per_second_squared);
 unit_conversion!(DegreePerSecSqr(float) <-> RotationPerSecSqr(float) ~ degree_per_second_squared_to_rotation_per_second_squared);
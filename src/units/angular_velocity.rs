@@ -1,9 +1,9 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(DegreePerSec: float);
-unit!(RadianPerSec: float);
-unit!(RotationPerSec: float);
-unit!(RotationPerMin: float);
+unit!(DegreePerSec("deg/s"): float);
+unit!(RadianPerSec("rad/s"): float);
+unit!(RotationPerSec("rot/s"): float);
+unit!(RotationPerMin("rpm"): float);
 
 unit_conversion!(DegreePerSec(float) <-> RadianPerSec(float) ~ degree_per_second_to_radian_per_second);
 unit_conversion!(DegreePerSec(float) <-> RotationPerSec(float) ~ degree_per_second_to_rotation_per_second);
@@ -0,0 +1,69 @@
+use core::hash::{Hash, Hasher};
+
+use super::Unit;
+
+/// A floating point unit with [`Eq`] and [`Hash`], for use in hash based collections.
+///
+/// Float units only implement [`PartialEq`] to keep IEEE semantics, `NaN != NaN` and `-0.0 == 0.0`.
+/// This wrapper canonicalizes the value when it is created, `-0.0` becomes `0.0` and every NaN
+/// becomes [`f64::NAN`], then compares bit for bit so NaN equals itself.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use frclib_core::units::{length::Meter, CanonEq};
+///
+/// let seen = [Meter::new(0.0), Meter::new(-0.0), Meter::NAN, Meter::NAN, Meter::new(1.0)]
+///     .into_iter()
+///     .map(CanonEq::new)
+///     .collect::<HashSet<_>>();
+/// assert_eq!(seen.len(), 3);
+/// assert!(seen.contains(&CanonEq::new(Meter::new(-0.0))));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CanonEq<U>(U);
+
+impl<U: Unit<Backing = f64>> CanonEq<U> {
+    /// Wraps the unit, normalizing signed zero and NaN.
+    #[must_use]
+    pub fn new(unit: U) -> Self {
+        let value = unit.value();
+        if value == 0.0 {
+            Self(U::new(0.0))
+        } else if value.is_nan() {
+            Self(U::new(f64::NAN))
+        } else {
+            Self(unit)
+        }
+    }
+
+    /// Returns the canonicalized unit.
+    #[must_use]
+    pub const fn get(self) -> U {
+        self.0
+    }
+
+    fn bits(self) -> u64 {
+        self.0.value().to_bits()
+    }
+}
+
+impl<U: Unit<Backing = f64>> From<U> for CanonEq<U> {
+    fn from(unit: U) -> Self {
+        Self::new(unit)
+    }
+}
+
+impl<U: Unit<Backing = f64>> PartialEq for CanonEq<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl<U: Unit<Backing = f64>> Eq for CanonEq<U> {}
+
+impl<U: Unit<Backing = f64>> Hash for CanonEq<U> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
+}
@@ -6,10 +6,10 @@ use crate::{unit, unit_conversion, unit_dim_analysis, unit_family};
 
 use super::time::Hour;
 
-unit!(Byte: float);
-unit!(Kilobyte: float);
-unit!(Megabyte: float);
-unit!(Gigabyte: float);
+unit!(Byte("B"): float);
+unit!(Kilobyte("kB"): float);
+unit!(Megabyte("MB"): float);
+unit!(Gigabyte("GB"): float);
 
 unit_conversion!(Byte(float) <-> Kilobyte(float) ~ byte_to_kilobyte);
 unit_conversion!(Byte(float) <-> Megabyte(float) ~ byte_to_megabyte);
@@ -1,9 +1,9 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(BytesPerSecond: float);
-unit!(KilobytesPerSecond: float);
-unit!(MegabytesPerSecond: float);
-unit!(GigabytesPerHour: float);
+unit!(BytesPerSecond("B/s"): float);
+unit!(KilobytesPerSecond("kB/s"): float);
+unit!(MegabytesPerSecond("MB/s"): float);
+unit!(GigabytesPerHour("GB/h"): float);
 
 unit_conversion!(BytesPerSecond(float) <-> KilobytesPerSecond(float) ~ byte_per_second_to_kilobyte_per_second);
 unit_conversion!(BytesPerSecond(float) <-> MegabytesPerSecond(float) ~ byte_per_second_to_megabyte_per_second);
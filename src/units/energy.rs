@@ -1,12 +1,12 @@
 use crate::units::time::Second;
 use crate::{unit, unit_conversion, unit_dim_analysis};
 
-unit!(Joule: float);
-unit!(Volt: float);
-unit!(Amp: float);
-unit!(Watt: float);
-unit!(WattHour: float);
-unit!(Ohm: float);
+unit!(Joule("J"): float);
+unit!(Volt("V"): float);
+unit!(Amp("A"): float);
+unit!(Watt("W"): float);
+unit!(WattHour("Wh"): float);
+unit!(Ohm("ohm"): float);
 
 unit_conversion!(Joule(float) <-> WattHour(float) ~ joule_to_watthour);
 
@@ -0,0 +1,59 @@
+use core::fmt;
+
+/// An error that occurs when constructing or converting a unit from an untrusted value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitError {
+    /// The value was `NaN` or infinite
+    NotFinite(&'static str),
+    /// The value does not fit in the backing type of the unit,
+    /// or in the primitive an integer unit is being narrowed into
+    OutOfRange(&'static str),
+    /// The value has more significant bits than the backing type of the unit can hold
+    PrecisionLoss(&'static str),
+}
+
+// written out instead of derived with `thiserror` so the units layer works without `std`
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFinite(unit) => write!(f, "Could not represent a non-finite value as {unit}"),
+            Self::OutOfRange(unit) => write!(f, "Value is out of range for {unit}"),
+            Self::PrecisionLoss(unit) => {
+                write!(f, "Could not represent the value exactly as {unit}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnitError {}
+
+/// An error that occurs when parsing a value followed by a unit symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseUnitError {
+    /// The text before the symbol is not a valid value for the named unit
+    InvalidNumber(&'static str),
+    /// The text doesn't end with any of the accepted symbols
+    UnknownSymbol(&'static [&'static str]),
+}
+
+impl fmt::Display for ParseUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(unit) => write!(f, "Could not parse the value of {unit}"),
+            Self::UnknownSymbol(accepted) => {
+                f.write_str("Unknown unit symbol, expected one of: ")?;
+                for (index, symbol) in accepted.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(symbol)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseUnitError {}
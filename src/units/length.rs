@@ -1,11 +1,11 @@
 use crate::units::linear_velocity::{FeetPerSecond, MetersPerSecond};
 use crate::units::time::Second;
-use crate::{unit, unit_conversion, unit_dim_analysis};
+use crate::{unit, unit_array, unit_conversion, unit_dim_analysis, unit_family};
 
-unit!(Meter: float);
-unit!(Foot: float);
-unit!(Inch: float);
-unit!(Centimeter: float);
+unit!(Meter("m"): float);
+unit!(Foot("ft"): float);
+unit!(Inch("in"): float);
+unit!(Centimeter("cm"): float);
 
 unit_conversion!(Meter(float) <-> Foot(float) ~ meter_to_foot);
 unit_conversion!(Meter(float) <-> Inch(float) ~ meter_to_inch);
@@ -17,6 +17,11 @@ unit_conversion!(Centimeter(float) <-> Inch(float) ~ centimeter_to_inch);
 unit_dim_analysis!(MetersPerSecond * Second = Meter);
 unit_dim_analysis!(FeetPerSecond * Second = Foot);
 
+unit_array!(Meter);
+unit_array!(Foot);
+unit_array!(Inch);
+unit_array!(Centimeter);
+
 //manually written `unit_family!` because of how plural of imperial units are weird
 #[doc = "A family of units. "]
 #[doc = "The standard unit is `Meter`."]
@@ -27,6 +32,31 @@ pub trait Distance: Into<Meter> + From<Meter> + Copy {
     fn standard(self) -> Meter {
         self.into()
     }
+    #[doc = "Converts this unit to any other unit of the family through the standard unit,"]
+    #[doc = "e.g. `value.convert::<Inch>()`."]
+    #[inline]
+    fn convert<U: Distance>(self) -> U {
+        U::from(self.standard())
+    }
+    #[doc = "Converts this unit to `U` and returns the raw value,"]
+    #[doc = "the value has the backing type of `U` so integer units return an integer."]
+    #[inline]
+    fn value_in<U: Distance + crate::units::Unit>(self) -> U::Backing {
+        crate::units::Unit::value(self.convert::<U>())
+    }
+    #[doc = "Compares this unit with any other unit of the family in the standard unit."]
+    #[doc = ""]
+    #[doc = "`NaN` values are ordered as defined by [`f64::total_cmp`]."]
+    #[inline]
+    fn compare<U: Distance>(self, other: U) -> core::cmp::Ordering {
+        self.standard().total_cmp(&other.standard())
+    }
+    #[doc = "Returns true if this unit and any other unit of the family"]
+    #[doc = "are within `epsilon` of each other in the standard unit."]
+    #[inline]
+    fn approx_eq<U: Distance>(self, other: U, epsilon: Meter) -> bool {
+        self.standard().abs_diff(other.standard()) <= epsilon
+    }
     #[doc = "Converts this unit to `Inch`."]
     #[inline]
     fn to_inches(self) -> Inch {
@@ -51,6 +81,8 @@ pub trait Distance: Into<Meter> + From<Meter> + Copy {
 }
 impl<T> Distance for T where T: Into<Meter> + From<Meter> + Copy {}
 
+unit_family!(@parse Distance(Meter): Inch, Foot, Centimeter);
+
 fn meter_to_foot(meter: f64) -> f64 {
     meter * 3.28084
 }
@@ -1,9 +1,9 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(MetersPerSecSqr: float);
-unit!(KilometersPerHrSqr: float);
-unit!(MilesPerHrSqr: float);
-unit!(FeetPerSecSqr: float);
+unit!(MetersPerSecSqr("m/s^2"): float);
+unit!(KilometersPerHrSqr("km/h^2"): float);
+unit!(MilesPerHrSqr("mi/h^2"): float);
+unit!(FeetPerSecSqr("ft/s^2"): float);
 
 unit_conversion!(MetersPerSecSqr(float) <-> KilometersPerHrSqr(float) ~ meter_to_kilometer);
 unit_conversion!(MetersPerSecSqr(float) <-> MilesPerHrSqr(float) ~ meter_to_mile);
@@ -1,9 +1,9 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(MetersPerSecond: float);
-unit!(KilometersPerHour: float);
-unit!(MilesPerHour: float);
-unit!(FeetPerSecond: float);
+unit!(MetersPerSecond("m/s"): float);
+unit!(KilometersPerHour("km/h"): float);
+unit!(MilesPerHour("mph"): float);
+unit!(FeetPerSecond("ft/s"): float);
 
 unit_conversion!(MetersPerSecond(float) <-> KilometersPerHour(float) ~ meter_to_kilometer);
 unit_conversion!(MetersPerSecond(float) <-> MilesPerHour(float) ~ meter_to_mile);
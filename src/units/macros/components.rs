@@ -11,10 +11,11 @@ macro_rules! unit_general {
 
         impl From<f32> for $unit_name {
             fn from(value: f32) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
+        #[allow(clippy::cast_precision_loss)]
         impl From<i64> for $unit_name {
             fn from(value: i64) -> Self {
                 Self(value as f64)
@@ -23,22 +24,23 @@ macro_rules! unit_general {
 
         impl From<i32> for $unit_name {
             fn from(value: i32) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
         impl From<i16> for $unit_name {
             fn from(value: i16) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
         impl From<i8> for $unit_name {
             fn from(value: i8) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
+        #[allow(clippy::cast_precision_loss)]
         impl From<u64> for $unit_name {
             fn from(value: u64) -> Self {
                 Self(value as f64)
@@ -47,19 +49,19 @@ macro_rules! unit_general {
 
         impl From<u32> for $unit_name {
             fn from(value: u32) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
         impl From<u16> for $unit_name {
             fn from(value: u16) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
         impl From<u8> for $unit_name {
             fn from(value: u8) -> Self {
-                Self(value as f64)
+                Self(f64::from(value))
             }
         }
 
@@ -75,12 +77,75 @@ macro_rules! unit_general {
             }
         }
 
-        impl std::fmt::Display for $unit_name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl core::fmt::Display for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "{}({}f64)", stringify!($unit_name), self.0)
             }
         }
     };
+    ($unit_name:ident : f16) => {
+        impl From<$crate::units::macros::half::f16> for $unit_name {
+            fn from(value: $crate::units::macros::half::f16) -> Self {
+                Self(value)
+            }
+        }
+
+        /// Rounds to the nearest `f16`, values out of range become infinite.
+        impl From<f64> for $unit_name {
+            fn from(value: f64) -> Self {
+                Self($crate::units::macros::half::f16::from_f64(value))
+            }
+        }
+
+        /// Rounds to the nearest `f16`, values out of range become infinite.
+        impl From<f32> for $unit_name {
+            fn from(value: f32) -> Self {
+                Self($crate::units::macros::half::f16::from_f32(value))
+            }
+        }
+
+        impl From<i8> for $unit_name {
+            fn from(value: i8) -> Self {
+                Self($crate::units::macros::half::f16::from(value))
+            }
+        }
+
+        impl From<u8> for $unit_name {
+            fn from(value: u8) -> Self {
+                Self($crate::units::macros::half::f16::from(value))
+            }
+        }
+
+        impl From<&$unit_name> for $unit_name {
+            fn from(value: &$unit_name) -> Self {
+                Self(value.0)
+            }
+        }
+
+        impl From<$unit_name> for $crate::units::macros::half::f16 {
+            fn from(value: $unit_name) -> Self {
+                value.0
+            }
+        }
+
+        impl From<$unit_name> for f32 {
+            fn from(value: $unit_name) -> Self {
+                value.0.to_f32()
+            }
+        }
+
+        impl From<$unit_name> for f64 {
+            fn from(value: $unit_name) -> Self {
+                value.0.to_f64()
+            }
+        }
+
+        impl core::fmt::Display for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}({}f16)", stringify!($unit_name), self.0)
+            }
+        }
+    };
     ($unit_name:ident : i64) => {
         impl From<i64> for $unit_name {
             fn from(value: i64) -> Self {
@@ -90,22 +155,23 @@ macro_rules! unit_general {
 
         impl From<i32> for $unit_name {
             fn from(value: i32) -> Self {
-                Self(value as i64)
+                Self(i64::from(value))
             }
         }
 
         impl From<i16> for $unit_name {
             fn from(value: i16) -> Self {
-                Self(value as i64)
+                Self(i64::from(value))
             }
         }
 
         impl From<i8> for $unit_name {
             fn from(value: i8) -> Self {
-                Self(value as i64)
+                Self(i64::from(value))
             }
         }
 
+        #[allow(clippy::cast_possible_wrap)]
         impl From<u64> for $unit_name {
             fn from(value: u64) -> Self {
                 Self(value as i64)
@@ -114,19 +180,19 @@ macro_rules! unit_general {
 
         impl From<u32> for $unit_name {
             fn from(value: u32) -> Self {
-                Self(value as i64)
+                Self(i64::from(value))
             }
         }
 
         impl From<u16> for $unit_name {
             fn from(value: u16) -> Self {
-                Self(value as i64)
+                Self(i64::from(value))
             }
         }
 
         impl From<u8> for $unit_name {
             fn from(value: u8) -> Self {
-                Self(value as i64)
+                Self(i64::from(value))
             }
         }
 
@@ -142,8 +208,8 @@ macro_rules! unit_general {
             }
         }
 
-        impl std::fmt::Display for $unit_name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl core::fmt::Display for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "{}({}i64)", stringify!($unit_name), self.0)
             }
         }
@@ -157,19 +223,19 @@ macro_rules! unit_general {
 
         impl From<u32> for $unit_name {
             fn from(value: u32) -> Self {
-                Self(value as u64)
+                Self(u64::from(value))
             }
         }
 
         impl From<u16> for $unit_name {
             fn from(value: u16) -> Self {
-                Self(value as u64)
+                Self(u64::from(value))
             }
         }
 
         impl From<u8> for $unit_name {
             fn from(value: u8) -> Self {
-                Self(value as u64)
+                Self(u64::from(value))
             }
         }
 
@@ -185,204 +251,965 @@ macro_rules! unit_general {
             }
         }
 
-        impl std::fmt::Display for $unit_name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl core::fmt::Display for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "{}({}u64)", stringify!($unit_name), self.0)
             }
         }
     };
 }
 
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_modular {
+    ($unit_name:ident) => {
+        impl From<f64> for $unit_name {
+            fn from(value: f64) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<&$unit_name> for $unit_name {
+            fn from(value: &$unit_name) -> Self {
+                *value
+            }
+        }
+
+        impl From<$unit_name> for f64 {
+            fn from(value: $unit_name) -> Self {
+                value.value()
+            }
+        }
+
+        impl core::fmt::Display for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}({}f64)", stringify!($unit_name), self.value())
+            }
+        }
+
+        impl $crate::units::Unit for $unit_name {
+            type Backing = f64;
+
+            const NAME: &'static str = Self::NAME;
+            const SYMBOL: Option<&'static str> = Self::SYMBOL;
+
+            #[inline]
+            fn new(value: f64) -> Self {
+                Self::new(value)
+            }
+
+            #[inline]
+            fn value(self) -> f64 {
+                self.value()
+            }
+        }
+
+        /// Compares the wrapped values, so `0` and `MODULUS` are equal.
+        impl core::cmp::PartialEq for $unit_name {
+            fn eq(&self, other: &Self) -> bool {
+                self.value() == other.value()
+            }
+        }
+
+        /// Orders the wrapped values within `[0, MODULUS)`.
+        impl core::cmp::PartialOrd for $unit_name {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                self.value().partial_cmp(&other.value())
+            }
+        }
+
+        impl core::hash::Hash for $unit_name {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::units::macros::hash_bits_f64(self.value()).hash(state);
+            }
+        }
+
+        impl<T> core::ops::Add<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: T) -> Self::Output {
+                Self::new(self.0 + rhs.into().0)
+            }
+        }
+
+        impl<T> core::ops::Sub<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: T) -> Self::Output {
+                Self::new(self.0 - rhs.into().0)
+            }
+        }
+
+        impl core::ops::Mul<f64> for $unit_name {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: f64) -> Self::Output {
+                Self::new(self.value() * rhs)
+            }
+        }
+
+        impl core::ops::Div<f64> for $unit_name {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: f64) -> Self::Output {
+                Self::new(self.value() / rhs)
+            }
+        }
+
+        impl core::ops::Neg for $unit_name {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self::Output {
+                Self::new(-self.0)
+            }
+        }
+
+        impl<T> core::ops::AddAssign<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            #[inline]
+            fn add_assign(&mut self, rhs: T) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T> core::ops::SubAssign<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            #[inline]
+            fn sub_assign(&mut self, rhs: T) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl core::ops::MulAssign<f64> for $unit_name {
+            #[inline]
+            fn mul_assign(&mut self, rhs: f64) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl core::ops::DivAssign<f64> for $unit_name {
+            #[inline]
+            fn div_assign(&mut self, rhs: f64) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_modular_int {
+    ($unit_name:ident $( ( $symbol:literal ) )? : $type:ident) => {
+        impl $unit_name {
+            /// Consumes the unit and returns the inner value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub const fn into_inner(self) -> $type {
+                self.value()
+            }
+
+            /// Applies `f` to the inner value and wraps the result back into the unit.
+            #[must_use]
+            #[inline]
+            pub fn map(self, f: impl FnOnce($type) -> $type) -> Self {
+                Self::new(f(self.value()))
+            }
+
+            /// Returns the shortest signed change from `previous` to `self`,
+            /// counting a step across the wrap at `MODULUS` as a small step instead of a full turn.
+            ///
+            /// The change is in the half-open range `[-MODULUS / 2, MODULUS / 2)`,
+            /// so summing the changes of every reading gives a count that doesn't wrap.
+            /// It is an `i64` as the unit itself can't hold a negative change.
+            #[must_use]
+            #[inline]
+            pub fn unwrap_delta(self, previous: Self) -> i64 {
+                use $crate::units::macros::SaturatingInt;
+                let modulus = Self::MODULUS.widen();
+                let half = modulus / 2;
+                let delta = self.value().widen() - previous.value().widen();
+                i64::saturate((delta + half).rem_euclid(modulus) - half)
+            }
+
+            /// The name of the unit.
+            pub const NAME: &'static str = stringify!($unit_name);
+            /// The symbol of the unit, if one was given.
+            pub const SYMBOL: Option<&'static str> = $crate::unit_symbol!($($symbol)?);
+        }
+
+        impl AsRef<$type> for $unit_name {
+            fn as_ref(&self) -> &$type {
+                &self.0
+            }
+        }
+
+        impl From<$type> for $unit_name {
+            fn from(value: $type) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<&$unit_name> for $unit_name {
+            fn from(value: &$unit_name) -> Self {
+                *value
+            }
+        }
+
+        impl From<$unit_name> for $type {
+            fn from(value: $unit_name) -> Self {
+                value.value()
+            }
+        }
+
+        impl core::fmt::Display for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}({}{})", stringify!($unit_name), self.value(), stringify!($type))
+            }
+        }
+
+        impl $crate::units::Unit for $unit_name {
+            type Backing = $type;
+
+            const NAME: &'static str = Self::NAME;
+            const SYMBOL: Option<&'static str> = Self::SYMBOL;
+
+            #[inline]
+            fn new(value: $type) -> Self {
+                Self::new(value)
+            }
+
+            #[inline]
+            fn value(self) -> $type {
+                self.value()
+            }
+        }
+
+        /// Compares the wrapped values, so `0` and `MODULUS` are equal.
+        impl core::cmp::PartialEq for $unit_name {
+            fn eq(&self, other: &Self) -> bool {
+                self.value() == other.value()
+            }
+        }
+
+        impl core::cmp::Eq for $unit_name {}
+
+        /// Orders the wrapped values within `[0, MODULUS)`.
+        impl core::cmp::PartialOrd for $unit_name {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// Orders the wrapped values within `[0, MODULUS)`.
+        impl core::cmp::Ord for $unit_name {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.value().cmp(&other.value())
+            }
+        }
+
+        impl core::hash::Hash for $unit_name {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.value().hash(state);
+            }
+        }
+
+        impl<T> core::ops::Add<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: T) -> Self::Output {
+                use $crate::units::macros::SaturatingInt;
+                Self($crate::units::macros::wrap_int(
+                    self.value().widen() + rhs.into().value().widen(),
+                    Self::MODULUS,
+                ))
+            }
+        }
+
+        impl<T> core::ops::Sub<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: T) -> Self::Output {
+                use $crate::units::macros::SaturatingInt;
+                Self($crate::units::macros::wrap_int(
+                    self.value().widen() - rhs.into().value().widen(),
+                    Self::MODULUS,
+                ))
+            }
+        }
+
+        impl core::ops::Neg for $unit_name {
+            type Output = Self;
+            #[inline]
+            fn neg(self) -> Self::Output {
+                use $crate::units::macros::SaturatingInt;
+                Self($crate::units::macros::wrap_int(-self.value().widen(), Self::MODULUS))
+            }
+        }
+
+        impl<T> core::ops::AddAssign<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            #[inline]
+            fn add_assign(&mut self, rhs: T) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T> core::ops::SubAssign<T> for $unit_name
+        where
+            T: Into<Self>,
+        {
+            #[inline]
+            fn sub_assign(&mut self, rhs: T) {
+                *self = *self - rhs;
+            }
+        }
+
+        $crate::unit_fmt!($unit_name);
+        $crate::unit_serde!($unit_name : $type);
+        $crate::unit_arbitrary!($unit_name);
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_fmt {
+    ($unit_name:ident) => {
+        /// Formats as `Name(value)`, with the symbol after the value if one was given.
+        /// Formats like the derived [`Debug`], `{:#?}` prints `Name(value symbol)` on one line instead
+        /// if a symbol was given.
+        impl core::fmt::Debug for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match Self::SYMBOL {
+                    Some(symbol) if f.alternate() => {
+                        f.write_str(concat!(stringify!($unit_name), "("))?;
+                        core::fmt::Debug::fmt(&self.value(), f)?;
+                        write!(f, " {symbol})")
+                    }
+                    _ => f
+                        .debug_tuple(stringify!($unit_name))
+                        .field(&self.value())
+                        .finish(),
+                }
+            }
+        }
+
+        /// Formats the value in scientific notation, with the symbol after it if one was given.
+        impl core::fmt::LowerExp for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::LowerExp::fmt(&self.value(), f)?;
+                if let Some(symbol) = Self::SYMBOL {
+                    write!(f, " {symbol}")?;
+                }
+                Ok(())
+            }
+        }
+
+        /// Formats the value in scientific notation, with the symbol after it if one was given.
+        impl core::fmt::UpperExp for $unit_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::UpperExp::fmt(&self.value(), f)?;
+                if let Some(symbol) = Self::SYMBOL {
+                    write!(f, " {symbol}")?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_trait {
+    ($unit_name:ident : $type:ty) => {
+        impl $crate::units::Unit for $unit_name {
+            type Backing = $type;
+
+            const NAME: &'static str = Self::NAME;
+            const SYMBOL: Option<&'static str> = Self::SYMBOL;
+
+            #[inline]
+            fn new(value: $type) -> Self {
+                Self(value)
+            }
+
+            #[inline]
+            fn value(self) -> $type {
+                self.0
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_consts {
+    ($unit_name:ident : f64) => {
+        impl $unit_name {
+            /// The additive identity of this unit.
+            pub const ZERO: Self = Self(0.0);
+            /// The multiplicative identity of this unit.
+            pub const ONE: Self = Self(1.0);
+            /// The smallest finite value of this unit.
+            pub const MIN: Self = Self(f64::MIN);
+            /// The largest finite value of this unit.
+            pub const MAX: Self = Self(f64::MAX);
+            /// Positive infinity of this unit.
+            pub const INFINITY: Self = Self(f64::INFINITY);
+            /// Not a number of this unit.
+            pub const NAN: Self = Self(f64::NAN);
+        }
+    };
+    ($unit_name:ident : f16) => {
+        impl $unit_name {
+            /// The additive identity of this unit.
+            pub const ZERO: Self = Self($crate::units::macros::half::f16::ZERO);
+            /// The multiplicative identity of this unit.
+            pub const ONE: Self = Self($crate::units::macros::half::f16::ONE);
+            /// The smallest finite value of this unit.
+            pub const MIN: Self = Self($crate::units::macros::half::f16::MIN);
+            /// The largest finite value of this unit.
+            pub const MAX: Self = Self($crate::units::macros::half::f16::MAX);
+            /// Positive infinity of this unit.
+            pub const INFINITY: Self = Self($crate::units::macros::half::f16::INFINITY);
+            /// Not a number of this unit.
+            pub const NAN: Self = Self($crate::units::macros::half::f16::NAN);
+        }
+    };
+    ($unit_name:ident : $type:ident) => {
+        impl $unit_name {
+            /// The additive identity of this unit.
+            pub const ZERO: Self = Self(0);
+            /// The multiplicative identity of this unit.
+            pub const ONE: Self = Self(1);
+            /// The smallest value of this unit.
+            pub const MIN: Self = Self($type::MIN);
+            /// The largest value of this unit.
+            pub const MAX: Self = Self($type::MAX);
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_methods {
+    ($unit_name:ident : f64) => {
+        impl $unit_name {
+            /// Returns the non-negative magnitude of the difference between `self` and `other`.
+            #[must_use]
+            #[inline]
+            pub fn abs_diff(self, other: Self) -> Self {
+                Self(num::traits::Float::abs(self.0 - other.0))
+            }
+
+            /// Returns the value halfway between `self` and `other`,
+            /// this doesn't overflow to infinity like `(self + other) / 2.0` can.
+            #[must_use]
+            #[inline]
+            pub fn midpoint(self, other: Self) -> Self {
+                Self($crate::units::macros::midpoint_f64(self.0, other.0))
+            }
+
+            /// Returns the dimensionless ratio of `self` to `other`,
+            /// a ratio by zero is infinite or `NaN` like any float division.
+            #[must_use]
+            #[inline]
+            pub fn ratio(self, other: Self) -> f64 {
+                self.0 / other.0
+            }
+
+            /// Multiplies the value by a dimensionless factor.
+            #[must_use]
+            #[inline]
+            pub fn scale(self, factor: f64) -> Self {
+                Self(self.0 * factor)
+            }
+
+            /// Returns the ordering between `self` and `other` as defined by [`f64::total_cmp`],
+            /// this is a total order unlike [`PartialOrd`] so `NaN` values are also ordered.
+            #[must_use]
+            #[inline]
+            pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+    };
+    ($unit_name:ident : f16) => {
+        impl $unit_name {
+            /// Returns the non-negative magnitude of the difference between `self` and `other`.
+            #[must_use]
+            #[inline]
+            pub fn abs_diff(self, other: Self) -> Self {
+                Self(num::traits::Float::abs(self.0 - other.0))
+            }
+
+            /// Returns the value halfway between `self` and `other`,
+            /// computed as an `f32` so it can't overflow and is rounded once.
+            #[must_use]
+            #[inline]
+            pub fn midpoint(self, other: Self) -> Self {
+                Self($crate::units::macros::half::f16::from_f32(
+                    (self.0.to_f32() + other.0.to_f32()) / 2.0,
+                ))
+            }
+
+            /// Returns the dimensionless ratio of `self` to `other` as an `f64`,
+            /// a ratio by zero is infinite or `NaN` like any float division.
+            #[must_use]
+            #[inline]
+            pub fn ratio(self, other: Self) -> f64 {
+                self.0.to_f64() / other.0.to_f64()
+            }
+
+            /// Multiplies the value by a dimensionless factor, rounding the result to the nearest `f16`.
+            #[must_use]
+            #[inline]
+            pub fn scale(self, factor: f64) -> Self {
+                Self($crate::units::macros::half::f16::from_f64(
+                    self.0.to_f64() * factor,
+                ))
+            }
+
+            /// Returns the ordering between `self` and `other` as a total order,
+            /// this is unlike [`PartialOrd`] so `NaN` values are also ordered.
+            #[must_use]
+            #[inline]
+            pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+    };
+    ($unit_name:ident : i64) => {
+        impl $unit_name {
+            /// Returns the non-negative magnitude of the difference between `self` and `other`,
+            /// saturating at [`MAX`](Self::MAX) instead of overflowing.
+            #[must_use]
+            #[inline]
+            pub fn abs_diff(self, other: Self) -> Self {
+                Self(i64::try_from(self.0.abs_diff(other.0)).unwrap_or(i64::MAX))
+            }
+
+            /// Returns the value halfway between `self` and `other` rounded towards zero,
+            /// this can't overflow unlike `(self + other) / 2`.
+            #[must_use]
+            #[inline]
+            pub const fn midpoint(self, other: Self) -> Self {
+                Self($crate::units::macros::midpoint_i64(self.0, other.0))
+            }
+
+            /// Returns the dimensionless ratio of `self` to `other` as an `f64`,
+            /// a ratio by zero is infinite or `NaN` as the division is done in floating point.
+            #[must_use]
+            #[inline]
+            #[allow(clippy::cast_precision_loss)]
+            pub fn ratio(self, other: Self) -> f64 {
+                self.0 as f64 / other.0 as f64
+            }
+
+            /// Multiplies the value by a dimensionless factor in floating point,
+            /// the result is truncated towards zero and saturates at [`MIN`](Self::MIN) and [`MAX`](Self::MAX).
+            #[must_use]
+            #[inline]
+            #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+            pub fn scale(self, factor: f64) -> Self {
+                Self((self.0 as f64 * factor) as i64)
+            }
+
+            /// Returns the ordering between `self` and `other`,
+            /// this is the same as [`Ord::cmp`] and exists for parity with float units.
+            #[must_use]
+            #[inline]
+            pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+    };
+    ($unit_name:ident : u64) => {
+        impl $unit_name {
+            /// Returns the magnitude of the difference between `self` and `other`,
+            /// this cannot underflow unlike `self - other`.
+            #[must_use]
+            #[inline]
+            pub const fn abs_diff(self, other: Self) -> Self {
+                Self(self.0.abs_diff(other.0))
+            }
+
+            /// Returns the value halfway between `self` and `other` rounded down,
+            /// this can't overflow unlike `(self + other) / 2`.
+            #[must_use]
+            #[inline]
+            pub const fn midpoint(self, other: Self) -> Self {
+                Self($crate::units::macros::midpoint_u64(self.0, other.0))
+            }
+
+            /// Returns the dimensionless ratio of `self` to `other` as an `f64`,
+            /// a ratio by zero is infinite or `NaN` as the division is done in floating point.
+            #[must_use]
+            #[inline]
+            #[allow(clippy::cast_precision_loss)]
+            pub fn ratio(self, other: Self) -> f64 {
+                self.0 as f64 / other.0 as f64
+            }
+
+            /// Multiplies the value by a dimensionless factor in floating point,
+            /// the result is truncated towards zero and saturates at `0` and [`MAX`](Self::MAX).
+            #[must_use]
+            #[inline]
+            #[allow(
+                clippy::cast_precision_loss,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss
+            )]
+            pub fn scale(self, factor: f64) -> Self {
+                Self((self.0 as f64 * factor) as u64)
+            }
+
+            /// Returns the ordering between `self` and `other`,
+            /// this is the same as [`Ord::cmp`] and exists for parity with float units.
+            #[must_use]
+            #[inline]
+            pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_try_from {
+    ($unit_name:ident : f64) => {
+        impl $unit_name {
+            /// Creates a new instance of the unit with the given value,
+            /// rejecting `NaN` and infinite values.
+            ///
+            /// # Errors
+            /// Returns [`UnitError::NotFinite`]($crate::units::UnitError::NotFinite) if the value is not finite
+            #[inline]
+            pub fn try_new(value: f64) -> Result<Self, $crate::units::UnitError> {
+                if value.is_finite() {
+                    Ok(Self(value))
+                } else {
+                    Err($crate::units::UnitError::NotFinite(stringify!($unit_name)))
+                }
+            }
+        }
+    };
+    ($unit_name:ident : f16) => {
+        impl $unit_name {
+            /// Creates a new instance of the unit with the given value,
+            /// rejecting `NaN` and infinite values.
+            ///
+            /// # Errors
+            /// Returns [`UnitError::NotFinite`]($crate::units::UnitError::NotFinite) if the value is not finite
+            #[inline]
+            pub fn try_new(
+                value: $crate::units::macros::half::f16,
+            ) -> Result<Self, $crate::units::UnitError> {
+                if value.is_finite() {
+                    Ok(Self(value))
+                } else {
+                    Err($crate::units::UnitError::NotFinite(stringify!($unit_name)))
+                }
+            }
+
+            /// Creates a new instance of the unit from an `f64`,
+            /// rejecting values that are not finite or don't fit in an `f16`.
+            ///
+            /// # Errors
+            /// Returns [`UnitError::NotFinite`]($crate::units::UnitError::NotFinite) if the value is not finite
+            /// and [`UnitError::OutOfRange`]($crate::units::UnitError::OutOfRange) if it is too large for an `f16`
+            #[inline]
+            pub fn try_from_f64(value: f64) -> Result<Self, $crate::units::UnitError> {
+                let narrowed = $crate::units::macros::half::f16::from_f64(value);
+                if !value.is_finite() {
+                    Err($crate::units::UnitError::NotFinite(stringify!($unit_name)))
+                } else if !narrowed.is_finite() {
+                    Err($crate::units::UnitError::OutOfRange(stringify!($unit_name)))
+                } else {
+                    Ok(Self(narrowed))
+                }
+            }
+        }
+    };
+    (@narrow $unit_name:ident : $($primitive:ty),*) => {
+        $(
+            /// Range checks the value so it is never truncated or wrapped.
+            impl core::convert::TryFrom<$unit_name> for $primitive {
+                type Error = $crate::units::UnitError;
+                fn try_from(value: $unit_name) -> Result<Self, Self::Error> {
+                    <$primitive>::try_from(value.0)
+                        .map_err(|_| $crate::units::UnitError::OutOfRange(stringify!($primitive)))
+                }
+            }
+        )*
+    };
+    (@new $unit_name:ident : $type:ty) => {
+        impl $unit_name {
+            /// Creates a new instance of the unit from any integer,
+            /// rejecting values that don't fit in the backing type.
+            ///
+            /// # Errors
+            /// Returns [`UnitError::OutOfRange`]($crate::units::UnitError::OutOfRange) if the value doesn't fit
+            #[inline]
+            pub fn try_new<T: TryInto<$type>>(value: T) -> Result<Self, $crate::units::UnitError> {
+                value
+                    .try_into()
+                    .map(Self)
+                    .map_err(|_| $crate::units::UnitError::OutOfRange(stringify!($unit_name)))
+            }
+        }
+    };
+    ($unit_name:ident : i64) => {
+        $crate::unit_try_from!(@narrow $unit_name : u64, i32, u32, i16, u16, i8, u8);
+        $crate::unit_try_from!(@new $unit_name : i64);
+
+        /// Truncates towards zero after rejecting non-finite and out of range values.
+        impl core::convert::TryFrom<f64> for $unit_name {
+            type Error = $crate::units::UnitError;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+            fn try_from(value: f64) -> Result<Self, Self::Error> {
+                if !value.is_finite() {
+                    Err($crate::units::UnitError::NotFinite(stringify!($unit_name)))
+                } else if value < i64::MIN as f64 || value >= i64::MAX as f64 {
+                    Err($crate::units::UnitError::OutOfRange(stringify!($unit_name)))
+                } else {
+                    Ok(Self(value as i64))
+                }
+            }
+        }
+    };
+    ($unit_name:ident : u64) => {
+        $crate::unit_try_from!(@narrow $unit_name : i64, u32, i32, u16, i16, u8, i8);
+        $crate::unit_try_from!(@new $unit_name : u64);
+
+        /// Truncates towards zero after rejecting non-finite, negative and out of range values.
+        impl core::convert::TryFrom<f64> for $unit_name {
+            type Error = $crate::units::UnitError;
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
+            fn try_from(value: f64) -> Result<Self, Self::Error> {
+                if !value.is_finite() {
+                    Err($crate::units::UnitError::NotFinite(stringify!($unit_name)))
+                } else if value < 0.0 || value >= u64::MAX as f64 {
+                    Err($crate::units::UnitError::OutOfRange(stringify!($unit_name)))
+                } else {
+                    Ok(Self(value as u64))
+                }
+            }
+        }
+
+        impl core::convert::TryFrom<i64> for $unit_name {
+            type Error = $crate::units::UnitError;
+            fn try_from(value: i64) -> Result<Self, Self::Error> {
+                u64::try_from(value)
+                    .map(Self)
+                    .map_err(|_| $crate::units::UnitError::OutOfRange(stringify!($unit_name)))
+            }
+        }
+    };
+}
+
 /// NOT FOR DIRECT USE
 #[doc(hidden)]
 #[macro_export]
 macro_rules! unit_binops {
     ($unit_name:ident : $type:ty) => {
-        impl<T> std::ops::Add<T> for $unit_name
+        impl<T> core::ops::Add<T> for $unit_name
         where
             T: Into<Self>,
         {
             type Output = Self;
-            #[must_use]
             #[inline]
             fn add(self, rhs: T) -> Self::Output {
                 Self(self.0 + rhs.into().0)
             }
         }
 
-        impl<T> std::ops::Add<T> for &$unit_name
+        impl<T> core::ops::Add<T> for &$unit_name
         where
             T: Into<$unit_name>,
         {
-            type Output = <$unit_name as std::ops::Add<T>>::Output;
-            #[must_use]
+            type Output = <$unit_name as core::ops::Add<T>>::Output;
             #[inline]
             fn add(self, rhs: T) -> Self::Output {
                 <$unit_name>::add(*self, rhs)
             }
         }
 
-        impl std::ops::Add<$unit_name> for $type {
+        impl core::ops::Add<$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn add(self, rhs: $unit_name) -> Self::Output {
                 $unit_name(self + rhs.0)
             }
         }
 
-        impl std::ops::Add<&$unit_name> for $type {
+        impl core::ops::Add<&$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn add(self, rhs: &$unit_name) -> Self::Output {
                 $unit_name(self + rhs.0)
             }
         }
 
-        impl<T> std::ops::Sub<T> for $unit_name
+        impl<T> core::ops::Sub<T> for $unit_name
         where
             T: Into<Self>,
         {
             type Output = Self;
-            #[must_use]
             #[inline]
             fn sub(self, rhs: T) -> Self::Output {
                 Self(self.0 - rhs.into().0)
             }
         }
 
-        impl<T> std::ops::Sub<T> for &$unit_name
+        impl<T> core::ops::Sub<T> for &$unit_name
         where
             T: Into<$unit_name>,
         {
-            type Output = <$unit_name as std::ops::Sub<T>>::Output;
-            #[must_use]
+            type Output = <$unit_name as core::ops::Sub<T>>::Output;
             #[inline]
             fn sub(self, rhs: T) -> Self::Output {
                 <$unit_name>::sub(*self, rhs)
             }
         }
 
-        impl std::ops::Sub<$unit_name> for $type {
+        impl core::ops::Sub<$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn sub(self, rhs: $unit_name) -> Self::Output {
                 $unit_name(self - rhs.0)
             }
         }
 
-        impl std::ops::Sub<&$unit_name> for $type {
+        impl core::ops::Sub<&$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn sub(self, rhs: &$unit_name) -> Self::Output {
                 $unit_name(self - rhs.0)
             }
         }
 
-        impl<T> std::ops::Mul<T> for $unit_name
+        impl<T> core::ops::Mul<T> for $unit_name
         where
             T: Into<Self>,
         {
             type Output = Self;
-            #[must_use]
             #[inline]
             fn mul(self, rhs: T) -> Self::Output {
                 Self(self.0 * rhs.into().0)
             }
         }
 
-        impl<T> std::ops::Mul<T> for &$unit_name
+        impl<T> core::ops::Mul<T> for &$unit_name
         where
             T: Into<$unit_name>,
         {
-            type Output = <$unit_name as std::ops::Mul<T>>::Output;
-            #[must_use]
+            type Output = <$unit_name as core::ops::Mul<T>>::Output;
             #[inline]
             fn mul(self, rhs: T) -> Self::Output {
                 <$unit_name>::mul(*self, rhs)
             }
         }
 
-        impl std::ops::Mul<$unit_name> for $type {
+        impl core::ops::Mul<$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn mul(self, rhs: $unit_name) -> Self::Output {
                 $unit_name(self * rhs.0)
             }
         }
 
-        impl std::ops::Mul<&$unit_name> for $type {
+        impl core::ops::Mul<&$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn mul(self, rhs: &$unit_name) -> Self::Output {
                 $unit_name(self * rhs.0)
             }
         }
 
-        impl<T> std::ops::Div<T> for $unit_name
+        impl<T> core::ops::Div<T> for $unit_name
         where
             T: Into<Self>,
         {
             type Output = Self;
-            #[must_use]
             #[inline]
             fn div(self, rhs: T) -> Self::Output {
                 Self(self.0 / rhs.into().0)
             }
         }
 
-        impl<T> std::ops::Div<T> for &$unit_name
+        impl<T> core::ops::Div<T> for &$unit_name
         where
             T: Into<$unit_name>,
         {
-            type Output = <$unit_name as std::ops::Div<T>>::Output;
-            #[must_use]
+            type Output = <$unit_name as core::ops::Div<T>>::Output;
             #[inline]
             fn div(self, rhs: T) -> Self::Output {
                 <$unit_name>::div(*self, rhs)
             }
         }
 
-        impl std::ops::Div<$unit_name> for $type {
+        impl core::ops::Div<$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn div(self, rhs: $unit_name) -> Self::Output {
                 $unit_name(self / rhs.0)
             }
         }
 
-        impl std::ops::Div<&$unit_name> for $type {
+        impl core::ops::Div<&$unit_name> for $type {
             type Output = $unit_name;
-            #[must_use]
             #[inline]
             fn div(self, rhs: &$unit_name) -> Self::Output {
                 $unit_name(self / rhs.0)
             }
         }
 
-        impl std::ops::Rem for $unit_name {
+        impl core::ops::Rem for $unit_name {
             type Output = Self;
-            #[must_use]
             #[inline]
             fn rem(self, rhs: Self) -> Self::Output {
                 Self(self.0 % rhs.0)
             }
         }
 
-        impl std::ops::RemAssign for $unit_name {
+        impl core::ops::RemAssign for $unit_name {
             #[inline]
             fn rem_assign(&mut self, rhs: Self) {
                 self.0 %= rhs.0;
             }
         }
 
-        impl<T> std::ops::AddAssign<T> for $unit_name
+        impl<T> core::ops::AddAssign<T> for $unit_name
         where
             T: Into<Self>,
         {
@@ -392,7 +1219,7 @@ macro_rules! unit_binops {
             }
         }
 
-        impl<T> std::ops::SubAssign<T> for $unit_name
+        impl<T> core::ops::SubAssign<T> for $unit_name
         where
             T: Into<Self>,
         {
@@ -402,7 +1229,9 @@ macro_rules! unit_binops {
             }
         }
 
-        impl<T> std::ops::MulAssign<T> for $unit_name
+        /// Scales in place by a scalar, like `gain *= 0.98`,
+        /// the backing type and the other number types the unit converts from all count as scalars.
+        impl<T> core::ops::MulAssign<T> for $unit_name
         where
             T: Into<Self>,
         {
@@ -412,7 +1241,9 @@ macro_rules! unit_binops {
             }
         }
 
-        impl<T> std::ops::DivAssign<T> for $unit_name
+        /// Divides in place by a scalar, like `gain /= 2.0`,
+        /// the backing type and the other number types the unit converts from all count as scalars.
+        impl<T> core::ops::DivAssign<T> for $unit_name
         where
             T: Into<Self>,
         {
@@ -421,6 +1252,36 @@ macro_rules! unit_binops {
                 self.0 /= rhs.into().0;
             }
         }
+
+        impl core::iter::Sum for $unit_name {
+            #[inline]
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, value| Self(acc.0 + value.0))
+            }
+        }
+
+        impl<'a> core::iter::Sum<&'a $unit_name> for $unit_name {
+            #[inline]
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, value| Self(acc.0 + value.0))
+            }
+        }
+
+        /// Sums every unit in the iterator, this does **not** collect into a container.
+        impl core::iter::FromIterator<$unit_name> for $unit_name {
+            #[inline]
+            fn from_iter<I: IntoIterator<Item = Self>>(iter: I) -> Self {
+                iter.into_iter().sum()
+            }
+        }
+
+        /// Sums every unit in the iterator, this does **not** collect into a container.
+        impl<'a> core::iter::FromIterator<&'a $unit_name> for $unit_name {
+            #[inline]
+            fn from_iter<I: IntoIterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.into_iter().sum()
+            }
+        }
     };
 }
 
@@ -429,9 +1290,8 @@ macro_rules! unit_binops {
 #[macro_export]
 macro_rules! unit_neg {
     ($unit_name:ident : $type:ty) => {
-        impl std::ops::Neg for $unit_name {
+        impl core::ops::Neg for $unit_name {
             type Output = Self;
-            #[must_use]
             #[inline]
             fn neg(self) -> Self::Output {
                 Self(-self.0)
@@ -441,23 +1301,87 @@ macro_rules! unit_neg {
 }
 
 /// NOT FOR DIRECT USE
+#[cfg(feature = "serde")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! unit_serde {
+    ($unit_name:ident : f16) => {
+        impl serde::Serialize for $unit_name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $crate::units::macros::serde_repr::serialize(
+                    &self.0.to_f32(),
+                    Self::SYMBOL.unwrap_or(Self::NAME),
+                    serializer,
+                )
+            }
+        }
+        impl<'de> serde::Deserialize<'de> for $unit_name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $crate::units::macros::serde_repr::deserialize::<f32, D>(
+                    Self::NAME,
+                    Self::SYMBOL,
+                    deserializer,
+                )
+                .map(|value| Self($crate::units::macros::half::f16::from_f32(value)))
+            }
+        }
+    };
     ($unit_name:ident : $type:ty) => {
         impl serde::Serialize for $unit_name {
             fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-                self.0.serialize(serializer)
+                $crate::units::macros::serde_repr::serialize(
+                    &self.value(),
+                    Self::SYMBOL.unwrap_or(Self::NAME),
+                    serializer,
+                )
             }
         }
         impl<'de> serde::Deserialize<'de> for $unit_name {
             fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-                <$type as serde::Deserialize>::deserialize(deserializer).map(|value| Self(value))
+                $crate::units::macros::serde_repr::deserialize::<$type, D>(
+                    Self::NAME,
+                    Self::SYMBOL,
+                    deserializer,
+                )
+                .map(Self::new)
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_serde {
+    ($unit_name:ident : $type:ty) => {};
+}
+
+/// NOT FOR DIRECT USE
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_arbitrary {
+    ($unit_name:ident) => {
+        impl $crate::units::macros::proptest::arbitrary::Arbitrary for $unit_name {
+            type Parameters = ();
+            type Strategy = $crate::units::macros::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                $crate::units::strategy::sensible::<Self>()
             }
         }
     };
 }
 
+/// NOT FOR DIRECT USE
+#[cfg(not(feature = "proptest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_arbitrary {
+    ($unit_name:ident) => {};
+}
+
 /// NOT FOR DIRECT USE
 #[doc(hidden)]
 #[macro_export]
@@ -508,6 +1432,7 @@ macro_rules! unit_num {
 }
 
 /// NOT FOR DIRECT USE
+#[cfg(feature = "structure")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! unit_structure {
@@ -515,16 +1440,19 @@ macro_rules! unit_structure {
         impl $crate::structure::FrcStructure for $unit_name {
             const TYPE: &'static str = "float64";
             const SIZE: usize = 8;
-            const SCHEMA_SUPPLIER: fn() -> String = || String::with_capacity(0);
+            const SCHEMA_SUPPLIER: fn() -> $crate::units::macros::String =
+                || $crate::units::macros::String::with_capacity(0);
+
+            fn pack(&self, buffer: &mut $crate::units::macros::Vec<u8>) {
+                buffer.extend_from_slice(&f64::to_le_bytes(self.value()));
+            }
 
-            fn pack(&self, buffer: &mut Vec<u8>) {
-                buffer.extend_from_slice(&f64::to_le_bytes(self.0));
+            fn unpack(buffer: &mut $crate::structure::Cursor<&[u8]>) -> Self {
+                Self::new(<f64 as $crate::structure::FrcStructure>::unpack(buffer))
             }
 
-            fn unpack(buffer: &mut std::io::Cursor<&[u8]>) -> Self {
-                let mut value_buffer = [0u8; Self::SIZE];
-                let _ = std::io::Read::read_exact(buffer, &mut value_buffer);
-                Self(f64::from_le_bytes(value_buffer))
+            fn unpack_slice(bytes: &[u8]) -> Self {
+                Self::new(f64::from_le_bytes($crate::structure::read_array(bytes)))
             }
         }
     };
@@ -532,16 +1460,19 @@ macro_rules! unit_structure {
         impl $crate::structure::FrcStructure for $unit_name {
             const TYPE: &'static str = "int64";
             const SIZE: usize = 8;
-            const SCHEMA_SUPPLIER: fn() -> String = || String::with_capacity(0);
+            const SCHEMA_SUPPLIER: fn() -> $crate::units::macros::String =
+                || $crate::units::macros::String::with_capacity(0);
+
+            fn pack(&self, buffer: &mut $crate::units::macros::Vec<u8>) {
+                buffer.extend_from_slice(&i64::to_le_bytes(self.value()));
+            }
 
-            fn pack(&self, buffer: &mut Vec<u8>) {
-                buffer.extend_from_slice(&i64::to_le_bytes(self.0));
+            fn unpack(buffer: &mut $crate::structure::Cursor<&[u8]>) -> Self {
+                Self::new(<i64 as $crate::structure::FrcStructure>::unpack(buffer))
             }
 
-            fn unpack(buffer: &mut std::io::Cursor<&[u8]>) -> Self {
-                let mut value_buffer = [0u8; Self::SIZE];
-                let _ = std::io::Read::read_exact(buffer, &mut value_buffer);
-                Self(i64::from_le_bytes(value_buffer))
+            fn unpack_slice(bytes: &[u8]) -> Self {
+                Self::new(i64::from_le_bytes($crate::structure::read_array(bytes)))
             }
         }
     };
@@ -549,17 +1480,28 @@ macro_rules! unit_structure {
         impl $crate::structure::FrcStructure for $unit_name {
             const TYPE: &'static str = "uint64";
             const SIZE: usize = 8;
-            const SCHEMA_SUPPLIER: fn() -> String = || String::with_capacity(0);
+            const SCHEMA_SUPPLIER: fn() -> $crate::units::macros::String =
+                || $crate::units::macros::String::with_capacity(0);
 
-            fn pack(&self, buffer: &mut Vec<u8>) {
-                buffer.extend_from_slice(&u64::to_le_bytes(self.0));
+            fn pack(&self, buffer: &mut $crate::units::macros::Vec<u8>) {
+                buffer.extend_from_slice(&u64::to_le_bytes(self.value()));
             }
 
-            fn unpack(buffer: &mut std::io::Cursor<&[u8]>) -> Self {
-                let mut value_buffer = [0u8; Self::SIZE];
-                let _ = std::io::Read::read_exact(buffer, &mut value_buffer);
-                Self(u64::from_le_bytes(value_buffer))
+            fn unpack(buffer: &mut $crate::structure::Cursor<&[u8]>) -> Self {
+                Self::new(<u64 as $crate::structure::FrcStructure>::unpack(buffer))
+            }
+
+            fn unpack_slice(bytes: &[u8]) -> Self {
+                Self::new(u64::from_le_bytes($crate::structure::read_array(bytes)))
             }
         }
     };
 }
+
+/// NOT FOR DIRECT USE
+#[cfg(not(feature = "structure"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_structure {
+    ($unit_name:ident : $type:ty) => {};
+}
@@ -0,0 +1,104 @@
+//! NOT FOR DIRECT USE
+//!
+//! The shared conversion logic behind [`unit_time!`](crate::unit_time).
+
+use core::time::Duration;
+
+use crate::units::UnitError;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+fn from_nanos(nanos: u128, name: &'static str) -> Result<Duration, UnitError> {
+    let secs = u64::try_from(nanos / NANOS_PER_SEC).map_err(|_| UnitError::OutOfRange(name))?;
+    // always less than a billion so it fits in a `u32`
+    #[allow(clippy::cast_possible_truncation)]
+    let subsec_nanos = (nanos % NANOS_PER_SEC) as u32;
+    Ok(Duration::new(secs, subsec_nanos))
+}
+
+/// The backing types of units that can be converted to and from a [`Duration`].
+pub trait DurationBacking: Sized {
+    /// Converts a [`Duration`] into the backing value of a unit
+    /// where one of the unit is `factor_to_nanos` nanoseconds,
+    /// integer units truncate and saturate.
+    fn from_duration(duration: Duration, factor_to_nanos: u128) -> Self;
+
+    /// Converts the backing value of a unit into a [`Duration`]
+    /// where one of the unit is `factor_to_nanos` nanoseconds,
+    /// float units are rounded to the nearest nanosecond.
+    ///
+    /// # Errors
+    /// Returns a [`UnitError`] if the value is negative, not finite or too large for a [`Duration`]
+    fn to_duration(self, factor_to_nanos: u128, name: &'static str) -> Result<Duration, UnitError>;
+
+    /// Converts the backing value of a unit into a [`Duration`] like [`to_duration`](Self::to_duration),
+    /// negative and `NaN` values become [`Duration::ZERO`] and values too large become [`Duration::MAX`].
+    fn saturating_to_duration(self, factor_to_nanos: u128) -> Duration;
+}
+
+impl DurationBacking for f64 {
+    #[allow(clippy::cast_precision_loss)]
+    fn from_duration(duration: Duration, factor_to_nanos: u128) -> Self {
+        duration.as_nanos() as Self / factor_to_nanos as Self
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn to_duration(self, factor_to_nanos: u128, name: &'static str) -> Result<Duration, UnitError> {
+        if !self.is_finite() {
+            return Err(UnitError::NotFinite(name));
+        }
+        let nanos = num::traits::Float::round(self * factor_to_nanos as Self);
+        if nanos < 0.0 || nanos >= u128::MAX as Self {
+            return Err(UnitError::OutOfRange(name));
+        }
+        from_nanos(nanos as u128, name)
+    }
+
+    fn saturating_to_duration(self, factor_to_nanos: u128) -> Duration {
+        if self.is_nan() || self <= 0.0 {
+            Duration::ZERO
+        } else {
+            self.to_duration(factor_to_nanos, "")
+                .unwrap_or(Duration::MAX)
+        }
+    }
+}
+
+impl DurationBacking for i64 {
+    fn from_duration(duration: Duration, factor_to_nanos: u128) -> Self {
+        Self::try_from(duration.as_nanos() / factor_to_nanos).unwrap_or(Self::MAX)
+    }
+
+    fn to_duration(self, factor_to_nanos: u128, name: &'static str) -> Result<Duration, UnitError> {
+        let value = u64::try_from(self).map_err(|_| UnitError::OutOfRange(name))?;
+        value.to_duration(factor_to_nanos, name)
+    }
+
+    fn saturating_to_duration(self, factor_to_nanos: u128) -> Duration {
+        u64::try_from(self).map_or(Duration::ZERO, |value| {
+            value.saturating_to_duration(factor_to_nanos)
+        })
+    }
+}
+
+impl DurationBacking for u64 {
+    fn from_duration(duration: Duration, factor_to_nanos: u128) -> Self {
+        Self::try_from(duration.as_nanos() / factor_to_nanos).unwrap_or(Self::MAX)
+    }
+
+    fn to_duration(self, factor_to_nanos: u128, name: &'static str) -> Result<Duration, UnitError> {
+        let nanos = u128::from(self)
+            .checked_mul(factor_to_nanos)
+            .ok_or(UnitError::OutOfRange(name))?;
+        from_nanos(nanos, name)
+    }
+
+    fn saturating_to_duration(self, factor_to_nanos: u128) -> Duration {
+        self.to_duration(factor_to_nanos, "")
+            .unwrap_or(Duration::MAX)
+    }
+}
@@ -13,61 +13,250 @@ macro_rules! complex_type_name {
     };
 }
 
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_symbol {
+    () => {
+        None
+    };
+    ($symbol:literal) => {
+        Some($symbol)
+    };
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Expands to `body` unless both units are the same, which is a compile error instead.
+/// The local macro matches the unit names literally so the conflicting impls are never generated.
+/// Every pair also implements [`UnitConversionPair`](crate::units::macros::UnitConversionPair)
+/// both ways, so declaring a pair twice names it in the error.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_conversion_guard {
+    ($unit_a:ident | $unit_b:ident { $($body:tt)* }) => {
+        macro_rules! frclib_unit_conversion_guard {
+            ($unit_a) => {
+                compile_error!(concat!(
+                    "`unit_conversion!` cannot convert `",
+                    stringify!($unit_a),
+                    "` to itself"
+                ));
+            };
+            ($unit_b) => {
+                impl $crate::units::macros::UnitConversionPair<$unit_b> for $unit_a {}
+                impl $crate::units::macros::UnitConversionPair<$unit_a> for $unit_b {}
+                $($body)*
+            };
+        }
+        frclib_unit_conversion_guard!($unit_b);
+    };
+}
+
 /// NOT FOR DIRECT USE
 #[doc(hidden)]
 #[macro_export]
 macro_rules! inner_unit_conversion {
     ($unit_a:ident $type_a:ty | $unit_b:ident $type_b:ty : $conv_fn:ident) => {
-        #[allow(trivial_numeric_casts)]
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
         impl From<$unit_a> for $unit_b {
             fn from(value: $unit_a) -> Self {
                 $unit_b($conv_fn(value.0))
             }
         }
-        #[allow(trivial_numeric_casts)]
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
         impl From<&$unit_a> for $unit_b {
             fn from(value: &$unit_a) -> Self {
                 $unit_b($conv_fn(value.0))
             }
         }
-        #[allow(trivial_numeric_casts)]
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
         impl From<$unit_b> for $unit_a {
             fn from(value: $unit_b) -> Self {
-                $unit_a((value.0 / $conv_fn(<$type_a>::from(1.0)) as $type_b) as $type_a)
+                let ratio = $conv_fn(<$type_a>::from(1.0));
+                $unit_a($crate::units::macros::RatioDiv::ratio_div(value.0, ratio) as $type_a)
             }
         }
-        #[allow(trivial_numeric_casts)]
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
         impl From<&$unit_b> for $unit_a {
             fn from(value: &$unit_b) -> Self {
-                $unit_a((value.0 / $conv_fn(<$type_a>::from(1.0)) as $type_b) as $type_a)
+                let ratio = $conv_fn(<$type_a>::from(1.0));
+                $unit_a($crate::units::macros::RatioDiv::ratio_div(value.0, ratio) as $type_a)
             }
         }
 
-        #[allow(trivial_numeric_casts)]
-        impl std::cmp::PartialEq<$unit_a> for $unit_b {
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        impl core::cmp::PartialEq<$unit_a> for $unit_b {
             fn eq(&self, other: &$unit_a) -> bool {
                 self.0 == ($conv_fn(other.0) as $type_b)
             }
         }
-        #[allow(trivial_numeric_casts)]
-        impl std::cmp::PartialOrd<$unit_a> for $unit_b {
-            fn partial_cmp(&self, other: &$unit_a) -> Option<std::cmp::Ordering> {
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        impl core::cmp::PartialOrd<$unit_a> for $unit_b {
+            fn partial_cmp(&self, other: &$unit_a) -> Option<core::cmp::Ordering> {
                 self.0.partial_cmp(&$conv_fn(other.0))
             }
         }
-        #[allow(trivial_numeric_casts)]
-        impl std::cmp::PartialEq<$unit_b> for $unit_a {
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        impl core::cmp::PartialEq<$unit_b> for $unit_a {
             fn eq(&self, other: &$unit_b) -> bool {
-                self.0 == ((other.0 / $conv_fn(<$type_a>::from(1.0)) as $type_b) as $type_a)
+                let ratio = $conv_fn(<$type_a>::from(1.0));
+                self.0 == ($crate::units::macros::RatioDiv::ratio_div(other.0, ratio) as $type_a)
             }
         }
-        #[allow(trivial_numeric_casts)]
-        impl std::cmp::PartialOrd<$unit_b> for $unit_a {
-            fn partial_cmp(&self, other: &$unit_b) -> Option<std::cmp::Ordering> {
+        #[allow(
+            trivial_numeric_casts,
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        impl core::cmp::PartialOrd<$unit_b> for $unit_a {
+            fn partial_cmp(&self, other: &$unit_b) -> Option<core::cmp::Ordering> {
+                let ratio = $conv_fn(<$type_a>::from(1.0));
                 self.0.partial_cmp(
-                    &((other.0 / $conv_fn(<$type_a>::from(1.0)) as $type_b) as $type_a),
+                    &($crate::units::macros::RatioDiv::ratio_div(other.0, ratio) as $type_a),
                 )
             }
         }
     };
 }
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! inner_saturating_conversion {
+    ($unit_a:ident $type_a:ty | $unit_b:ident $type_b:ty : $conv_fn:ident, $inverse_fn:ident) => {
+        impl From<$unit_a> for $unit_b {
+            fn from(value: $unit_a) -> Self {
+                $unit_b(<$type_b as $crate::units::macros::SaturatingInt>::saturate(
+                    $conv_fn($crate::units::macros::SaturatingInt::widen(value.0)),
+                ))
+            }
+        }
+        impl From<&$unit_a> for $unit_b {
+            fn from(value: &$unit_a) -> Self {
+                Self::from(*value)
+            }
+        }
+        impl From<$unit_b> for $unit_a {
+            fn from(value: $unit_b) -> Self {
+                $unit_a(<$type_a as $crate::units::macros::SaturatingInt>::saturate(
+                    $inverse_fn($crate::units::macros::SaturatingInt::widen(value.0)),
+                ))
+            }
+        }
+        impl From<&$unit_b> for $unit_a {
+            fn from(value: &$unit_b) -> Self {
+                Self::from(*value)
+            }
+        }
+
+        impl core::cmp::PartialEq<$unit_a> for $unit_b {
+            fn eq(&self, other: &$unit_a) -> bool {
+                $crate::units::macros::SaturatingInt::widen(self.0)
+                    == $conv_fn($crate::units::macros::SaturatingInt::widen(other.0))
+            }
+        }
+        impl core::cmp::PartialOrd<$unit_a> for $unit_b {
+            fn partial_cmp(&self, other: &$unit_a) -> Option<core::cmp::Ordering> {
+                $crate::units::macros::SaturatingInt::widen(self.0).partial_cmp(&$conv_fn(
+                    $crate::units::macros::SaturatingInt::widen(other.0),
+                ))
+            }
+        }
+        impl core::cmp::PartialEq<$unit_b> for $unit_a {
+            fn eq(&self, other: &$unit_b) -> bool {
+                other == self
+            }
+        }
+        impl core::cmp::PartialOrd<$unit_b> for $unit_a {
+            fn partial_cmp(&self, other: &$unit_b) -> Option<core::cmp::Ordering> {
+                other.partial_cmp(self).map(core::cmp::Ordering::reverse)
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_try_conversion {
+    ($unit_a:ident float | $unit_b:ident float : $conv_fn:ident) => {};
+    ($unit_a:ident int | $unit_b:ident int : $conv_fn:ident) => {};
+    ($unit_a:ident uint | $unit_b:ident uint : $conv_fn:ident) => {};
+    ($unit_a:ident float | $unit_b:ident $unit_b_type:ident : $conv_fn:ident) => {
+        $crate::units::macros::paste::paste! {
+            impl $unit_b {
+                #[doc = concat!("Converts from [`", stringify!($unit_a), "`] like the [`From`] impl does,")]
+                #[doc = "but fails instead of saturating when the value doesn't fit."]
+                #[doc = ""]
+                #[doc = "The fractional part is still truncated towards zero."]
+                #[doc = ""]
+                #[doc = "# Errors"]
+                #[doc = "Returns [`UnitError::NotFinite`]($crate::units::UnitError::NotFinite) if the value is not finite"]
+                #[doc = "and [`UnitError::OutOfRange`]($crate::units::UnitError::OutOfRange) if it is out of range for this unit"]
+                #[allow(trivial_numeric_casts, clippy::cast_precision_loss)]
+                #[inline]
+                pub fn [< try_from_ $unit_a:snake >](
+                    value: $unit_a,
+                ) -> Result<Self, $crate::units::UnitError> {
+                    let ratio = $conv_fn(1.0) as f64;
+                    <Self as core::convert::TryFrom<f64>>::try_from(value.0 * ratio)
+                }
+            }
+
+            impl $unit_a {
+                #[doc = concat!("Converts from [`", stringify!($unit_b), "`] like the [`From`] impl does,")]
+                #[doc = "but fails instead of rounding when the value has more significant bits than an `f64` holds."]
+                #[doc = ""]
+                #[doc = "# Errors"]
+                #[doc = "Returns [`UnitError::PrecisionLoss`]($crate::units::UnitError::PrecisionLoss) if the value can't be represented exactly"]
+                #[allow(trivial_numeric_casts, clippy::cast_precision_loss)]
+                #[inline]
+                pub fn [< try_from_ $unit_b:snake >](
+                    value: $unit_b,
+                ) -> Result<Self, $crate::units::UnitError> {
+                    let ratio = $conv_fn(1.0) as f64;
+                    $crate::units::macros::ExactF64::exact_f64(value.0)
+                        .map(|value| Self(value / ratio))
+                        .ok_or($crate::units::UnitError::PrecisionLoss(stringify!($unit_a)))
+                }
+            }
+        }
+    };
+}
@@ -5,25 +5,320 @@ mod number;
 #[macro_use]
 mod helper;
 #[doc(hidden)]
+pub mod duration;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub mod serde_repr;
+#[cfg(feature = "structure")]
+#[doc(hidden)]
+pub use alloc::{string::String, vec::Vec};
+#[cfg(feature = "half")]
+#[doc(hidden)]
+pub use half;
+#[doc(hidden)]
 pub use paste;
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub use proptest;
+
+/// NOT FOR DIRECT USE
+///
+/// Implemented both ways for every pair of units given to [`unit_conversion!`](crate::unit_conversion),
+/// a conflicting implementation of it means the pair was declared twice.
+#[doc(hidden)]
+pub trait UnitConversionPair<T> {}
+
+/// NOT FOR DIRECT USE
+///
+/// Widens an integer into an `f64` only when no bits are lost.
+#[doc(hidden)]
+pub trait ExactF64: Copy {
+    /// The value as an `f64` or `None` if it can't be represented exactly.
+    fn exact_f64(self) -> Option<f64>;
+}
+
+impl ExactF64 for i64 {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn exact_f64(self) -> Option<f64> {
+        // `2^63` rounds down to `i64::MAX` when cast back so it has to be excluded first
+        let widened = self as f64;
+        (widened < 9_223_372_036_854_775_808.0 && widened as Self == self).then_some(widened)
+    }
+}
+
+impl ExactF64 for u64 {
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn exact_f64(self) -> Option<f64> {
+        // `2^64` rounds down to `u64::MAX` when cast back so it has to be excluded first
+        let widened = self as f64;
+        (widened < 18_446_744_073_709_551_616.0 && widened as Self == self).then_some(widened)
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Divides a backing value by the conversion of `1`, for the reverse of a [`unit_conversion!`].
+/// Integers are divided in their own type so the whole part is exact,
+/// only the remainder goes through an `f64`.
+#[doc(hidden)]
+pub trait RatioDiv: Copy {
+    /// The value divided by `ratio`, a zero ratio divides as floats and gives an infinity or `NaN`.
+    fn ratio_div(self, ratio: Self) -> f64;
+}
+
+impl RatioDiv for f64 {
+    fn ratio_div(self, ratio: Self) -> f64 {
+        self / ratio
+    }
+}
+
+impl RatioDiv for i64 {
+    #[allow(clippy::cast_precision_loss)]
+    fn ratio_div(self, ratio: Self) -> f64 {
+        match (self.checked_div(ratio), self.checked_rem(ratio)) {
+            (Some(quotient), Some(remainder)) => quotient as f64 + remainder as f64 / ratio as f64,
+            _ => self as f64 / ratio as f64,
+        }
+    }
+}
+
+impl RatioDiv for u64 {
+    #[allow(clippy::cast_precision_loss)]
+    fn ratio_div(self, ratio: Self) -> f64 {
+        match (self.checked_div(ratio), self.checked_rem(ratio)) {
+            (Some(quotient), Some(remainder)) => quotient as f64 + remainder as f64 / ratio as f64,
+            _ => self as f64 / ratio as f64,
+        }
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Widens an integer backing into an `i128` and clamps it back,
+/// for conversions that saturate instead of wrapping.
+#[doc(hidden)]
+pub trait SaturatingInt: Copy {
+    /// The value as an `i128`, which holds every `i64` and `u64`.
+    fn widen(self) -> i128;
+    /// The value clamped into the range of the backing type.
+    fn saturate(value: i128) -> Self;
+}
+
+impl SaturatingInt for i64 {
+    fn widen(self) -> i128 {
+        i128::from(self)
+    }
+    fn saturate(value: i128) -> Self {
+        Self::try_from(value).unwrap_or(if value < 0 { Self::MIN } else { Self::MAX })
+    }
+}
+
+impl SaturatingInt for u64 {
+    fn widen(self) -> i128 {
+        i128::from(self)
+    }
+    fn saturate(value: i128) -> Self {
+        Self::try_from(value).unwrap_or(if value < 0 { Self::MIN } else { Self::MAX })
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Wraps a widened value into `[0, modulus)` for modular integer units,
+/// sums and differences of two wrapped values always fit in an `i128`.
+#[doc(hidden)]
+#[must_use]
+pub fn wrap_int<T: SaturatingInt>(value: i128, modulus: T) -> T {
+    T::saturate(value.rem_euclid(modulus.widen()))
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Divides a widened value by the conversion of `1`, the default inverse of a `saturating` conversion.
+/// A ratio of `0` means the conversion coarsens past one unit,
+/// so any non-zero value saturates towards its sign instead of dividing by zero.
+#[doc(hidden)]
+#[must_use]
+pub const fn saturating_ratio_div(value: i128, ratio: i128) -> i128 {
+    match value.checked_div(ratio) {
+        Some(quotient) => quotient,
+        None if value < 0 => i128::MIN,
+        None if value == 0 => 0,
+        None => i128::MAX,
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The midpoint of two floats without overflowing to infinity,
+/// the same algorithm as `f64::midpoint` which needs a newer compiler than this crate supports.
+#[doc(hidden)]
+#[must_use]
+pub fn midpoint_f64(a: f64, b: f64) -> f64 {
+    const LO: f64 = f64::MIN_POSITIVE * 2.0;
+    const HI: f64 = f64::MAX / 2.0;
+    let (abs_a, abs_b) = (num::traits::Float::abs(a), num::traits::Float::abs(b));
+    if abs_a <= HI && abs_b <= HI {
+        // overflow is impossible
+        (a + b) / 2.0
+    } else if abs_a < LO {
+        // not safe to halve `a`
+        a + (b / 2.0)
+    } else if abs_b < LO {
+        // not safe to halve `b`
+        (a / 2.0) + b
+    } else {
+        // safe to halve both
+        (a / 2.0) + (b / 2.0)
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The midpoint of two signed integers rounded towards zero without overflowing,
+/// the same as `i64::midpoint` which needs a newer compiler than this crate supports.
+#[doc(hidden)]
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn midpoint_i64(a: i64, b: i64) -> i64 {
+    ((a as i128 + b as i128) / 2) as i64
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The midpoint of two unsigned integers rounded down without overflowing,
+/// the same as `u64::midpoint` which needs a newer compiler than this crate supports.
+#[doc(hidden)]
+#[must_use]
+pub const fn midpoint_u64(a: u64, b: u64) -> u64 {
+    (a & b) + ((a ^ b) >> 1)
+}
+
+/// NOT FOR DIRECT USE
+///
+/// Wraps a float into `[0, modulus)`, `NaN` and infinities become `NaN`.
+#[doc(hidden)]
+#[must_use]
+pub fn wrap_f64(value: f64, modulus: f64) -> f64 {
+    let wrapped = num::traits::Euclid::rem_euclid(&value, &modulus);
+    // `rem_euclid` can round up to `modulus` for tiny negative inputs
+    if wrapped >= modulus {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The bits of a float to hash, `-0.0` hashes as `0.0` and every NaN hashes the same
+/// so values that compare equal always hash equal.
+#[doc(hidden)]
+#[must_use]
+pub fn hash_bits_f64(value: f64) -> u64 {
+    if value == 0.0 {
+        0
+    } else if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The symbol a unit is parsed by, its name if it has no symbol.
+#[doc(hidden)]
+#[must_use]
+pub const fn symbol_or_name(symbol: Option<&'static str>, name: &'static str) -> &'static str {
+    match symbol {
+        Some(symbol) => symbol,
+        None => name,
+    }
+}
+
+/// NOT FOR DIRECT USE
+///
+/// The trimmed value before `symbol` if `text` ends with it.
+#[doc(hidden)]
+#[must_use]
+pub fn strip_symbol<'a>(text: &'a str, symbol: &str) -> Option<&'a str> {
+    text.trim().strip_suffix(symbol).map(str::trim)
+}
 
 /// A macro for defining a unit of measurement.
 /// This macro is used to define a new unit of measurement.
 ///
+/// A symbol can optionally be given after the unit name,
+/// it is exposed as `SYMBOL` and used when serializing to human-readable formats
+/// with the `units-serde-struct` feature.
+/// [`LowerExp`](core::fmt::LowerExp) and [`UpperExp`](core::fmt::UpperExp)
+/// also append it after the value, e.g. `format!("{:.1e}", Meter(1234.5))` is `1.2e3 m`.
+/// [`Debug`] prints `Meter(1234.5)` like a derived one, `{:#?}` prints `Meter(1234.5 m)`.
+///
+/// Float units only implement [`PartialEq`] and [`PartialOrd`] to keep IEEE semantics,
+/// wrap them in [`CanonEq`](crate::units::CanonEq) where [`Eq`] is needed.
+///
+/// A float unit can be made modular with `float mod <modulus>`, like `unit!(WrappedDegree: float mod 360.0)`.
+/// Its values live in the half-open range `[0, modulus)`, so `modulus` itself is `0`.
+/// `new`, `From<f64>` and the arithmetic operators wrap their results into that range,
+/// and [`PartialEq`], [`PartialOrd`] and [`Hash`](core::hash::Hash) compare the wrapped values
+/// so a value written into the field directly still compares as its wrapped value.
+/// `NaN` and infinities wrap to `NaN`.
+/// The modulus has to be finite and greater than zero, anything else fails to compile.
+/// Modular units only add and subtract each other and scale by an `f64`,
+/// the other float methods aren't generated as most don't make sense on a circle.
+///
+/// Integer units can be modular too with `int mod <modulus>` or `uint mod <modulus>`,
+/// like `unit!(EncoderTick: uint mod 65536)` for a 16 bit encoder.
+/// They wrap the same way, only add, subtract and negate,
+/// and have `unwrap_delta` to turn consecutive readings into a count that doesn't wrap.
+///
+/// With the `half` feature a unit can also be backed by a `half::f16` using `float16`,
+/// these units serialize as an `f32` and have no `FrcStructure` implementation
+/// as the struct spec has no half-precision type.
+///
 /// # Example
 /// ```
 /// use frclib_core::unit;
 ///
 /// unit!(DegreeFloat: float);
-/// unit!(RadianInt: int);
+/// unit!(RadianInt("rad"): int);
 /// unit!(RotationUint: uint);
+///
+/// assert_eq!(DegreeFloat::NAME, "DegreeFloat");
+/// assert_eq!(DegreeFloat::SYMBOL, None);
+/// assert_eq!(RadianInt::SYMBOL, Some("rad"));
+///
+/// unit!(WrappedDegree("°"): float mod 360.0);
+///
+/// assert_eq!(WrappedDegree::new(370.0).value(), 10.0);
+/// assert_eq!(WrappedDegree::new(350.0) + WrappedDegree::new(20.0), WrappedDegree::new(10.0));
+/// assert_eq!(WrappedDegree(360.0), WrappedDegree(0.0));
+///
+/// unit!(EncoderTick: uint mod 65536);
+///
+/// assert_eq!(EncoderTick::new(65_540), EncoderTick::new(4));
+/// assert_eq!(EncoderTick::new(4).unwrap_delta(EncoderTick::new(65_530)), 10);
+/// ```
+///
+/// A modulus that isn't finite and greater than zero is a compile error
+///
+/// ```compile_fail
+/// use frclib_core::unit;
+///
+/// unit!(Broken: float mod 0.0);
 /// ```
 #[macro_export]
 macro_rules! unit {
-    ($unit_name:ident $( | $unit_alias:ident)* : float) => {
+    ($unit_name:ident $( ( $symbol:literal ) )? $( | $unit_alias:ident)* : float) => {
         /// A unit of measurement.
         /// This is a newtype wrapper around a [`f64`].
-        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+        #[derive(Clone, Copy, PartialEq, PartialOrd, Default)]
         pub struct $unit_name(pub f64);
 
         $crate::units::macros::paste::paste! {
@@ -34,9 +329,9 @@ macro_rules! unit {
             )*
         }
 
-        impl std::hash::Hash for $unit_name {
-            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-                self.0.to_bits().hash(state);
+        impl core::hash::Hash for $unit_name {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::units::macros::hash_bits_f64(self.0).hash(state);
             }
         }
 
@@ -54,20 +349,193 @@ macro_rules! unit {
             pub const fn value(self) -> f64 {
                 self.0
             }
+
+            /// Consumes the unit and returns the inner [`f64`] value.
+            #[must_use]
+            #[inline]
+            pub const fn into_inner(self) -> f64 {
+                self.0
+            }
+
+            /// Applies `f` to the inner value and wraps the result back into the unit,
+            /// e.g. `reading.map(|v| (v / 0.05).round() * 0.05)` to snap to a sensor's resolution.
+            #[must_use]
+            #[inline]
+            pub fn map(self, f: impl FnOnce(f64) -> f64) -> Self {
+                Self(f(self.0))
+            }
+
+            /// The name of the unit.
+            pub const NAME: &'static str = stringify!($unit_name);
+            /// The symbol of the unit, if one was given.
+            pub const SYMBOL: Option<&'static str> = $crate::unit_symbol!($($symbol)?);
+        }
+
+        impl AsRef<f64> for $unit_name {
+            fn as_ref(&self) -> &f64 {
+                &self.0
+            }
         }
 
         $crate::unit_general!($unit_name : f64);
+        $crate::unit_fmt!($unit_name);
+        $crate::unit_trait!($unit_name : f64);
+        $crate::unit_consts!($unit_name : f64);
+        $crate::unit_try_from!($unit_name : f64);
+        $crate::unit_methods!($unit_name : f64);
         $crate::unit_binops!($unit_name : f64);
         $crate::unit_neg!($unit_name : f64);
         $crate::unit_serde!($unit_name : f64);
+        $crate::unit_arbitrary!($unit_name);
         $crate::unit_num!($unit_name : f64);
-        $crate::unit_float!($unit_name);
+        $crate::unit_float!($unit_name : f64);
+        $crate::unit_float_methods!($unit_name : f64);
         $crate::unit_structure!($unit_name : f64);
     };
-    ($unit_name:ident : int) => {
+    ($unit_name:ident $( ( $symbol:literal ) )? : float mod $modulus:expr) => {
+        /// A unit of measurement that wraps into `[0, MODULUS)`.
+        /// This is a newtype wrapper around a [`f64`].
+        #[derive(Clone, Copy, Default)]
+        pub struct $unit_name(pub f64);
+
+        impl $unit_name {
+            /// The value this unit wraps at, values are kept in `[0, MODULUS)`.
+            pub const MODULUS: f64 = $modulus;
+            /// The additive identity of this unit.
+            pub const ZERO: Self = Self(0.0);
+
+            /// Creates a new instance of the unit with the given value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub fn new(value: f64) -> Self {
+                Self($crate::units::macros::wrap_f64(value, Self::MODULUS))
+            }
+
+            /// Returns the inner [`f64`] value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub fn value(self) -> f64 {
+                $crate::units::macros::wrap_f64(self.0, Self::MODULUS)
+            }
+
+            /// Consumes the unit and returns the inner [`f64`] value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub fn into_inner(self) -> f64 {
+                self.value()
+            }
+
+            /// Applies `f` to the inner value and wraps the result back into the unit.
+            #[must_use]
+            #[inline]
+            pub fn map(self, f: impl FnOnce(f64) -> f64) -> Self {
+                Self::new(f(self.value()))
+            }
+
+            /// The name of the unit.
+            pub const NAME: &'static str = stringify!($unit_name);
+            /// The symbol of the unit, if one was given.
+            pub const SYMBOL: Option<&'static str> = $crate::unit_symbol!($($symbol)?);
+        }
+
+        // a zero, negative or non-finite modulus would wrap every value to `NaN`
+        const _: () = assert!(
+            $unit_name::MODULUS > 0.0 && $unit_name::MODULUS < f64::INFINITY,
+            concat!(
+                "the modulus of `",
+                stringify!($unit_name),
+                "` must be finite and greater than zero"
+            )
+        );
+
+        impl AsRef<f64> for $unit_name {
+            fn as_ref(&self) -> &f64 {
+                &self.0
+            }
+        }
+
+        $crate::unit_modular!($unit_name);
+        $crate::unit_fmt!($unit_name);
+        $crate::unit_serde!($unit_name : f64);
+        $crate::unit_arbitrary!($unit_name);
+        $crate::unit_structure!($unit_name : f64);
+    };
+    ($unit_name:ident $( ( $symbol:literal ) )? : int mod $modulus:expr) => {
+        /// A unit of measurement that wraps into `[0, MODULUS)`.
+        /// This is a newtype wrapper around a [`i64`].
+        #[derive(Clone, Copy, Default)]
+        pub struct $unit_name(pub i64);
+
+        impl $unit_name {
+            /// The value this unit wraps at, values are kept in `[0, MODULUS)`.
+            pub const MODULUS: i64 = $modulus;
+            /// The additive identity of this unit.
+            pub const ZERO: Self = Self(0);
+
+            /// Creates a new instance of the unit with the given value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub const fn new(value: i64) -> Self {
+                Self(value.rem_euclid(Self::MODULUS))
+            }
+
+            /// Returns the inner [`i64`] value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub const fn value(self) -> i64 {
+                self.0.rem_euclid(Self::MODULUS)
+            }
+        }
+
+        // a zero or negative modulus has no values to wrap into
+        const _: () = assert!(
+            $unit_name::MODULUS > 0,
+            concat!("the modulus of `", stringify!($unit_name), "` must be greater than zero")
+        );
+
+        $crate::unit_modular_int!($unit_name $( ( $symbol ) )? : i64);
+        $crate::unit_structure!($unit_name : i64);
+    };
+    ($unit_name:ident $( ( $symbol:literal ) )? : uint mod $modulus:expr) => {
+        /// A unit of measurement that wraps into `[0, MODULUS)`.
+        /// This is a newtype wrapper around a [`u64`].
+        #[derive(Clone, Copy, Default)]
+        pub struct $unit_name(pub u64);
+
+        impl $unit_name {
+            /// The value this unit wraps at, values are kept in `[0, MODULUS)`.
+            pub const MODULUS: u64 = $modulus;
+            /// The additive identity of this unit.
+            pub const ZERO: Self = Self(0);
+
+            /// Creates a new instance of the unit with the given value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub const fn new(value: u64) -> Self {
+                Self(value % Self::MODULUS)
+            }
+
+            /// Returns the inner [`u64`] value wrapped into `[0, MODULUS)`.
+            #[must_use]
+            #[inline]
+            pub const fn value(self) -> u64 {
+                self.0 % Self::MODULUS
+            }
+        }
+
+        // a zero modulus has no values to wrap into
+        const _: () = assert!(
+            $unit_name::MODULUS > 0,
+            concat!("the modulus of `", stringify!($unit_name), "` must be greater than zero")
+        );
+
+        $crate::unit_modular_int!($unit_name $( ( $symbol ) )? : u64);
+        $crate::unit_structure!($unit_name : u64);
+    };
+    ($unit_name:ident $( ( $symbol:literal ) )? : int) => {
         /// A unit of measurement.
         /// This is a newtype wrapper around a [`i64`].
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
         pub struct $unit_name(pub i64);
 
         impl $unit_name {
@@ -84,20 +552,52 @@ macro_rules! unit {
             pub const fn value(self) -> i64 {
                 self.0
             }
+
+            /// Consumes the unit and returns the inner [`i64`] value.
+            #[must_use]
+            #[inline]
+            pub const fn into_inner(self) -> i64 {
+                self.0
+            }
+
+            /// Applies `f` to the inner value and wraps the result back into the unit,
+            /// e.g. `reading.map(|v| (v / 0.05).round() * 0.05)` to snap to a sensor's resolution.
+            #[must_use]
+            #[inline]
+            pub fn map(self, f: impl FnOnce(i64) -> i64) -> Self {
+                Self(f(self.0))
+            }
+
+            /// The name of the unit.
+            pub const NAME: &'static str = stringify!($unit_name);
+            /// The symbol of the unit, if one was given.
+            pub const SYMBOL: Option<&'static str> = $crate::unit_symbol!($($symbol)?);
+        }
+
+        impl AsRef<i64> for $unit_name {
+            fn as_ref(&self) -> &i64 {
+                &self.0
+            }
         }
 
         $crate::unit_general!($unit_name : i64);
+        $crate::unit_fmt!($unit_name);
+        $crate::unit_trait!($unit_name : i64);
+        $crate::unit_consts!($unit_name : i64);
+        $crate::unit_try_from!($unit_name : i64);
+        $crate::unit_methods!($unit_name : i64);
         $crate::unit_binops!($unit_name : i64);
         $crate::unit_neg!($unit_name : i64);
         $crate::unit_serde!($unit_name : i64);
+        $crate::unit_arbitrary!($unit_name);
         $crate::unit_num!($unit_name : i64);
         $crate::unit_integer!($unit_name);
         $crate::unit_structure!($unit_name : i64);
     };
-    ($unit_name:ident : uint) => {
+    ($unit_name:ident $( ( $symbol:literal ) )? : uint) => {
         /// A unit of measurement.
         /// This is a newtype wrapper around a [`u64`].
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
         pub struct $unit_name(pub u64);
 
         impl $unit_name {
@@ -114,20 +614,200 @@ macro_rules! unit {
             pub const fn value(self) -> u64 {
                 self.0
             }
+
+            /// Consumes the unit and returns the inner [`u64`] value.
+            #[must_use]
+            #[inline]
+            pub const fn into_inner(self) -> u64 {
+                self.0
+            }
+
+            /// Applies `f` to the inner value and wraps the result back into the unit,
+            /// e.g. `reading.map(|v| (v / 0.05).round() * 0.05)` to snap to a sensor's resolution.
+            #[must_use]
+            #[inline]
+            pub fn map(self, f: impl FnOnce(u64) -> u64) -> Self {
+                Self(f(self.0))
+            }
+
+            /// The name of the unit.
+            pub const NAME: &'static str = stringify!($unit_name);
+            /// The symbol of the unit, if one was given.
+            pub const SYMBOL: Option<&'static str> = $crate::unit_symbol!($($symbol)?);
+        }
+
+        impl AsRef<u64> for $unit_name {
+            fn as_ref(&self) -> &u64 {
+                &self.0
+            }
         }
 
         $crate::unit_general!($unit_name : u64);
+        $crate::unit_fmt!($unit_name);
+        $crate::unit_trait!($unit_name : u64);
+        $crate::unit_consts!($unit_name : u64);
+        $crate::unit_try_from!($unit_name : u64);
+        $crate::unit_methods!($unit_name : u64);
         $crate::unit_binops!($unit_name : u64);
         $crate::unit_serde!($unit_name : u64);
+        $crate::unit_arbitrary!($unit_name);
         $crate::unit_num!($unit_name : u64);
         $crate::unit_uinteger!($unit_name);
         $crate::unit_structure!($unit_name : u64);
     };
+    ($unit_name:ident $( ( $symbol:literal ) )? : float16) => {
+        /// A unit of measurement.
+        /// This is a newtype wrapper around a half-precision `f16`.
+        #[derive(Clone, Copy, PartialEq, PartialOrd, Default)]
+        pub struct $unit_name(pub $crate::units::macros::half::f16);
+
+        impl core::hash::Hash for $unit_name {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::units::macros::hash_bits_f64(self.0.to_f64()).hash(state);
+            }
+        }
+
+        impl $unit_name {
+            /// Creates a new instance of the unit with the given value.
+            #[must_use]
+            #[inline]
+            pub const fn new(value: $crate::units::macros::half::f16) -> Self {
+                Self(value)
+            }
+
+            /// Returns the inner `f16` value.
+            #[must_use]
+            #[inline]
+            pub const fn value(self) -> $crate::units::macros::half::f16 {
+                self.0
+            }
+
+            /// Consumes the unit and returns the inner `f16` value.
+            #[must_use]
+            #[inline]
+            pub const fn into_inner(self) -> $crate::units::macros::half::f16 {
+                self.0
+            }
+
+            /// Applies `f` to the inner value and wraps the result back into the unit.
+            #[must_use]
+            #[inline]
+            pub fn map(
+                self,
+                f: impl FnOnce($crate::units::macros::half::f16) -> $crate::units::macros::half::f16,
+            ) -> Self {
+                Self(f(self.0))
+            }
+
+            /// Rounds the value of an `f64` backed unit to the nearest `f16`,
+            /// the value is copied as is so both units should measure the same quantity.
+            #[must_use]
+            #[inline]
+            pub fn from_unit<U: $crate::units::Unit<Backing = f64>>(unit: U) -> Self {
+                Self($crate::units::macros::half::f16::from_f64(unit.value()))
+            }
+
+            /// Widens the value into an `f64` backed unit, this is lossless,
+            /// the value is copied as is so both units should measure the same quantity.
+            #[must_use]
+            #[inline]
+            pub fn into_unit<U: $crate::units::Unit<Backing = f64>>(self) -> U {
+                U::new(self.0.to_f64())
+            }
+
+            /// The name of the unit.
+            pub const NAME: &'static str = stringify!($unit_name);
+            /// The symbol of the unit, if one was given.
+            pub const SYMBOL: Option<&'static str> = $crate::unit_symbol!($($symbol)?);
+        }
+
+        impl AsRef<$crate::units::macros::half::f16> for $unit_name {
+            fn as_ref(&self) -> &$crate::units::macros::half::f16 {
+                &self.0
+            }
+        }
+
+        $crate::unit_general!($unit_name : f16);
+        $crate::unit_fmt!($unit_name);
+        $crate::unit_trait!($unit_name : $crate::units::macros::half::f16);
+        $crate::unit_consts!($unit_name : f16);
+        $crate::unit_try_from!($unit_name : f16);
+        $crate::unit_methods!($unit_name : f16);
+        $crate::unit_binops!($unit_name : $crate::units::macros::half::f16);
+        $crate::unit_neg!($unit_name : $crate::units::macros::half::f16);
+        $crate::unit_serde!($unit_name : f16);
+        $crate::unit_arbitrary!($unit_name);
+        $crate::unit_num!($unit_name : $crate::units::macros::half::f16);
+        $crate::unit_float!($unit_name : $crate::units::macros::half::f16);
+        $crate::unit_float_methods!($unit_name : $crate::units::macros::half::f16);
+    };
 }
 
 /// A macro for defining a unit conversion.
 /// This macro is used to define a conversion between two units of the same dimension.
 ///
+/// A conversion is bidirectional so only one of `A <-> B` and `B <-> A` should be defined,
+/// defining both is a conflicting implementation of `UnitConversionPair`.
+/// Converting a unit to itself is a compile error.
+///
+/// Between a `float` and an `int` or `uint` unit the [`From`] impls cast with `as`,
+/// so integers past `2^53` lose their low bits and floats saturate at the integer bounds.
+/// Converting back to the float unit divides the integer by the conversion of `1` in integer math,
+/// only the remainder is divided as a float, and a conversion of `1` that rounds to `0` gives an infinity.
+/// These pairs also get checked `try_from_<unit>` constructors that return a
+/// [`UnitError`](crate::units::UnitError) instead.
+///
+/// Between two integer units, `int` or `uint`, the `saturating` form clamps instead.
+/// Its conversion function takes and returns an `i128`, which holds every `i64` and `u64`
+/// so the conversion itself doesn't overflow, and the result is clamped into the target unit:
+/// - negative values become `0` when converting into a `uint` unit
+/// - values past the largest value of the target become that value, `u64::MAX` or `i64::MAX`
+/// - values past the smallest value of an `int` target become `i64::MIN`
+///
+/// The reverse conversion divides by the conversion of `1` like the other forms, truncating towards zero.
+/// When the conversion coarsens, like microseconds to milliseconds, the conversion of `1` is `0`
+/// and every non-zero value saturates, so pass the inverse function after the conversion function instead.
+/// Comparisons between the two units are done in `i128` without clamping.
+/// ```
+/// use frclib_core::{unit_conversion, unit};
+///
+/// unit!(Millisecond: int);
+/// unit!(Microsecond: uint);
+///
+/// unit_conversion!(Millisecond(int) <-> Microsecond(uint) ~ saturating ms_to_us);
+///
+/// fn ms_to_us(ms: i128) -> i128 {
+///     ms * 1000
+/// }
+///
+/// assert_eq!(Microsecond::from(Millisecond(20)), Microsecond(20_000));
+/// assert_eq!(Microsecond::from(Millisecond(-1)), Microsecond(0));
+/// assert_eq!(Millisecond::from(Microsecond(1_500)), Millisecond(1));
+/// assert_eq!(Millisecond::from(Microsecond(u64::MAX)), Millisecond(18_446_744_073_709_551));
+/// assert!(Millisecond(-1) < Microsecond(0));
+/// ```
+///
+/// ```
+/// use frclib_core::{unit_conversion, unit};
+///
+/// unit!(Microsecond: int);
+/// unit!(Millisecond: uint);
+///
+/// unit_conversion!(Microsecond(int) <-> Millisecond(uint) ~ saturating us_to_ms, ms_to_us);
+///
+/// fn us_to_ms(us: i128) -> i128 {
+///     us / 1000
+/// }
+///
+/// fn ms_to_us(ms: i128) -> i128 {
+///     ms * 1000
+/// }
+///
+/// assert_eq!(Millisecond::from(Microsecond(1_500)), Millisecond(1));
+/// assert_eq!(Microsecond::from(Millisecond(2)), Microsecond(2_000));
+/// assert_eq!(Microsecond::from(Millisecond(u64::MAX)), Microsecond(i64::MAX));
+/// ```
+///
 /// # Example
 /// ```
 /// use frclib_core::{unit_conversion, unit};
@@ -141,57 +821,124 @@ macro_rules! unit {
 ///     degree.to_radians()
 /// }
 /// ````
+///
+/// ```compile_fail
+/// use frclib_core::{unit_conversion, unit};
+///
+/// unit!(Meter: float);
+///
+/// unit_conversion!(Meter(float) <-> Meter(float) ~ |x| x);
+/// ```
+///
+/// ```compile_fail
+/// use frclib_core::{unit_conversion, unit};
+///
+/// unit!(Meter: float);
+/// unit!(Foot: float);
+///
+/// unit_conversion!(Meter(float) <-> Foot(float) ~ ratio 3.28);
+/// unit_conversion!(Foot(float) <-> Meter(float) ~ ratio 0.3048);
+/// ```
 #[macro_export]
 macro_rules! unit_conversion {
     ($unit_a:ident ( $unit_a_type:ident ) <-> $unit_b:ident ( $unit_b_type:ident ) ~ $conv_fn:ident ) => {
-        $crate::inner_unit_conversion!(
-            $unit_a $crate::complex_type_name!($unit_a_type)
-            | $unit_b $crate::complex_type_name!($unit_b_type)
-            : $conv_fn
-        );
+        $crate::unit_conversion_guard!($unit_a | $unit_b {
+            $crate::inner_unit_conversion!(
+                $unit_a $crate::complex_type_name!($unit_a_type)
+                | $unit_b $crate::complex_type_name!($unit_b_type)
+                : $conv_fn
+            );
+            $crate::unit_try_conversion!($unit_a $unit_a_type | $unit_b $unit_b_type : $conv_fn);
+        });
     };
     ($unit_a:ident ( $unit_a_type:ident ) <-> $unit_b:ident ( $unit_b_type:ident ) ~ | $c:ident | $conv_ex:expr ) => {
-        $crate::units::macros::paste::paste! {
-            #[doc(hidden)]
-            #[inline]
-            fn [< $unit_a:lower _to_ $unit_b:lower >]
-                (inner_value: $crate::complex_type_name!($unit_a_type))
-                -> $crate::complex_type_name!($unit_b_type)
-            {
-                #[allow(clippy::redundant_closure_call)]
-                (| $c : $crate::complex_type_name!($unit_a_type) | $conv_ex)(inner_value)
+        $crate::unit_conversion_guard!($unit_a | $unit_b {
+            $crate::units::macros::paste::paste! {
+                #[doc(hidden)]
+                #[inline]
+                fn [< $unit_a:lower _to_ $unit_b:lower >]
+                    (inner_value: $crate::complex_type_name!($unit_a_type))
+                    -> $crate::complex_type_name!($unit_b_type)
+                {
+                    #[allow(clippy::redundant_closure_call)]
+                    (| $c : $crate::complex_type_name!($unit_a_type) | $conv_ex)(inner_value)
+                }
+                $crate::inner_unit_conversion!(
+                    $unit_a $crate::complex_type_name!($unit_a_type)
+                    | $unit_b $crate::complex_type_name!($unit_b_type)
+                    : [< $unit_a:lower _to_ $unit_b:lower >]
+                );
+                $crate::unit_try_conversion!(
+                    $unit_a $unit_a_type | $unit_b $unit_b_type : [< $unit_a:lower _to_ $unit_b:lower >]
+                );
+            }
+        });
+    };
+    ($unit_a:ident ( $unit_a_type:ident ) <-> $unit_b:ident ( $unit_b_type:ident ) ~ saturating $conv_fn:ident ) => {
+        $crate::unit_conversion_guard!($unit_a | $unit_b {
+            $crate::units::macros::paste::paste! {
+                #[doc(hidden)]
+                #[inline]
+                fn [< $unit_b:lower _to_ $unit_a:lower >](inner_value: i128) -> i128 {
+                    $crate::units::macros::saturating_ratio_div(inner_value, $conv_fn(1))
+                }
+                $crate::inner_saturating_conversion!(
+                    $unit_a $crate::complex_type_name!($unit_a_type)
+                    | $unit_b $crate::complex_type_name!($unit_b_type)
+                    : $conv_fn, [< $unit_b:lower _to_ $unit_a:lower >]
+                );
             }
-            $crate::inner_unit_conversion!(
+        });
+    };
+    ($unit_a:ident ( $unit_a_type:ident ) <-> $unit_b:ident ( $unit_b_type:ident ) ~ saturating $conv_fn:ident , $inverse_fn:ident ) => {
+        $crate::unit_conversion_guard!($unit_a | $unit_b {
+            $crate::inner_saturating_conversion!(
                 $unit_a $crate::complex_type_name!($unit_a_type)
                 | $unit_b $crate::complex_type_name!($unit_b_type)
-                : [< $unit_a:lower _to_ $unit_b:lower >]
+                : $conv_fn, $inverse_fn
             );
-        }
+        });
     };
     ($unit_a:ident ( $unit_a_type:ident ) <-> $unit_b:ident ( $unit_b_type:ident ) ~ ratio $conv_ex:expr ) => {
-        $crate::units::macros::paste::paste! {
-            #[doc(hidden)]
-            #[inline]
-            fn [< $unit_a:lower _to_ $unit_b:lower >]
-                (inner_value: $crate::complex_type_name!($unit_a_type))
-                -> $crate::complex_type_name!($unit_b_type)
-            {
-                inner_value * $conv_ex
+        $crate::unit_conversion_guard!($unit_a | $unit_b {
+            $crate::units::macros::paste::paste! {
+                #[doc(hidden)]
+                #[inline]
+                fn [< $unit_a:lower _to_ $unit_b:lower >]
+                    (inner_value: $crate::complex_type_name!($unit_a_type))
+                    -> $crate::complex_type_name!($unit_b_type)
+                {
+                    inner_value * $conv_ex
+                }
+                $crate::inner_unit_conversion!(
+                    $unit_a $crate::complex_type_name!($unit_a_type)
+                    | $unit_b $crate::complex_type_name!($unit_b_type)
+                    : [< $unit_a:lower _to_ $unit_b:lower >]
+                );
+                $crate::unit_try_conversion!(
+                    $unit_a $unit_a_type | $unit_b $unit_b_type : [< $unit_a:lower _to_ $unit_b:lower >]
+                );
             }
-            $crate::inner_unit_conversion!(
-                $unit_a $crate::complex_type_name!($unit_a_type)
-                | $unit_b $crate::complex_type_name!($unit_b_type)
-                : [< $unit_a:lower _to_ $unit_b:lower >]
-            );
-        }
+        });
     };
 }
 
 /// A macro for defining a unit family.
+///
 /// Unit families allow all units to fall under a single trait.
 /// This allows for easy conversion between units of the same family
 /// and allows for functions to be generic over all units of a family.
 ///
+/// The standard unit gets a `parse_any` that parses a value followed by the symbol of any unit in the family,
+/// e.g. `"12 in"`, and converts it to the standard unit.
+/// Units without a symbol are parsed by their name.
+///
+/// A family whose standard unit is a float can be declared `periodic` with its period in the standard unit,
+/// this adds `normalize_signed` and `normalize_unsigned` for wrapping values into a single period.
+///
+/// A family can instead be declared with `bounds(min = .., max = ..)` in the standard unit,
+/// this adds `clamp_to_bounds` which clamps any member into that range.
+///
 /// # Example
 /// ```
 /// use frclib_core::{unit_family, unit, unit_conversion};
@@ -204,7 +951,10 @@ macro_rules! unit_conversion {
 /// unit_conversion!(Degree(float) <-> Rotation(float) ~ degree_to_rotation);
 /// unit_conversion!(Radian(float) <-> Rotation(float) ~ radian_to_rotation);
 ///
-/// unit_family!(Angle(Radian): Degree, Rotation);
+/// unit_family!(Angle(Radian) periodic(std::f64::consts::TAU): Degree, Rotation);
+///
+/// assert_eq!(Degree::new(180.0).normalize_signed(), Degree::new(-180.0));
+/// assert_eq!(Degree::new(-90.0).normalize_unsigned(), Degree::new(270.0));
 ///
 /// fn degree_to_radian(degree: f64) -> f64 {
 ///     degree.to_radians()
@@ -218,9 +968,78 @@ macro_rules! unit_conversion {
 ///     degree_to_rotation(radian.to_degrees())
 /// }
 /// ````
+///
+/// ```
+/// use frclib_core::{unit_family, unit, unit_conversion};
+///
+/// unit!(ServoDegree: float);
+/// unit!(ServoRadian: float);
+///
+/// unit_conversion!(ServoDegree(float) <-> ServoRadian(float) ~ |x| x.to_radians());
+///
+/// unit_family!(ServoAngle(ServoRadian) bounds(min = 0.0, max = std::f64::consts::PI): ServoDegree);
+///
+/// assert_eq!(ServoDegree::new(200.0).clamp_to_bounds(), ServoDegree::new(180.0));
+/// assert_eq!(ServoDegree::new(-10.0).clamp_to_bounds(), ServoDegree::new(0.0));
+/// assert_eq!(ServoDegree::new(45.0).clamp_to_bounds(), ServoDegree::new(45.0));
+/// ```
 #[macro_export]
 macro_rules! unit_family {
     ($family_name:ident ( $standard:ident ): $($unit_name:ident),*) => {
+        $crate::unit_family!(@impl $family_name($standard): $($unit_name),* {});
+    };
+    ($family_name:ident ( $standard:ident ) periodic($period:expr): $($unit_name:ident),*) => {
+        $crate::unit_family!(@impl $family_name($standard): $($unit_name),* {
+            #[doc = "Wraps this unit into the half-open range `[-period / 2, period / 2)`."]
+            #[doc = ""]
+            #[doc = "The lower endpoint is inclusive, so for angles `180°` maps to `-180°`."]
+            #[must_use]
+            #[inline]
+            fn normalize_signed(self) -> Self {
+                const PERIOD: f64 = $period;
+                let half = PERIOD / 2.0;
+                let wrapped =
+                    num::traits::Euclid::rem_euclid(&(self.standard().value() + half), &PERIOD);
+                // `rem_euclid` can round up to `PERIOD` for tiny negative inputs
+                let wrapped = if wrapped >= PERIOD { 0.0 } else { wrapped };
+                Self::from($standard::new(wrapped - half))
+            }
+
+            #[doc = "Wraps this unit into the half-open range `[0, period)`."]
+            #[doc = ""]
+            #[doc = "The lower endpoint is inclusive, so for angles `360°` maps to `0°`."]
+            #[must_use]
+            #[inline]
+            fn normalize_unsigned(self) -> Self {
+                const PERIOD: f64 = $period;
+                let wrapped = num::traits::Euclid::rem_euclid(&self.standard().value(), &PERIOD);
+                // `rem_euclid` can round up to `PERIOD` for tiny negative inputs
+                let wrapped = if wrapped >= PERIOD { 0.0 } else { wrapped };
+                Self::from($standard::new(wrapped))
+            }
+        });
+    };
+    ($family_name:ident ( $standard:ident ) bounds(min = $min:expr, max = $max:expr): $($unit_name:ident),*) => {
+        $crate::unit_family!(@impl $family_name($standard): $($unit_name),* {
+            #[doc = "Clamps this unit into the bounds of the family, converting through the standard unit."]
+            #[doc = ""]
+            #[doc = "Values outside the bounds become the nearest bound, `NaN` is returned as is."]
+            #[must_use]
+            #[inline]
+            fn clamp_to_bounds(self) -> Self {
+                let (min, max) = ($standard::new($min), $standard::new($max));
+                let standard = self.standard();
+                if standard < min {
+                    Self::from(min)
+                } else if standard > max {
+                    Self::from(max)
+                } else {
+                    self
+                }
+            }
+        });
+    };
+    (@impl $family_name:ident ( $standard:ident ): $($unit_name:ident),* { $($periodic:tt)* }) => {
         $crate::units::macros::paste::paste! {
             #[doc = "A family of units representing an `" $standard "` measurement."]
             #[doc = ""]
@@ -234,9 +1053,42 @@ macro_rules! unit_family {
                     self.into()
                 }
 
-                fn conv<U: $family_name>(self) -> U {
+                #[doc = "Converts this unit to any other unit of the family through the standard unit,"]
+                #[doc = "e.g. `value.convert::<" $standard ">()`."]
+                #[inline]
+                fn convert<U: $family_name>(self) -> U {
                     U::from(self.standard())
                 }
+
+                #[doc = "Converts this unit to `U` and returns the raw value,"]
+                #[doc = "the value has the backing type of `U` so integer units return an integer."]
+                #[inline]
+                fn value_in<U: $family_name + $crate::units::Unit>(self) -> U::Backing {
+                    $crate::units::Unit::value(self.convert::<U>())
+                }
+
+                #[doc = "Shorthand for [`convert`](" $family_name "::convert)."]
+                #[inline]
+                fn conv<U: $family_name>(self) -> U {
+                    self.convert()
+                }
+
+                #[doc = "Compares this unit with any other unit of the family in the standard unit."]
+                #[doc = ""]
+                #[doc = "`NaN` values are ordered as defined by [`f64::total_cmp`]."]
+                #[inline]
+                fn compare<U: $family_name>(self, other: U) -> core::cmp::Ordering {
+                    self.standard().total_cmp(&other.standard())
+                }
+
+                #[doc = "Returns true if this unit and any other unit of the family"]
+                #[doc = "are within `epsilon` of each other in the standard unit."]
+                #[inline]
+                fn approx_eq<U: $family_name>(self, other: U, epsilon: $standard) -> bool {
+                    self.standard().abs_diff(other.standard()) <= epsilon
+                }
+
+                $($periodic)*
             }
         }
 
@@ -245,6 +1097,273 @@ macro_rules! unit_family {
             T: Into<$standard> + From<$standard> + Copy,
         {
         }
+
+        $crate::unit_family!(@parse $family_name($standard): $($unit_name),*);
+    };
+    (@parse $family_name:ident ( $standard:ident ): $($unit_name:ident),*) => {
+        impl $standard {
+            #[doc = concat!(
+                "Parses a value followed by the symbol of any unit in the [`", stringify!($family_name),
+                "`] family, e.g. `\"12 in\"`, and converts it to [`", stringify!($standard), "`]."
+            )]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = "Returns an error listing the accepted symbols if the text doesn't end with one of them,"]
+            #[doc = "or an error if the value before the symbol can't be parsed."]
+            pub fn parse_any(text: &str) -> Result<Self, $crate::units::ParseUnitError> {
+                const ACCEPTED: &[&str] = &[
+                    $crate::units::macros::symbol_or_name($standard::SYMBOL, $standard::NAME),
+                    $($crate::units::macros::symbol_or_name($unit_name::SYMBOL, $unit_name::NAME)),*
+                ];
+                let mut error = $crate::units::ParseUnitError::UnknownSymbol(ACCEPTED);
+                // a symbol can end with another one, e.g. `cm` and `m`,
+                // so every symbol that matches is tried until a value parses
+                $crate::unit_family!(@parse_unit text, error, $standard);
+                $($crate::unit_family!(@parse_unit text, error, $unit_name);)*
+                Err(error)
+            }
+        }
+    };
+    (@parse_unit $text:ident, $error:ident, $unit_name:ident) => {
+        let symbol = $crate::units::macros::symbol_or_name($unit_name::SYMBOL, $unit_name::NAME);
+        if let Some(value) = $crate::units::macros::strip_symbol($text, symbol) {
+            match <$unit_name as num::Num>::from_str_radix(value, 10) {
+                Ok(value) => return Ok(Self::from(value)),
+                Err(_) => $error = $crate::units::ParseUnitError::InvalidNumber($unit_name::NAME),
+            }
+        }
+    };
+}
+
+/// A macro for converting a time unit to and from [`std::time::Duration`].
+///
+/// `factor_to_nanos` is how many nanoseconds one of the unit is.
+/// This generates `From<Duration>` for the unit and `TryFrom<unit>` for [`Duration`](std::time::Duration),
+/// which fails on negative or non-finite values as [`Duration`](std::time::Duration) is unsigned.
+///
+/// Adding `, saturating` generates `From<unit>` for [`Duration`](std::time::Duration) instead,
+/// negative and `NaN` values become zero and values too large become [`Duration::MAX`](std::time::Duration::MAX).
+/// The time units of this crate use it.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use frclib_core::{unit, unit_time};
+///
+/// unit!(Second: float);
+/// unit!(Millisecond: int);
+///
+/// unit_time!(Second, factor_to_nanos = 1_000_000_000);
+/// unit_time!(Millisecond, factor_to_nanos = 1_000_000);
+///
+/// assert_eq!(Duration::try_from(Millisecond(20)), Ok(Duration::from_millis(20)));
+/// assert!(Duration::try_from(Second(-1.0)).is_err());
+/// assert_eq!(Second::from(Duration::from_millis(1500)), Second(1.5));
+///
+/// unit!(Minute: float);
+/// unit_time!(Minute, factor_to_nanos = 60_000_000_000, saturating);
+///
+/// assert_eq!(Duration::from(Minute(0.5)), Duration::from_secs(30));
+/// assert_eq!(Duration::from(Minute(-1.0)), Duration::ZERO);
+/// ```
+#[macro_export]
+macro_rules! unit_time {
+    ($unit_name:ident, factor_to_nanos = $factor:literal, saturating) => {
+        $crate::unit_time!(@from_duration $unit_name, $factor);
+
+        impl From<$unit_name> for core::time::Duration {
+            fn from(value: $unit_name) -> Self {
+                $crate::units::macros::duration::DurationBacking::saturating_to_duration(
+                    value.0, $factor,
+                )
+            }
+        }
+    };
+    ($unit_name:ident, factor_to_nanos = $factor:literal) => {
+        $crate::unit_time!(@from_duration $unit_name, $factor);
+
+        impl core::convert::TryFrom<$unit_name> for core::time::Duration {
+            type Error = $crate::units::UnitError;
+            fn try_from(value: $unit_name) -> Result<Self, Self::Error> {
+                $crate::units::macros::duration::DurationBacking::to_duration(
+                    value.0,
+                    $factor,
+                    stringify!($unit_name),
+                )
+            }
+        }
+    };
+    (@from_duration $unit_name:ident, $factor:literal) => {
+        impl From<core::time::Duration> for $unit_name {
+            fn from(duration: core::time::Duration) -> Self {
+                Self(
+                    $crate::units::macros::duration::DurationBacking::from_duration(
+                        duration, $factor,
+                    ),
+                )
+            }
+        }
+    };
+}
+
+/// A macro for negating an unsigned unit into a signed sibling unit.
+///
+/// `uint` units don't implement [`Neg`](core::ops::Neg) as most of them can't be negative,
+/// this opts a unit in by promoting it to an `int` unit of the same scale,
+/// e.g. a backwards time delta.
+///
+/// Negation saturates at `i64::MIN` for values past `i64::MAX + 1`,
+/// `checked_neg_signed` returns `None` for them instead.
+/// The signed unit has to be defined separately and has no conversions to the unsigned one.
+///
+/// # Example
+/// ```
+/// use frclib_core::{unit, unit_signed_neg};
+///
+/// unit!(Tick: uint);
+/// unit!(SignedTick: int);
+///
+/// unit_signed_neg!(Tick -> SignedTick);
+///
+/// assert_eq!(-Tick(5), SignedTick(-5));
+/// assert_eq!(Tick(5).checked_neg_signed(), Some(SignedTick(-5)));
+/// assert_eq!(Tick(u64::MAX).checked_neg_signed(), None);
+/// assert_eq!(-Tick(u64::MAX), SignedTick(i64::MIN));
+/// ```
+#[macro_export]
+macro_rules! unit_signed_neg {
+    ($unit_name:ident -> $signed:ident) => {
+        impl core::ops::Neg for $unit_name {
+            type Output = $signed;
+            #[inline]
+            fn neg(self) -> Self::Output {
+                self.checked_neg_signed().unwrap_or($signed(i64::MIN))
+            }
+        }
+
+        impl $unit_name {
+            #[doc = concat!("Negates this unit into a [`", stringify!($signed), "`],")]
+            #[doc = "returning `None` if the negated value doesn't fit in an `i64`."]
+            #[must_use]
+            #[inline]
+            pub fn checked_neg_signed(self) -> Option<$signed> {
+                0i64.checked_sub_unsigned(self.0).map($signed)
+            }
+        }
+    };
+}
+
+/// A macro for converting arrays of raw values to and from arrays of a unit.
+///
+/// This generates `from_array` and `to_array` on the unit for any array length,
+/// e.g. for the components of a 3D point at an FFI boundary.
+/// It's opt-in so only units that are grouped like this carry the methods,
+/// the built-in length units have them.
+///
+/// # Example
+/// ```
+/// use frclib_core::{unit, unit_array};
+///
+/// unit!(Meter: float);
+///
+/// unit_array!(Meter);
+///
+/// let point = Meter::from_array([1.0, -2.0, 0.5]);
+/// assert_eq!(point, [Meter(1.0), Meter(-2.0), Meter(0.5)]);
+/// assert_eq!(Meter::to_array(point), [1.0, -2.0, 0.5]);
+/// ```
+#[macro_export]
+macro_rules! unit_array {
+    ($unit_name:ident) => {
+        impl $unit_name {
+            #[doc = concat!("Wraps every value of an array in [`", stringify!($unit_name), "`].")]
+            #[must_use]
+            #[inline]
+            pub fn from_array<const N: usize>(
+                values: [<Self as $crate::units::Unit>::Backing; N],
+            ) -> [Self; N] {
+                values.map(Self)
+            }
+
+            #[doc = concat!("Unwraps every [`", stringify!($unit_name), "`] of an array into its value.")]
+            #[must_use]
+            #[inline]
+            pub fn to_array<const N: usize>(
+                units: [Self; N],
+            ) -> [<Self as $crate::units::Unit>::Backing; N] {
+                units.map(|unit| unit.0)
+            }
+        }
+    };
+}
+
+/// A macro for adding trigonometric methods to an angle unit.
+///
+/// The trig methods return plain [`f64`] ratios and always operate on radians,
+/// units that are not radians must name a radian unit they can be converted into.
+///
+/// # Example
+/// ```
+/// use frclib_core::{unit, unit_angle, unit_conversion};
+///
+/// unit!(Radian: float);
+/// unit!(Degree: float);
+///
+/// unit_conversion!(Degree(float) <-> Radian(float) ~ |x| x.to_radians());
+///
+/// unit_angle!(Radian);
+/// unit_angle!(Degree as Radian);
+///
+/// assert!((Degree(90.0).sin() - 1.0).abs() < 1e-12);
+/// assert!((Degree::atan2(1.0, 1.0).0 - 45.0).abs() < 1e-12);
+/// ```
+#[macro_export]
+macro_rules! unit_angle {
+    ($unit_name:ident) => {
+        $crate::unit_angle!(@impl $unit_name, |angle: $unit_name| angle.0, |radians: f64| $unit_name(radians));
+    };
+    ($unit_name:ident as $radian:ident) => {
+        $crate::unit_angle!(
+            @impl $unit_name,
+            |angle: $unit_name| $radian::from(angle).0,
+            |radians: f64| $unit_name::from($radian(radians))
+        );
+    };
+    (@impl $unit_name:ident, $to_radians:expr, $from_radians:expr) => {
+        impl $unit_name {
+            /// Computes the sine of this angle.
+            #[must_use]
+            #[inline]
+            pub fn sin(self) -> f64 {
+                #[allow(clippy::redundant_closure_call)]
+                num::traits::Float::sin(($to_radians)(self))
+            }
+
+            /// Computes the cosine of this angle.
+            #[must_use]
+            #[inline]
+            pub fn cos(self) -> f64 {
+                #[allow(clippy::redundant_closure_call)]
+                num::traits::Float::cos(($to_radians)(self))
+            }
+
+            /// Computes the tangent of this angle.
+            #[must_use]
+            #[inline]
+            pub fn tan(self) -> f64 {
+                #[allow(clippy::redundant_closure_call)]
+                num::traits::Float::tan(($to_radians)(self))
+            }
+
+            /// Computes the angle of the point `(x, y)` from the positive x axis,
+            /// both components must be in the same unit.
+            #[must_use]
+            #[inline]
+            pub fn atan2<L: Into<f64>>(y: L, x: L) -> Self {
+                #[allow(clippy::redundant_closure_call)]
+                ($from_radians)(num::traits::Float::atan2(y.into(), x.into()))
+            }
+        }
     };
 }
 
@@ -261,59 +1380,108 @@ macro_rules! unit_family {
 /// unit_dim_analysis!(DegreePerSecond * Second = Degree);
 /// // also supports division but mult implicitly adds support for division the other way
 /// ```
+///
+/// Reciprocals of float units are declared with `1 / unit`.
+/// Dividing a scalar by a unit already returns the same unit,
+/// so this generates `dim_recip` on both units along with multiplying them together into a scalar.
+/// A unit can only have one reciprocal.
+/// ```
+/// use frclib_core::{unit_dim_analysis, unit};
+///
+/// unit!(Second: float);
+/// unit!(Hertz: float);
+///
+/// unit_dim_analysis!(1 / Second = Hertz);
+///
+/// assert_eq!(Second(0.5).dim_recip(), Hertz(2.0));
+/// assert_eq!(Hertz(4.0).dim_recip(), Second(0.25));
+/// assert_eq!(Hertz(4.0) * Second(0.5), 2.0);
+/// assert_eq!(1.0 / Second(0.5), Second(2.0));
+/// ```
+///
+/// The product of a unit with itself is declared with `unit ^ 2`.
+/// Units implement [`num::Num`] so `unit * unit` always returns the same unit,
+/// instead this generates `squared` and `dim_mul` methods along with the division back into the unit.
+/// ```
+/// use frclib_core::{unit_dim_analysis, unit};
+///
+/// unit!(Meter: float);
+/// unit!(SquareMeter: float);
+///
+/// unit_dim_analysis!(Meter ^ 2 = SquareMeter);
+///
+/// assert_eq!(Meter(3.0).dim_mul(Meter(4.0)), SquareMeter(12.0));
+/// assert_eq!(Meter(3.0).squared(), SquareMeter(9.0));
+/// assert_eq!(SquareMeter(12.0) / Meter(4.0), Meter(3.0));
+/// ```
+///
+/// `square: unit = squared` also generates `dim_sqrt` on the squared unit to take the root back into the unit.
+/// Every float unit already has a `sqrt` that stays in the same unit, `dim_sqrt` is the dimensionally correct one
+/// and only exists on the squared unit.
+/// ```
+/// use frclib_core::{unit_dim_analysis, unit};
+///
+/// unit!(Meter: float);
+/// unit!(SquareMeter: float);
+///
+/// unit_dim_analysis!(square: Meter = SquareMeter);
+///
+/// assert_eq!((Meter(3.0).squared() + Meter(4.0).squared()).dim_sqrt(), Meter(5.0));
+/// assert_eq!(Meter(2.0).dim_mul(Meter(8.0)).dim_sqrt(), Meter(4.0));
+/// ```
 #[macro_export]
 macro_rules! unit_dim_analysis {
     ($unit_a:ident * $unit_b:ident = $ret:ident) => {
-        impl std::ops::Mul<$unit_b> for $unit_a {
+        impl core::ops::Mul<$unit_b> for $unit_a {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: $unit_b) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<$unit_a> for $unit_b {
+        impl core::ops::Mul<$unit_a> for $unit_b {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: $unit_a) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<&$unit_b> for $unit_a {
+        impl core::ops::Mul<&$unit_b> for $unit_a {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: &$unit_b) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<$unit_a> for &$unit_b {
+        impl core::ops::Mul<$unit_a> for &$unit_b {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: $unit_a) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<$unit_b> for &$unit_a {
+        impl core::ops::Mul<$unit_b> for &$unit_a {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: $unit_b) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<&$unit_a> for $unit_b {
+        impl core::ops::Mul<&$unit_a> for $unit_b {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: &$unit_a) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<&$unit_b> for &$unit_a {
+        impl core::ops::Mul<&$unit_b> for &$unit_a {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: &$unit_b) -> Self::Output {
                 $ret::from(self.0 * rhs.0)
             }
         }
-        impl std::ops::Mul<&$unit_a> for &$unit_b {
+        impl core::ops::Mul<&$unit_a> for &$unit_b {
             type Output = $ret;
             #[inline]
             fn mul(self, rhs: &$unit_a) -> Self::Output {
@@ -322,56 +1490,56 @@ macro_rules! unit_dim_analysis {
         }
 
         //other order
-        impl std::ops::Div<$unit_a> for $ret {
+        impl core::ops::Div<$unit_a> for $ret {
             type Output = $unit_b;
             #[inline]
             fn div(self, rhs: $unit_a) -> Self::Output {
                 $unit_b::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<$unit_b> for $ret {
+        impl core::ops::Div<$unit_b> for $ret {
             type Output = $unit_a;
             #[inline]
             fn div(self, rhs: $unit_b) -> Self::Output {
                 $unit_a::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<&$unit_a> for $ret {
+        impl core::ops::Div<&$unit_a> for $ret {
             type Output = $unit_b;
             #[inline]
             fn div(self, rhs: &$unit_a) -> Self::Output {
                 $unit_b::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<$unit_a> for &$ret {
+        impl core::ops::Div<$unit_a> for &$ret {
             type Output = $unit_b;
             #[inline]
             fn div(self, rhs: $unit_a) -> Self::Output {
                 $unit_b::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<&$unit_a> for &$ret {
+        impl core::ops::Div<&$unit_a> for &$ret {
             type Output = $unit_b;
             #[inline]
             fn div(self, rhs: &$unit_a) -> Self::Output {
                 $unit_b::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<&$unit_b> for $ret {
+        impl core::ops::Div<&$unit_b> for $ret {
             type Output = $unit_a;
             #[inline]
             fn div(self, rhs: &$unit_b) -> Self::Output {
                 $unit_a::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<$unit_b> for &$ret {
+        impl core::ops::Div<$unit_b> for &$ret {
             type Output = $unit_a;
             #[inline]
             fn div(self, rhs: $unit_b) -> Self::Output {
                 $unit_a::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<&$unit_b> for &$ret {
+        impl core::ops::Div<&$unit_b> for &$ret {
             type Output = $unit_a;
             #[inline]
             fn div(self, rhs: &$unit_b) -> Self::Output {
@@ -379,26 +1547,132 @@ macro_rules! unit_dim_analysis {
             }
         }
     };
+    ($unit:ident ^ 2 = $ret:ident) => {
+        impl $unit {
+            #[doc = concat!("Multiplies `self` by itself into [`", stringify!($ret), "`].")]
+            #[must_use]
+            #[inline]
+            pub fn squared(self) -> $ret {
+                $ret::from(self.0 * self.0)
+            }
+
+            #[doc = concat!("Multiplies `self` by `rhs` into [`", stringify!($ret), "`].")]
+            #[must_use]
+            #[inline]
+            pub fn dim_mul(self, rhs: Self) -> $ret {
+                $ret::from(self.0 * rhs.0)
+            }
+        }
+
+        impl core::ops::Div<$unit> for $ret {
+            type Output = $unit;
+            #[inline]
+            fn div(self, rhs: $unit) -> Self::Output {
+                $unit::from(self.0 / rhs.0)
+            }
+        }
+        impl core::ops::Div<&$unit> for $ret {
+            type Output = $unit;
+            #[inline]
+            fn div(self, rhs: &$unit) -> Self::Output {
+                $unit::from(self.0 / rhs.0)
+            }
+        }
+        impl core::ops::Div<$unit> for &$ret {
+            type Output = $unit;
+            #[inline]
+            fn div(self, rhs: $unit) -> Self::Output {
+                $unit::from(self.0 / rhs.0)
+            }
+        }
+        impl core::ops::Div<&$unit> for &$ret {
+            type Output = $unit;
+            #[inline]
+            fn div(self, rhs: &$unit) -> Self::Output {
+                $unit::from(self.0 / rhs.0)
+            }
+        }
+    };
+    (square: $unit:ident = $ret:ident) => {
+        $crate::unit_dim_analysis!($unit ^ 2 = $ret);
+
+        impl $ret {
+            #[doc = concat!("Takes the square root of `self` into [`", stringify!($unit), "`].")]
+            #[must_use]
+            #[inline]
+            pub fn dim_sqrt(self) -> $unit {
+                $unit::from(self.sqrt().0)
+            }
+        }
+    };
+    (1 / $unit:ident = $ret:ident) => {
+        impl $unit {
+            #[doc = concat!("Takes the reciprocal of `self` into [`", stringify!($ret), "`].")]
+            #[must_use]
+            #[inline]
+            pub fn dim_recip(self) -> $ret {
+                $ret::from(1.0 / self.0)
+            }
+        }
+        impl $ret {
+            #[doc = concat!("Takes the reciprocal of `self` into [`", stringify!($unit), "`].")]
+            #[must_use]
+            #[inline]
+            pub fn dim_recip(self) -> $unit {
+                $unit::from(1.0 / self.0)
+            }
+        }
+
+        //other order
+        impl core::ops::Mul<$unit> for $ret {
+            type Output = f64;
+            #[inline]
+            fn mul(self, rhs: $unit) -> Self::Output {
+                self.0 * rhs.0
+            }
+        }
+        impl core::ops::Mul<&$unit> for $ret {
+            type Output = f64;
+            #[inline]
+            fn mul(self, rhs: &$unit) -> Self::Output {
+                self.0 * rhs.0
+            }
+        }
+        impl core::ops::Mul<$ret> for $unit {
+            type Output = f64;
+            #[inline]
+            fn mul(self, rhs: $ret) -> Self::Output {
+                self.0 * rhs.0
+            }
+        }
+        impl core::ops::Mul<&$ret> for $unit {
+            type Output = f64;
+            #[inline]
+            fn mul(self, rhs: &$ret) -> Self::Output {
+                self.0 * rhs.0
+            }
+        }
+    };
     ($unit_a:ident / $unit_b:ident = $ret:ident) => {
-        impl std::ops::Div<$unit_b> for $unit_a {
+        impl core::ops::Div<$unit_b> for $unit_a {
             type Output = $ret;
             fn div(self, rhs: $unit_b) -> Self::Output {
                 $ret::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<&$unit_b> for $unit_a {
+        impl core::ops::Div<&$unit_b> for $unit_a {
             type Output = $ret;
             fn div(self, rhs: &$unit_b) -> Self::Output {
                 $ret::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<$unit_b> for &$unit_a {
+        impl core::ops::Div<$unit_b> for &$unit_a {
             type Output = $ret;
             fn div(self, rhs: $unit_b) -> Self::Output {
                 $ret::from(self.0 / rhs.0)
             }
         }
-        impl std::ops::Div<&$unit_b> for &$unit_a {
+        impl core::ops::Div<&$unit_b> for &$unit_a {
             type Output = $ret;
             fn div(self, rhs: &$unit_b) -> Self::Output {
                 $ret::from(self.0 / rhs.0)
@@ -427,4 +1701,31 @@ mod test {
         let new_micro = 1u64 + micro;
         assert_eq!(new_micro, Microsecond(2));
     }
+
+    #[test]
+    fn consts() {
+        const SENTINELS: [Degree; 2] = [Degree::ZERO, Degree::MAX];
+        assert_eq!(SENTINELS[0], Degree(0.0));
+        assert_eq!(SENTINELS[1], Degree(f64::MAX));
+        assert_eq!(Degree::ONE, Degree(1.0));
+        assert!(Degree::NAN.0.is_nan());
+        assert!(Degree::INFINITY > Degree::MAX);
+
+        assert_eq!(Millisecond::MIN, Millisecond(i64::MIN));
+        assert_eq!(Millisecond::ZERO + Millisecond::ONE, Millisecond(1));
+        assert_eq!(Microsecond::MAX, Microsecond(u64::MAX));
+        assert_eq!(Microsecond::MIN, Microsecond::ZERO);
+    }
+
+    #[test]
+    fn abs_diff() {
+        assert_eq!(Degree(1.0).abs_diff(Degree(-2.0)), Degree(3.0));
+        assert_eq!(Degree(-2.0).abs_diff(Degree(1.0)), Degree(3.0));
+        assert_eq!(Millisecond(-5).abs_diff(Millisecond(5)), Millisecond(10));
+        assert_eq!(
+            Millisecond::MIN.abs_diff(Millisecond::MAX),
+            Millisecond::MAX
+        );
+        assert_eq!(Microsecond(3).abs_diff(Microsecond(10)), Microsecond(7));
+    }
 }
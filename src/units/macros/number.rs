@@ -68,86 +68,255 @@ macro_rules! forward_into_args {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! unit_float {
-    ($unit_name:ident) => {
+    ($unit_name:ident : $type:ty) => {
         impl num::traits::NumCast for $unit_name {
             fn from<T: num::traits::ToPrimitive>(n: T) -> Option<Self> {
-                n.to_f64().map(Self)
+                <$type as num::traits::NumCast>::from(n).map(Self)
             }
         }
         impl num::traits::Float for $unit_name {
             #[allow(deprecated)]
             fn abs_sub(self, other: Self) -> Self {
-                Self(self.0.abs_sub(other.0))
+                Self(num::traits::Float::abs_sub(self.0, other.0))
             }
             fn sin_cos(self) -> (Self, Self) {
-                let (sin, cos) = self.0.sin_cos();
+                let (sin, cos) = num::traits::Float::sin_cos(self.0);
                 (Self(sin), Self(cos))
             }
             fn powi(self, n: i32) -> Self {
-                Self(self.0.powi(n))
+                Self(num::traits::Float::powi(self.0, n))
             }
             $crate::forward_into! {
-                f64::floor as floor(self) -> Self;
-                f64::ceil as ceil(self) -> Self;
-                f64::round as round(self) -> Self;
-                f64::trunc as trunc(self) -> Self;
-                f64::abs as abs(self) -> Self;
-                f64::sqrt as sqrt(self) -> Self;
-                f64::exp as exp(self) -> Self;
-                f64::exp2 as exp2(self) -> Self;
-                f64::ln as ln(self) -> Self;
-                f64::log2 as log2(self) -> Self;
-                f64::log10 as log10(self) -> Self;
-                f64::cbrt as cbrt(self) -> Self;
-                f64::sin as sin(self) -> Self;
-                f64::cos as cos(self) -> Self;
-                f64::tan as tan(self) -> Self;
-                f64::asin as asin(self) -> Self;
-                f64::acos as acos(self) -> Self;
-                f64::atan as atan(self) -> Self;
-                f64::exp_m1 as exp_m1(self) -> Self;
-                f64::ln_1p as ln_1p(self) -> Self;
-                f64::sinh as sinh(self) -> Self;
-                f64::cosh as cosh(self) -> Self;
-                f64::tanh as tanh(self) -> Self;
-                f64::asinh as asinh(self) -> Self;
-                f64::acosh as acosh(self) -> Self;
-                f64::atanh as atanh(self) -> Self;
-                f64::to_degrees as to_degrees(self) -> Self;
-                f64::to_radians as to_radians(self) -> Self;
-                f64::fract as fract(self) -> Self;
-                f64::recip as recip(self) -> Self;
-                f64::signum as signum(self) -> Self;
+                num::traits::Float::floor as floor(self) -> Self;
+                num::traits::Float::ceil as ceil(self) -> Self;
+                num::traits::Float::round as round(self) -> Self;
+                num::traits::Float::trunc as trunc(self) -> Self;
+                num::traits::Float::abs as abs(self) -> Self;
+                num::traits::Float::sqrt as sqrt(self) -> Self;
+                num::traits::Float::exp as exp(self) -> Self;
+                num::traits::Float::exp2 as exp2(self) -> Self;
+                num::traits::Float::ln as ln(self) -> Self;
+                num::traits::Float::log2 as log2(self) -> Self;
+                num::traits::Float::log10 as log10(self) -> Self;
+                num::traits::Float::cbrt as cbrt(self) -> Self;
+                num::traits::Float::sin as sin(self) -> Self;
+                num::traits::Float::cos as cos(self) -> Self;
+                num::traits::Float::tan as tan(self) -> Self;
+                num::traits::Float::asin as asin(self) -> Self;
+                num::traits::Float::acos as acos(self) -> Self;
+                num::traits::Float::atan as atan(self) -> Self;
+                num::traits::Float::exp_m1 as exp_m1(self) -> Self;
+                num::traits::Float::ln_1p as ln_1p(self) -> Self;
+                num::traits::Float::sinh as sinh(self) -> Self;
+                num::traits::Float::cosh as cosh(self) -> Self;
+                num::traits::Float::tanh as tanh(self) -> Self;
+                num::traits::Float::asinh as asinh(self) -> Self;
+                num::traits::Float::acosh as acosh(self) -> Self;
+                num::traits::Float::atanh as atanh(self) -> Self;
+                num::traits::Float::to_degrees as to_degrees(self) -> Self;
+                num::traits::Float::to_radians as to_radians(self) -> Self;
+                num::traits::Float::fract as fract(self) -> Self;
+                num::traits::Float::recip as recip(self) -> Self;
+                num::traits::Float::signum as signum(self) -> Self;
             }
             $crate::forward! {
-                f64::is_nan as is_nan(self) -> bool;
-                f64::is_infinite as is_infinite(self) -> bool;
-                f64::is_finite as is_finite(self) -> bool;
-                f64::is_normal as is_normal(self) -> bool;
-                f64::classify as classify(self) -> std::num::FpCategory;
-                f64::integer_decode as integer_decode(self) -> (u64, i16, i8);
-                f64::is_sign_positive as is_sign_positive(self) -> bool;
-                f64::is_sign_negative as is_sign_negative(self) -> bool;
+                num::traits::Float::is_nan as is_nan(self) -> bool;
+                num::traits::Float::is_infinite as is_infinite(self) -> bool;
+                num::traits::Float::is_finite as is_finite(self) -> bool;
+                num::traits::Float::is_normal as is_normal(self) -> bool;
+                num::traits::Float::classify as classify(self) -> core::num::FpCategory;
+                num::traits::Float::integer_decode as integer_decode(self) -> (u64, i16, i8);
+                num::traits::Float::is_sign_positive as is_sign_positive(self) -> bool;
+                num::traits::Float::is_sign_negative as is_sign_negative(self) -> bool;
             }
             $crate::forward_into_args! {
-                f64::mul_add as mul_add(self, a: Self, b: Self) -> Self;
-                f64::log as log(self, base: Self) -> Self;
-                f64::hypot as hypot(self, other: Self) -> Self;
-                f64::atan2 as atan2(self, other: Self) -> Self;
-                f64::copysign as copysign(self, sign: Self) -> Self;
-                f64::min as min(self, other: Self) -> Self;
-                f64::max as max(self, other: Self) -> Self;
-                f64::powf as powf(self, n: Self) -> Self;
+                num::traits::Float::mul_add as mul_add(self, a: Self, b: Self) -> Self;
+                num::traits::Float::log as log(self, base: Self) -> Self;
+                num::traits::Float::hypot as hypot(self, other: Self) -> Self;
+                num::traits::Float::atan2 as atan2(self, other: Self) -> Self;
+                num::traits::Float::copysign as copysign(self, sign: Self) -> Self;
+                num::traits::Float::min as min(self, other: Self) -> Self;
+                num::traits::Float::max as max(self, other: Self) -> Self;
+                num::traits::Float::powf as powf(self, n: Self) -> Self;
             }
             $crate::constant! {
-                infinity() -> $unit_name(f64::INFINITY);
-                neg_infinity() -> $unit_name(f64::NEG_INFINITY);
-                nan() -> $unit_name(f64::NAN);
-                neg_zero() -> $unit_name(-0.0);
-                min_value() -> $unit_name(f64::MIN);
-                min_positive_value() -> $unit_name(f64::MIN_POSITIVE);
-                epsilon() -> $unit_name(f64::EPSILON);
-                max_value() -> $unit_name(f64::MAX);
+                infinity() -> $unit_name(<$type as num::traits::Float>::infinity());
+                neg_infinity() -> $unit_name(<$type as num::traits::Float>::neg_infinity());
+                nan() -> $unit_name(<$type as num::traits::Float>::nan());
+                neg_zero() -> $unit_name(<$type as num::traits::Float>::neg_zero());
+                min_value() -> $unit_name(<$type as num::traits::Float>::min_value());
+                min_positive_value() -> $unit_name(<$type as num::traits::Float>::min_positive_value());
+                epsilon() -> $unit_name(<$type as num::traits::Float>::epsilon());
+                max_value() -> $unit_name(<$type as num::traits::Float>::max_value());
+            }
+        }
+    };
+}
+
+/// NOT FOR DIRECT USE
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unit_float_methods {
+    ($unit_name:ident : $type:ty) => {
+        impl $unit_name {
+            /// Returns a unit with the magnitude of `self` and the sign of `sign`.
+            #[must_use]
+            #[inline]
+            pub fn copysign(self, sign: Self) -> Self {
+                Self(num::traits::Float::copysign(self.0, sign.0))
+            }
+
+            /// Calculates the least nonnegative remainder of `self (mod rhs)`,
+            /// useful for wrapping a value into `[0, rhs)`.
+            #[must_use]
+            #[inline]
+            pub fn rem_euclid(self, rhs: Self) -> Self {
+                let rem = self.0 % rhs.0;
+                if rem < <$type as num::Zero>::zero() {
+                    Self(rem + num::traits::Float::abs(rhs.0))
+                } else {
+                    Self(rem)
+                }
+            }
+
+            /// Calculates Euclidean division, the matching method for [`rem_euclid`](Self::rem_euclid).
+            #[must_use]
+            #[inline]
+            pub fn div_euclid(self, rhs: Self) -> Self {
+                let (zero, one) = (<$type as num::Zero>::zero(), <$type as num::One>::one());
+                let quotient = num::traits::Float::trunc(self.0 / rhs.0);
+                if self.0 % rhs.0 >= zero {
+                    Self(quotient)
+                } else if rhs.0 > zero {
+                    Self(quotient - one)
+                } else {
+                    Self(quotient + one)
+                }
+            }
+
+            /// Takes the square root of the inner value and re-wraps it in the same unit.
+            ///
+            /// # Dimensionality
+            /// The square root of a quantity is not the same dimension as the quantity,
+            /// the caller is responsible for making sure the result is dimensionally correct.
+            /// Prefer [`hypot`](Self::hypot) for computing magnitudes.
+            #[must_use]
+            #[inline]
+            pub fn sqrt(self) -> Self {
+                Self(num::traits::Float::sqrt(self.0))
+            }
+
+            /// Raises the inner value to an integer power and re-wraps it in the same unit.
+            ///
+            /// # Dimensionality
+            /// The caller is responsible for making sure the result is dimensionally correct.
+            #[must_use]
+            #[inline]
+            pub fn powi(self, n: i32) -> Self {
+                Self(num::traits::Float::powi(self.0, n))
+            }
+
+            /// Raises the inner value to a floating point power and re-wraps it in the same unit.
+            ///
+            /// # Dimensionality
+            /// The caller is responsible for making sure the result is dimensionally correct.
+            #[must_use]
+            #[inline]
+            pub fn powf(self, n: $type) -> Self {
+                Self(num::traits::Float::powf(self.0, n))
+            }
+
+            /// Computes `sqrt(self² + other²)` without intermediate overflow,
+            /// this is dimensionally sound as both sides share a unit.
+            #[must_use]
+            #[inline]
+            pub fn hypot(self, other: Self) -> Self {
+                Self(num::traits::Float::hypot(self.0, other.0))
+            }
+
+            /// Returns `true` if the inner value is neither infinite nor `NaN`.
+            #[must_use]
+            #[inline]
+            pub fn is_finite(self) -> bool {
+                num::traits::Float::is_finite(self.0)
+            }
+
+            /// Returns `true` if the inner value is `NaN`.
+            #[must_use]
+            #[inline]
+            pub fn is_nan(self) -> bool {
+                num::traits::Float::is_nan(self.0)
+            }
+
+            /// Returns `true` if the inner value is positive or negative infinity.
+            #[must_use]
+            #[inline]
+            pub fn is_infinite(self) -> bool {
+                num::traits::Float::is_infinite(self.0)
+            }
+
+            /// Returns `true` if the inner value is neither zero, infinite, subnormal, or `NaN`.
+            #[must_use]
+            #[inline]
+            pub fn is_normal(self) -> bool {
+                num::traits::Float::is_normal(self.0)
+            }
+
+            /// Returns `self` if it is finite, otherwise returns `fallback`.
+            #[must_use]
+            #[inline]
+            pub fn finite_or(self, fallback: Self) -> Self {
+                self.unwrap_finite().unwrap_or(fallback)
+            }
+
+            /// Clamps the inner value to `[0, 1]`, the range of a normalized output like a duty cycle.
+            ///
+            /// `NaN` is returned as is, like [`f64::clamp`].
+            ///
+            /// # Dimensionality
+            /// This assumes the unit is already dimensionless or normalized,
+            /// `1` of any other unit is not a meaningful limit.
+            #[must_use]
+            #[inline]
+            pub fn clamp01(self) -> Self {
+                self.clamp_inner(<$type as num::Zero>::zero())
+            }
+
+            /// Clamps the inner value to `[-1, 1]`, the range of a normalized output like motor power.
+            ///
+            /// `NaN` is returned as is, like [`f64::clamp`].
+            ///
+            /// # Dimensionality
+            /// This assumes the unit is already dimensionless or normalized,
+            /// `1` of any other unit is not a meaningful limit.
+            #[must_use]
+            #[inline]
+            pub fn clamp_symmetric(self) -> Self {
+                self.clamp_inner(-<$type as num::One>::one())
+            }
+
+            /// Clamps the inner value to `[min, 1]`
+            #[inline]
+            fn clamp_inner(self, min: $type) -> Self {
+                let max = <$type as num::One>::one();
+                if self.0 < min {
+                    Self(min)
+                } else if self.0 > max {
+                    Self(max)
+                } else {
+                    self
+                }
+            }
+
+            /// Returns `Some(self)` if it is finite, `None` if it is infinite or `NaN`.
+            #[must_use]
+            #[inline]
+            pub fn unwrap_finite(self) -> Option<Self> {
+                if self.is_finite() {
+                    Some(self)
+                } else {
+                    None
+                }
             }
         }
     };
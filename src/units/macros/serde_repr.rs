@@ -0,0 +1,153 @@
+//! NOT FOR DIRECT USE
+//!
+//! The shared serde logic behind [`unit_serde!`](crate::unit_serde),
+//! kept out of the macro so it is not expanded for every unit.
+
+use alloc::string::String;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+const FIELDS: &[&str] = &["value", "unit"];
+
+/// Serializes a unit as the bare number, or as `{"value": .., "unit": ..}` for human-readable formats
+/// when the `units-serde-struct` feature is enabled.
+///
+/// # Errors
+/// Returns the serializer's error if the value could not be written.
+pub fn serialize<T: Serialize, S: Serializer>(
+    value: &T,
+    unit: &'static str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if cfg!(feature = "units-serde-struct") && serializer.is_human_readable() {
+        let mut state = serializer.serialize_struct("Unit", FIELDS.len())?;
+        state.serialize_field("value", value)?;
+        state.serialize_field("unit", unit)?;
+        state.end()
+    } else {
+        value.serialize(serializer)
+    }
+}
+
+/// The backing types of units, integer units reject numbers with a fractional part.
+pub trait SerdeBacking: num::NumCast {
+    /// Whether the backing type is an integer
+    const INTEGER: bool;
+}
+
+impl SerdeBacking for f64 {
+    const INTEGER: bool = false;
+}
+
+impl SerdeBacking for f32 {
+    const INTEGER: bool = false;
+}
+
+impl SerdeBacking for i64 {
+    const INTEGER: bool = true;
+}
+
+impl SerdeBacking for u64 {
+    const INTEGER: bool = true;
+}
+
+/// Deserializes a unit from either a bare number or a `{"value": .., "unit": ..}` struct.
+///
+/// Human-readable formats accept both shapes whether or not `units-serde-struct` is enabled,
+/// the `unit` field has to match `name` or `symbol` when present.
+/// Other formats only accept the bare number.
+///
+/// # Errors
+/// Returns the deserializer's error if the input has neither shape,
+/// the number does not fit in `T`, has a fractional part for an integer `T` or the unit does not match.
+pub fn deserialize<'de, T, D>(
+    name: &'static str,
+    symbol: Option<&'static str>,
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + SerdeBacking,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(UnitVisitor {
+            name,
+            symbol,
+            marker: PhantomData,
+        })
+    } else {
+        T::deserialize(deserializer)
+    }
+}
+
+struct UnitVisitor<T> {
+    name: &'static str,
+    symbol: Option<&'static str>,
+    marker: PhantomData<T>,
+}
+
+impl<T> UnitVisitor<T> {
+    fn matches(&self, unit: &str) -> bool {
+        unit == self.name || self.symbol == Some(unit)
+    }
+}
+
+impl<'de, T> Visitor<'de> for UnitVisitor<T>
+where
+    T: Deserialize<'de> + SerdeBacking,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a number or a struct with a value and a unit of {}",
+            self.symbol.unwrap_or(self.name)
+        )
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<T, E> {
+        if T::INTEGER && num::traits::Float::fract(v) != 0.0 {
+            return Err(E::invalid_value(Unexpected::Float(v), &self));
+        }
+        T::from(v).ok_or_else(|| E::invalid_value(Unexpected::Float(v), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        T::from(v).ok_or_else(|| E::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        T::from(v).ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut value = None;
+        let mut unit_seen = false;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "value" => {
+                    if value.is_some() {
+                        return Err(de::Error::duplicate_field("value"));
+                    }
+                    value = Some(map.next_value::<T>()?);
+                }
+                "unit" => {
+                    if unit_seen {
+                        return Err(de::Error::duplicate_field("unit"));
+                    }
+                    let unit = map.next_value::<String>()?;
+                    if !self.matches(&unit) {
+                        return Err(de::Error::invalid_value(Unexpected::Str(&unit), &self));
+                    }
+                    unit_seen = true;
+                }
+                other => return Err(de::Error::unknown_field(other, FIELDS)),
+            }
+        }
+        value.ok_or_else(|| de::Error::missing_field("value"))
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(Kilogram: float);
-unit!(Gram: float);
-unit!(Pound: float);
-unit!(Ounce: float);
+unit!(Kilogram("kg"): float);
+unit!(Gram("g"): float);
+unit!(Pound("lb"): float);
+unit!(Ounce("oz"): float);
 
 unit_conversion!(Kilogram(float) <-> Gram(float) ~ kilogram_to_gram);
 unit_conversion!(Kilogram(float) <-> Pound(float) ~ kilogram_to_pound);
@@ -52,12 +52,24 @@ pub mod linear_acceleration;
 pub mod linear_velocity;
 pub mod mass;
 pub mod moment_of_inertia;
+#[cfg(feature = "units-prelude")]
+pub mod prelude;
+#[cfg(feature = "proptest")]
+pub mod strategy;
 pub mod temperature;
 pub mod time;
 pub mod torque;
+pub mod voltage;
 
+pub use canon::CanonEq;
+pub use error::{ParseUnitError, UnitError};
+pub use traits::Unit;
+
+mod canon;
+mod error;
 #[doc(hidden)]
 pub mod macros;
 #[cfg(test)]
 #[doc(hidden)]
 mod test;
+mod traits;
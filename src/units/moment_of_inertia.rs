@@ -1,7 +1,7 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(KilogramSquareMeter: float);
-unit!(PoundSquareFoot: float);
+unit!(KilogramSquareMeter("kg*m^2"): float);
+unit!(PoundSquareFoot("lb*ft^2"): float);
 
 unit_conversion!(KilogramSquareMeter(float) <-> PoundSquareFoot(float) ~ kilogram_square_meter_to_pound_square_foot);
 
@@ -0,0 +1,25 @@
+//! The units most FRC code needs, in one import.
+//!
+//! This is opt-in through the `units-prelude` feature
+//! so crates declaring their own units aren't forced into these names.
+//!
+//! # Example
+//! ```
+//! use frclib_core::units::prelude::*;
+//!
+//! let wheel_radius = Inch::new(2.0);
+//! let rotations = Rotation::new(1.0);
+//! let traveled = Meter::from(wheel_radius) * Radian::from(rotations).value();
+//! assert!(traveled.approx_eq(Meter::new(0.319_186), Meter::new(1e-6)));
+//! assert_eq!(Volt::from(Millivolt::new(12_000.0)), Volt::new(12.0));
+//! ```
+
+pub use super::angle::{Angle, Degree, Radian, Rotation};
+pub use super::angular_velocity::{
+    AngleVel, DegreePerSec, RadianPerSec, RotationPerMin, RotationPerSec,
+};
+pub use super::length::{Centimeter, Distance, Distance as Length, Foot, Inch, Meter};
+pub use super::linear_velocity::{FeetPerSecond, LinearVelocity, MetersPerSecond};
+pub use super::time::{Microsecond, Millisecond, Minute, Second, Time};
+pub use super::voltage::{Millivolt, Volt, Voltage};
+pub use super::Unit;
@@ -0,0 +1,147 @@
+//! [`proptest`] strategies for generating units.
+//!
+//! Every unit implements [`Arbitrary`](proptest::arbitrary::Arbitrary) using [`sensible`],
+//! floating point units are kept within `±1e6` so conversions and arithmetic stay finite.
+//! The other strategies here widen that range when a test needs it.
+//!
+//! # Example
+//! ```
+//! use frclib_core::units::{length::{Foot, Meter}, strategy};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(meters in any::<Meter>())| {
+//!     let feet: Foot = meters.into();
+//!     prop_assert!((Meter::from(feet).value() - meters.value()).abs() <= 1e-6);
+//! });
+//! proptest!(|(meters in strategy::any_float::<Meter>())| {
+//!     prop_assert!(meters.value().is_nan() || meters == meters);
+//! });
+//! ```
+
+use proptest::num::f64;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use super::Unit;
+
+/// The backing types that have a default strategy for [`sensible`]
+pub trait SensibleBacking: Sized {
+    /// The default strategy for the backing type
+    fn sensible() -> BoxedStrategy<Self>;
+}
+
+impl SensibleBacking for f64 {
+    fn sensible() -> BoxedStrategy<Self> {
+        (-1.0e6..=1.0e6).boxed()
+    }
+}
+
+#[cfg(feature = "half")]
+impl SensibleBacking for half::f16 {
+    fn sensible() -> BoxedStrategy<Self> {
+        (-1.0e3f32..=1.0e3).prop_map(Self::from_f32).boxed()
+    }
+}
+
+impl SensibleBacking for i64 {
+    fn sensible() -> BoxedStrategy<Self> {
+        proptest::arbitrary::any::<Self>().boxed()
+    }
+}
+
+impl SensibleBacking for u64 {
+    fn sensible() -> BoxedStrategy<Self> {
+        proptest::arbitrary::any::<Self>().boxed()
+    }
+}
+
+/// Generates units with values in a range that won't overflow in typical math,
+/// floating point units are finite and within `±1e6` (`±1e3` for `f16`), integer units can be any value.
+pub fn sensible<U>() -> BoxedStrategy<U>
+where
+    U: Unit + 'static,
+    U::Backing: SensibleBacking + 'static,
+{
+    U::Backing::sensible().prop_map(U::new).boxed()
+}
+
+/// Generates floating point units with any finite value, including subnormals
+pub fn finite<U>() -> BoxedStrategy<U>
+where
+    U: Unit<Backing = f64> + 'static,
+{
+    (f64::POSITIVE | f64::NEGATIVE | f64::NORMAL | f64::SUBNORMAL | f64::ZERO)
+        .prop_map(U::new)
+        .boxed()
+}
+
+/// Generates floating point units with any value, including infinities and NaN
+pub fn any_float<U>() -> BoxedStrategy<U>
+where
+    U: Unit<Backing = f64> + 'static,
+{
+    f64::ANY.prop_map(U::new).boxed()
+}
+
+/// Generates a property test for a conversion declared with [`unit_conversion!`](crate::unit_conversion).
+///
+/// The test checks that converting each unit into the other and back lands within
+/// `epsilon` of where it started, relative to its magnitude (or `1` for values smaller than `1`),
+/// and that converting two values never swaps their order.
+/// Both units are generated with [`sensible`], `epsilon` defaults to `1e-9`.
+///
+/// The test is named `<unit_a>_<unit_b>_conversion_roundtrip` in snake case,
+/// outside of `cfg(test)` it is a plain function that runs the same checks when called.
+///
+/// # Example
+/// ```
+/// use frclib_core::{assert_unit_conversion_roundtrip, units::length::{Foot, Inch, Meter}};
+///
+/// assert_unit_conversion_roundtrip!(Inch <-> Meter);
+/// assert_unit_conversion_roundtrip!(Foot <-> Meter, epsilon = 1e-12);
+///
+/// inch_meter_conversion_roundtrip();
+/// foot_meter_conversion_roundtrip();
+/// ```
+#[macro_export]
+macro_rules! assert_unit_conversion_roundtrip {
+    ($unit_a:ident <-> $unit_b:ident) => {
+        $crate::assert_unit_conversion_roundtrip!($unit_a <-> $unit_b, epsilon = 1e-9);
+    };
+    ($unit_a:ident <-> $unit_b:ident, epsilon = $epsilon:expr) => {
+        $crate::units::macros::paste::paste! {
+            #[cfg_attr(test, test)]
+            #[allow(dead_code)]
+            fn [< $unit_a:snake _ $unit_b:snake _conversion_roundtrip >]() {
+                $crate::assert_unit_conversion_roundtrip!(@check $unit_a -> $unit_b, $epsilon);
+                $crate::assert_unit_conversion_roundtrip!(@check $unit_b -> $unit_a, $epsilon);
+            }
+        }
+    };
+    (@check $from:ident -> $to:ident, $epsilon:expr) => {
+        $crate::units::macros::proptest::proptest!(|(
+            value in $crate::units::strategy::sensible::<$from>(),
+            other in $crate::units::strategy::sensible::<$from>()
+        )| {
+            let back = $from::from($to::from(value));
+            let error = back.abs_diff(value).ratio($from::ONE);
+            let tolerance = $epsilon * value.ratio($from::ONE).abs().max(1.0);
+            $crate::units::macros::proptest::prop_assert!(
+                error <= tolerance,
+                "{:?} became {:?} after converting to {} and back",
+                value,
+                back,
+                stringify!($to)
+            );
+
+            let (converted, other_converted) = ($to::from(value), $to::from(other));
+            $crate::units::macros::proptest::prop_assert!(
+                !(value < other && converted > other_converted)
+                    && !(value > other && converted < other_converted),
+                "converting {:?} and {:?} to {} swapped their order",
+                value,
+                other,
+                stringify!($to)
+            );
+        });
+    };
+}
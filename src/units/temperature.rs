@@ -1,8 +1,8 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(Celsius: float);
-unit!(Fahrenheit: float);
-unit!(Kelvin: float);
+unit!(Celsius("degC"): float);
+unit!(Fahrenheit("degF"): float);
+unit!(Kelvin("K"): float);
 
 unit_conversion!(Celsius(float) <-> Fahrenheit(float) ~ celsius_to_fahrenheit);
 unit_conversion!(Celsius(float) <-> Kelvin(float) ~ celsius_to_kelvin);
@@ -11,7 +11,7 @@ unit_conversion!(Fahrenheit(float) <-> Kelvin(float) ~ fahrenheit_to_kelvin);
 unit_family!(Temperature(Celsius): Fahrenheit, Kelvin);
 
 fn celsius_to_fahrenheit(celsius: f64) -> f64 {
-    celsius.mul_add(1.8, 32.0)
+    num::traits::Float::mul_add(celsius, 1.8, 32.0)
 }
 
 fn celsius_to_kelvin(celsius: f64) -> f64 {
@@ -16,6 +16,34 @@ fn conversion() {
     assert!(combined > feet);
 }
 
+#[test]
+fn family_convert() {
+    use crate::units::angle::{Angle, Degree, Radian};
+    use crate::units::length::{Distance, Inch};
+
+    fn to_target<L: Distance, T: Distance>(value: L) -> T {
+        value.convert()
+    }
+
+    let inches: Inch = to_target(Meter::new(1.0));
+    assert!(inches.approx_eq(Inch::new(39.370_08), Meter::new(1e-6)));
+    assert_eq!(Foot::new(1.0).convert::<Inch>(), Foot::new(1.0).to_inches());
+    assert_eq!(
+        Radian::new(core::f64::consts::PI).convert::<Degree>(),
+        Degree::new(180.0)
+    );
+}
+
+#[test]
+fn family_value_in() {
+    use crate::units::length::{Distance, Inch};
+    use crate::units::time::{Microsecond, Time};
+
+    assert!((Foot::new(2.0).value_in::<Inch>() - 24.0).abs() < 1e-9);
+    let micros: u64 = Second::new(1.5).value_in::<Microsecond>();
+    assert_eq!(micros, 1_500_000);
+}
+
 #[test]
 fn dim_analysis() {
     let meter = Meter::new(1.0);
@@ -23,3 +51,868 @@ fn dim_analysis() {
     let meter_per_second: MetersPerSecond = meter / second;
     assert_eq!(meter_per_second, MetersPerSecond::new(1.0));
 }
+
+#[test]
+fn square_dim_analysis() {
+    use crate::{unit, unit_dim_analysis};
+
+    unit!(Side: float);
+    unit!(Area: float);
+    unit_dim_analysis!(square: Side = Area);
+
+    let area: Area = Side(3.0).dim_mul(Side(12.0));
+    assert_eq!(area, Area(36.0));
+    assert_eq!(area.dim_sqrt(), Side(6.0));
+    assert_eq!(area / Side(4.0), Side(9.0));
+
+    // the root of a sum of squares, like a standard deviation from its variance
+    let variance = [Side(1.0), Side(-1.0), Side(1.0), Side(-1.0)]
+        .into_iter()
+        .map(Side::squared)
+        .fold(Area(0.0), |sum, square| sum + square)
+        / 4.0;
+    assert_eq!(variance.dim_sqrt(), Side(1.0));
+    assert_eq!(Area(16.0).sqrt(), Area(4.0));
+}
+
+#[test]
+fn unit_array() {
+    use crate::units::length::Inch;
+
+    let point = Meter::from_array([1.0, -2.0, 0.5]);
+    assert_eq!(point, [Meter::new(1.0), Meter::new(-2.0), Meter::new(0.5)]);
+    assert_eq!(
+        Meter::to_array(point).map(f64::to_bits),
+        [1.0f64, -2.0, 0.5].map(f64::to_bits)
+    );
+    assert_eq!(
+        Inch::from_array([12.0, 6.0]),
+        [Inch::new(12.0), Inch::new(6.0)]
+    );
+    assert_eq!(Meter::from_array([]), [Meter::ZERO; 0]);
+}
+
+#[test]
+fn saturating_conversion() {
+    crate::unit!(Tick: int);
+    crate::unit!(Count: uint);
+
+    crate::unit_conversion!(Tick(int) <-> Count(uint) ~ saturating ticks_to_counts);
+
+    fn ticks_to_counts(ticks: i128) -> i128 {
+        ticks * 4
+    }
+
+    assert_eq!(Count::from(Tick(3)), Count(12));
+    assert_eq!(Count::from(Tick(-3)), Count(0));
+    assert_eq!(Count::from(Tick(i64::MAX)), Count(u64::MAX));
+    assert_eq!(Tick::from(Count(13)), Tick(3));
+    assert_eq!(Tick::from(Count(u64::MAX)), Tick(i64::MAX / 2));
+    assert_eq!(Count(12), Tick(3));
+    assert!(Tick(-3) < Count(0), "comparisons shouldn't clamp");
+}
+
+#[test]
+fn saturating_coarse_conversion() {
+    crate::unit!(Count: uint);
+    crate::unit!(Tick: int);
+    crate::unit!(Microtick: int);
+    crate::unit!(Millitick: uint);
+
+    crate::unit_conversion!(Count(uint) <-> Tick(int) ~ saturating counts_to_ticks);
+    crate::unit_conversion!(Microtick(int) <-> Millitick(uint) ~ saturating us_to_ms);
+
+    const fn counts_to_ticks(counts: i128) -> i128 {
+        counts
+    }
+
+    const fn us_to_ms(us: i128) -> i128 {
+        us / 1000
+    }
+
+    assert_eq!(Tick::from(Count(u64::MAX)), Tick(i64::MAX));
+    assert_eq!(Tick::from(Count(7)), Tick(7));
+    assert_eq!(Count::from(Tick(i64::MIN)), Count(0));
+
+    assert_eq!(Millitick::from(Microtick(2_500)), Millitick(2));
+    assert_eq!(Microtick::from(Millitick(2)), Microtick(i64::MAX));
+    assert_eq!(Microtick::from(Millitick(0)), Microtick(0));
+}
+
+#[test]
+fn scalar_assign() {
+    crate::unit!(Tick: int);
+    crate::unit!(Count: uint);
+
+    let mut accumulator = Meter::new(2.0);
+    accumulator *= 0.5;
+    assert_eq!(accumulator, Meter::new(1.0));
+    accumulator /= 4.0;
+    assert_eq!(accumulator, Meter::new(0.25));
+    accumulator *= 2i32;
+    assert_eq!(accumulator, Meter::new(0.5));
+    accumulator += Meter::new(0.5);
+    assert_eq!(accumulator, Meter::new(1.0));
+
+    let mut ticks = Tick(-6);
+    ticks *= 3i64;
+    ticks /= 2i8;
+    assert_eq!(ticks, Tick(-9));
+
+    let mut counts = Count(6);
+    counts *= 4u64;
+    counts /= 3u8;
+    assert_eq!(counts, Count(8));
+}
+
+#[test]
+fn normalized_clamp() {
+    crate::unit!(DutyCycle: float);
+
+    assert_eq!(DutyCycle::new(1.5).clamp01(), DutyCycle::new(1.0));
+    assert_eq!(DutyCycle::new(-0.25).clamp01(), DutyCycle::new(0.0));
+    assert_eq!(DutyCycle::new(0.75).clamp01(), DutyCycle::new(0.75));
+    assert_eq!(DutyCycle::new(-1.5).clamp_symmetric(), DutyCycle::new(-1.0));
+    assert_eq!(DutyCycle::new(2.0).clamp_symmetric(), DutyCycle::new(1.0));
+    assert_eq!(
+        DutyCycle::new(-0.25).clamp_symmetric(),
+        DutyCycle::new(-0.25)
+    );
+    assert!(
+        DutyCycle::new(f64::NAN).clamp01().is_nan(),
+        "NaN should pass through"
+    );
+}
+
+#[test]
+fn angle_unit_with_radians_method() {
+    crate::unit!(Heading: float);
+
+    impl Heading {
+        const fn radians(self) -> f64 {
+            self.0
+        }
+    }
+
+    crate::unit_angle!(Heading);
+
+    assert_eq!(Heading(0.5).radians().to_bits(), 0.5f64.to_bits());
+    assert!((Heading(core::f64::consts::FRAC_PI_2).sin() - 1.0).abs() < 1e-12);
+    assert!((Heading::atan2(1.0, 1.0).0 - core::f64::consts::FRAC_PI_4).abs() < 1e-12);
+}
+
+#[test]
+fn unwrap_delta() {
+    crate::unit!(EncoderTick: uint mod 65536);
+    crate::unit!(SignedTick: int mod 4096);
+
+    assert_eq!(
+        EncoderTick::new(5).unwrap_delta(EncoderTick::new(65_530)),
+        11,
+        "forward across the wrap"
+    );
+    assert_eq!(
+        EncoderTick::new(65_530).unwrap_delta(EncoderTick::new(5)),
+        -11,
+        "backward across the wrap"
+    );
+    assert_eq!(
+        EncoderTick::new(300).unwrap_delta(EncoderTick::new(100)),
+        200
+    );
+    assert_eq!(
+        EncoderTick::new(32_768).unwrap_delta(EncoderTick::new(0)),
+        -32_768
+    );
+    assert_eq!(SignedTick::new(-3).unwrap_delta(SignedTick::new(4)), -7);
+    assert_eq!(SignedTick::new(4090).unwrap_delta(SignedTick::new(10)), -16);
+
+    let readings = [65_000, 65_500, 200, 900, 100].map(EncoderTick::new);
+    let extended = readings
+        .iter()
+        .zip(readings.iter().skip(1))
+        .map(|(previous, reading)| reading.unwrap_delta(*previous))
+        .fold(65_000, |count, delta| count + delta);
+    assert_eq!(extended, 65_636);
+}
+
+#[test]
+fn modular_int_unit() {
+    crate::unit!(EncoderTick("ticks"): uint mod 65536);
+    crate::unit!(SignedTick: int mod 4096);
+    crate::unit!(FullTick: uint mod u64::MAX);
+
+    assert_eq!(EncoderTick::new(65_540).value(), 4);
+    assert_eq!(EncoderTick(65_536), EncoderTick::ZERO);
+    assert_eq!(SignedTick::new(-1).value(), 4095);
+    assert_eq!(
+        EncoderTick::new(65_530) + EncoderTick::new(10),
+        EncoderTick::new(4)
+    );
+    assert_eq!(EncoderTick::new(3) - 5u64, EncoderTick::new(65_534));
+    assert_eq!(-SignedTick::new(1), SignedTick::new(4095));
+    assert_eq!(
+        FullTick::new(u64::MAX - 1) + FullTick::new(3),
+        FullTick::new(2)
+    );
+    assert!(
+        EncoderTick(65_537) < EncoderTick(2),
+        "compared after wrapping"
+    );
+
+    let mut count = SignedTick::new(4000);
+    count += 100i64;
+    assert_eq!(count, SignedTick::new(4));
+    count -= SignedTick::new(8);
+    assert_eq!(count, SignedTick::new(4092));
+    assert_eq!(format!("{:?}", EncoderTick(65_537)), "EncoderTick(1)");
+    assert_eq!(EncoderTick(65_537).to_string(), "EncoderTick(1u64)");
+}
+
+#[test]
+fn modular_unit() {
+    crate::unit!(WrappedDegree: float mod 360.0);
+
+    assert_eq!(
+        WrappedDegree::new(360.0).value().to_bits(),
+        0.0f64.to_bits()
+    );
+    assert_eq!(WrappedDegree::new(725.0), WrappedDegree::new(5.0));
+    assert_eq!(WrappedDegree::new(-90.0), WrappedDegree::new(270.0));
+    assert_eq!(WrappedDegree::new(-1e-20), WrappedDegree::ZERO);
+    assert_eq!(WrappedDegree(360.0), WrappedDegree(0.0));
+    assert!(
+        WrappedDegree::new(f64::INFINITY).value().is_nan(),
+        "infinity has no angle"
+    );
+
+    assert_eq!(
+        WrappedDegree::new(350.0) + WrappedDegree::new(20.0),
+        WrappedDegree::new(10.0)
+    );
+    assert_eq!(WrappedDegree::new(10.0) - 20.0, WrappedDegree::new(350.0));
+    assert_eq!(-WrappedDegree::new(90.0), WrappedDegree::new(270.0));
+    assert_eq!(WrappedDegree::new(200.0) * 2.0, WrappedDegree::new(40.0));
+    assert_eq!(WrappedDegree(540.0) / 2.0, WrappedDegree::new(90.0));
+
+    let mut heading = WrappedDegree::new(300.0);
+    heading += 120.0;
+    assert_eq!(heading, WrappedDegree::new(60.0));
+    heading -= WrappedDegree::new(120.0);
+    assert_eq!(heading, WrappedDegree::new(300.0));
+    assert!(
+        WrappedDegree(370.0) < WrappedDegree(20.0),
+        "compared after wrapping"
+    );
+}
+
+#[test]
+fn modular_unit_formatting() {
+    crate::unit!(WrappedDegree: float mod 360.0);
+
+    assert_eq!(format!("{:?}", WrappedDegree(370.0)), "WrappedDegree(10.0)");
+    assert_eq!(format!("{:e}", WrappedDegree(370.0)), "1e1");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn modular_unit_serde() {
+    crate::unit!(WrappedDegree: float mod 360.0);
+
+    let json = serde_json::to_string(&WrappedDegree(370.0)).expect("serialize");
+    if cfg!(feature = "units-serde-struct") {
+        assert_eq!(json, r#"{"value":10.0,"unit":"WrappedDegree"}"#);
+    } else {
+        assert_eq!(json, "10.0");
+    }
+    let parsed = serde_json::from_str::<WrappedDegree>("370.0").expect("deserialize");
+    assert_eq!(
+        parsed.0.to_bits(),
+        10.0f64.to_bits(),
+        "stored wrapped after deserializing"
+    );
+}
+
+#[cfg(feature = "structure")]
+#[test]
+fn modular_unit_structure() {
+    use crate::structure::FrcStructure;
+
+    crate::unit!(WrappedDegree: float mod 360.0);
+
+    let packed = WrappedDegree(370.0).pack_to_vec();
+    assert_eq!(packed, 10.0f64.to_le_bytes());
+    let unpacked = WrappedDegree::unpack_slice(&370.0f64.to_le_bytes());
+    assert_eq!(
+        unpacked.0.to_bits(),
+        10.0f64.to_bits(),
+        "stored wrapped after unpacking"
+    );
+    assert_eq!(
+        WrappedDegree::try_unpack(&mut std::io::Cursor::new(packed.as_slice()))
+            .map(|unpacked| unpacked.0.to_bits()),
+        Ok(10.0f64.to_bits())
+    );
+}
+
+#[test]
+fn num_identities() {
+    use num::{One, Zero};
+
+    fn accumulate<T: Zero + One + Copy>(count: usize) -> T {
+        (0..count).fold(T::zero(), |acc, _| acc + T::one())
+    }
+
+    assert_eq!(accumulate::<Meter>(3), Meter::new(3.0));
+    assert!(Meter::zero().is_zero());
+    assert!(Meter::one().is_one());
+    assert!(!Meter::new(-0.5).is_zero());
+}
+
+#[test]
+fn sum_and_collect() {
+    let readings = [Meter::new(1.0), Meter::new(2.5), Meter::new(-0.5)];
+    let summed: Meter = readings.iter().sum();
+    assert_eq!(summed, Meter::new(3.0));
+    let collected: Meter = readings.into_iter().collect();
+    assert_eq!(collected, summed);
+    let empty: Meter = std::iter::empty::<Meter>().collect();
+    assert_eq!(empty, Meter::ZERO);
+}
+
+#[test]
+fn checked_construction() {
+    use crate::units::{time::Microsecond, UnitError};
+
+    assert_eq!(Meter::try_new(1.5), Ok(Meter::new(1.5)));
+    assert_eq!(Meter::try_new(f64::NAN), Err(UnitError::NotFinite("Meter")));
+    assert!(Meter::try_new(f64::NEG_INFINITY).is_err());
+
+    assert_eq!(Microsecond::try_from(20i64), Ok(Microsecond::new(20)));
+    assert_eq!(
+        Microsecond::try_from(-1i64),
+        Err(UnitError::OutOfRange("Microsecond"))
+    );
+    assert_eq!(Microsecond::try_from(2.9f64), Ok(Microsecond::new(2)));
+    assert!(Microsecond::try_from(-0.5f64).is_err());
+    assert!(Microsecond::try_from(f64::NAN).is_err());
+
+    assert_eq!(Microsecond::try_new(7i8), Ok(Microsecond::new(7)));
+    assert_eq!(
+        Microsecond::try_new(-7i8),
+        Err(UnitError::OutOfRange("Microsecond"))
+    );
+}
+
+#[test]
+fn checked_int_construction() {
+    use crate::unit;
+    use crate::units::UnitError;
+
+    unit!(Tick: int);
+
+    assert_eq!(Tick::try_new(42u64), Ok(Tick(42)));
+    assert_eq!(Tick::try_new(u64::MAX), Err(UnitError::OutOfRange("Tick")));
+    assert_eq!(Tick::try_new(-3i128), Ok(Tick(-3)));
+    assert_eq!(Tick::try_new(i128::MIN), Err(UnitError::OutOfRange("Tick")));
+}
+
+#[test]
+fn checked_narrowing() {
+    use crate::unit;
+    use crate::units::{time::Microsecond, UnitError};
+
+    unit!(Tick: int);
+
+    assert_eq!(u32::try_from(Microsecond::new(20_000)), Ok(20_000));
+    assert_eq!(
+        u32::try_from(Microsecond::new(u64::from(u32::MAX) + 1)),
+        Err(UnitError::OutOfRange("u32"))
+    );
+    assert_eq!(
+        i64::try_from(Microsecond::MAX),
+        Err(UnitError::OutOfRange("i64"))
+    );
+    assert_eq!(u8::try_from(Microsecond::new(255)), Ok(255));
+
+    assert_eq!(i16::try_from(Tick(-300)), Ok(-300));
+    assert_eq!(u64::try_from(Tick(-1)), Err(UnitError::OutOfRange("u64")));
+    assert_eq!(i8::try_from(Tick(128)), Err(UnitError::OutOfRange("i8")));
+}
+
+#[test]
+fn checked_mixed_conversion() {
+    use crate::units::{time::Microsecond, UnitError};
+
+    assert_eq!(Second::from(Microsecond::new(1_500_000)), Second::new(1.5));
+    assert_eq!(
+        Second::try_from_microsecond(Microsecond::new(1_500_000)),
+        Ok(Second::new(1.5))
+    );
+    assert_eq!(
+        Second::try_from_microsecond(Microsecond::new((1 << 53) + 1)),
+        Err(UnitError::PrecisionLoss("Second"))
+    );
+    assert_eq!(
+        Second::try_from_microsecond(Microsecond::new(u64::MAX)),
+        Err(UnitError::PrecisionLoss("Second"))
+    );
+    assert!(Second::try_from_microsecond(Microsecond::new(1 << 60)).is_ok());
+    assert_eq!(
+        Second::from(Microsecond::new(18_446_744_073_709_000_000)),
+        Second::new(18_446_744_073_709.0)
+    );
+    assert!(Second::new(1.5) == Microsecond::new(1_500_000));
+    assert!(Second::new(1.0) < Microsecond::new(1_000_001));
+
+    assert_eq!(
+        Microsecond::try_from_second(Second::new(2.5)),
+        Ok(Microsecond::new(2_500_000))
+    );
+    assert_eq!(
+        Microsecond::try_from_second(Second::new(-1.0)),
+        Err(UnitError::OutOfRange("Microsecond"))
+    );
+    assert_eq!(
+        Microsecond::try_from_second(Second::new(1e20)),
+        Err(UnitError::OutOfRange("Microsecond"))
+    );
+    assert_eq!(
+        Microsecond::try_from_second(Second::new(f64::NAN)),
+        Err(UnitError::NotFinite("Microsecond"))
+    );
+}
+
+#[test]
+fn coarse_mixed_conversion() {
+    use crate::{unit, unit_conversion};
+
+    unit!(Micrometer: float);
+    unit!(WholeMeter: uint);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn micrometer_to_whole_meter(micrometer: f64) -> u64 {
+        (micrometer / 1e6) as u64
+    }
+
+    unit_conversion!(Micrometer(float) <-> WholeMeter(uint) ~ micrometer_to_whole_meter);
+
+    assert_eq!(WholeMeter::from(Micrometer(2.5e6)), WholeMeter(2));
+    assert!(
+        Micrometer::from(WholeMeter(2)).0.is_infinite(),
+        "a ratio that rounds to zero must not panic"
+    );
+}
+
+#[test]
+fn midpoint() {
+    use crate::unit;
+    use crate::units::time::Microsecond;
+
+    unit!(Tick: int);
+
+    assert_eq!(Meter::new(1.0).midpoint(Meter::new(2.0)), Meter::new(1.5));
+    assert_eq!(Meter::MAX.midpoint(Meter::MAX), Meter::MAX);
+    assert_eq!(Meter::MAX.midpoint(Meter::MIN), Meter::ZERO);
+
+    assert_eq!(
+        Tick(i64::MAX).midpoint(Tick(i64::MAX - 2)),
+        Tick(i64::MAX - 1)
+    );
+    assert_eq!(Tick(i64::MIN).midpoint(Tick(i64::MIN)), Tick(i64::MIN));
+    assert_eq!(Tick(-3).midpoint(Tick(0)), Tick(-1));
+    assert_eq!(Tick(3).midpoint(Tick(0)), Tick(1));
+
+    assert_eq!(
+        Microsecond::MAX.midpoint(Microsecond::new(u64::MAX - 2)),
+        Microsecond::new(u64::MAX - 1)
+    );
+    assert_eq!(
+        Microsecond::new(3).midpoint(Microsecond::new(0)),
+        Microsecond::new(1)
+    );
+}
+
+#[test]
+fn ratio_and_scale() {
+    use crate::units::time::Microsecond;
+
+    let traveled = Meter::new(1.5);
+    let total = Meter::new(6.0);
+    assert_eq!(traveled.ratio(total).to_bits(), 0.25f64.to_bits());
+    assert!(traveled.ratio(Meter::ZERO).is_infinite());
+    assert!(Meter::ZERO.ratio(Meter::ZERO).is_nan());
+    assert_eq!(total.scale(0.5), Meter::new(3.0));
+
+    assert_eq!(
+        Microsecond::new(250)
+            .ratio(Microsecond::new(1000))
+            .to_bits(),
+        0.25f64.to_bits()
+    );
+    assert!(Microsecond::new(1).ratio(Microsecond::new(0)).is_infinite());
+    assert_eq!(Microsecond::new(10).scale(2.55), Microsecond::new(25));
+    assert_eq!(Microsecond::new(10).scale(-1.0), Microsecond::new(0));
+}
+
+#[test]
+fn formatting() {
+    use crate::unit;
+    use crate::units::time::Microsecond;
+
+    unit!(Tick: int);
+
+    assert_eq!(format!("{:e}", Meter::new(1234.5)), "1.2345e3 m");
+    assert_eq!(format!("{:.1E}", Meter::new(1234.5)), "1.2E3 m");
+    assert_eq!(format!("{:e}", Microsecond::new(1500)), "1.5e3 us");
+    assert_eq!(format!("{:E}", Tick(-20)), "-2E1");
+
+    assert_eq!(format!("{:?}", Meter::new(1.5)), "Meter(1.5)");
+    assert_eq!(format!("{:.2?}", Meter::new(1.5)), "Meter(1.50)");
+    assert_eq!(format!("{:#?}", Meter::new(1.5)), "Meter(1.5 m)");
+    assert_eq!(format!("{:#.2?}", Meter::new(1.5)), "Meter(1.50 m)");
+    assert_eq!(format!("{:?}", Tick(3)), "Tick(3)");
+    assert_eq!(format!("{:#?}", Tick(3)), "Tick(\n    3,\n)");
+}
+
+#[test]
+fn euclid() {
+    use crate::units::angle::Degree;
+
+    assert_eq!(
+        Degree::new(-90.0).rem_euclid(Degree::new(360.0)),
+        Degree::new(270.0)
+    );
+    assert_eq!(
+        Degree::new(730.0).rem_euclid(Degree::new(360.0)),
+        Degree::new(10.0)
+    );
+    assert_eq!(
+        Degree::new(-90.0).div_euclid(Degree::new(360.0)),
+        Degree::new(-1.0)
+    );
+    assert_eq!(Meter::new(2.0).copysign(Meter::new(-0.1)), Meter::new(-2.0));
+}
+
+#[test]
+fn trig() {
+    use crate::units::angle::{Degree, Radian, Rotation};
+    use approx::assert_relative_eq;
+
+    assert_relative_eq!(Degree::new(30.0).sin(), 0.5, epsilon = 1e-12);
+    assert_relative_eq!(Rotation::new(0.5).cos(), -1.0, epsilon = 1e-12);
+    assert_relative_eq!(Radian::new(0.0).tan(), 0.0);
+    assert_relative_eq!(
+        Degree::atan2(Meter::new(1.0), Meter::new(-1.0)).value(),
+        135.0,
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn powers() {
+    assert_eq!(Meter::new(3.0).hypot(Meter::new(4.0)), Meter::new(5.0));
+    assert_eq!(Meter::new(9.0).sqrt(), Meter::new(3.0));
+    assert_eq!(Meter::new(2.0).powi(3), Meter::new(8.0));
+    assert_eq!(Meter::new(4.0).powf(0.5), Meter::new(2.0));
+}
+
+#[test]
+fn finiteness() {
+    let odometry = Meter::new(1.0) / Meter::new(0.0);
+    assert!(odometry.is_infinite());
+    assert!(!odometry.is_finite());
+    assert!(Meter::NAN.is_nan());
+    assert!(Meter::new(1.0).is_normal());
+    assert_eq!(odometry.finite_or(Meter::ZERO), Meter::ZERO);
+    assert_eq!(Meter::new(2.0).finite_or(Meter::ZERO), Meter::new(2.0));
+    assert_eq!(odometry.unwrap_finite(), None);
+    assert_eq!(Meter::NAN.unwrap_finite(), None);
+    assert_eq!(Meter::new(-2.0).unwrap_finite(), Some(Meter::new(-2.0)));
+}
+
+#[test]
+fn parse_any() {
+    use crate::units::angle::{Degree, Radian};
+    use crate::units::length::Inch;
+    use crate::units::ParseUnitError;
+
+    assert_eq!(Meter::parse_any("12 in"), Ok(Meter::from(Inch::new(12.0))));
+    assert_eq!(Meter::parse_any(" 0.3 m "), Ok(Meter::new(0.3)));
+    assert_eq!(Meter::parse_any("250cm"), Ok(Meter::new(2.5)));
+    assert_eq!(
+        Meter::parse_any("-1e1 ft"),
+        Ok(Meter::from(Foot::new(-10.0)))
+    );
+    assert_eq!(
+        Radian::parse_any("180 deg"),
+        Ok(Radian::from(Degree::new(180.0)))
+    );
+
+    assert_eq!(
+        Meter::parse_any("3 yd"),
+        Err(ParseUnitError::UnknownSymbol(&["m", "in", "ft", "cm"]))
+    );
+    assert_eq!(
+        Meter::parse_any("three ft"),
+        Err(ParseUnitError::InvalidNumber("Foot"))
+    );
+    assert_eq!(
+        Meter::parse_any("3 yd").map_err(|error| error.to_string()),
+        Err("Unknown unit symbol, expected one of: m, in, ft, cm".to_owned())
+    );
+}
+
+#[test]
+fn hashing() {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    let state = RandomState::new();
+    assert_eq!(
+        state.hash_one(Meter::new(0.0)),
+        state.hash_one(Meter::new(-0.0))
+    );
+    assert_eq!(
+        state.hash_one(Meter::NAN),
+        state.hash_one(Meter::new(f64::from_bits(f64::NAN.to_bits() ^ 1)))
+    );
+    assert_eq!(
+        state.hash_one(Meter::NAN),
+        state.hash_one(Meter::new(-f64::NAN))
+    );
+    assert_ne!(
+        state.hash_one(Meter::new(1.0)),
+        state.hash_one(Meter::new(-1.0))
+    );
+
+    let returned = Meter::new(1.0) * -0.0;
+    assert_eq!(returned.value().to_bits(), (-0.0f64).to_bits());
+    assert_eq!(returned, Meter::ZERO);
+    assert_eq!(state.hash_one(returned), state.hash_one(Meter::ZERO));
+}
+
+#[test]
+fn canonical_eq() {
+    use crate::units::CanonEq;
+    use std::collections::HashSet;
+
+    assert_eq!(CanonEq::new(Meter::new(-0.0)), CanonEq::new(Meter::ZERO));
+    assert_eq!(
+        CanonEq::new(Meter::NAN),
+        CanonEq::new(Meter::new(-f64::NAN))
+    );
+    assert_ne!(CanonEq::new(Meter::NAN), CanonEq::new(Meter::ZERO));
+    assert_eq!(
+        CanonEq::new(Meter::new(-0.0)).get().value().to_bits(),
+        0.0f64.to_bits()
+    );
+
+    let mut seen = HashSet::new();
+    assert!(seen.insert(CanonEq::from(Meter::ZERO)));
+    assert!(!seen.insert(CanonEq::from(Meter::new(1.0) * -0.0)));
+    assert!(seen.insert(CanonEq::from(Meter::NAN)));
+    assert!(!seen.insert(CanonEq::from(Meter::new(0.0) / Meter::new(0.0))));
+    assert!(seen.insert(CanonEq::from(Meter::new(1.0))));
+    assert_eq!(seen.len(), 3);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_human_readable() {
+    use crate::units::time::Microsecond;
+
+    let json = serde_json::to_string(&Meter::new(1.5)).expect("serialize");
+    if cfg!(feature = "units-serde-struct") {
+        assert_eq!(json, r#"{"value":1.5,"unit":"m"}"#);
+    } else {
+        assert_eq!(json, "1.5");
+    }
+    assert_eq!(Meter::SYMBOL, Some("m"));
+    assert_eq!(Meter::NAME, "Meter");
+
+    let parse = |json: &str| serde_json::from_str::<Meter>(json);
+    assert_eq!(parse(&json).expect("struct form"), Meter::new(1.5));
+    assert_eq!(parse("1.5").expect("bare number"), Meter::new(1.5));
+    assert_eq!(parse("2").expect("integer"), Meter::new(2.0));
+    assert_eq!(
+        parse(r#"{"unit":"Meter","value":3.0}"#).expect("unit name"),
+        Meter::new(3.0)
+    );
+    assert_eq!(parse(r#"{"value":3.0}"#).expect("no unit"), Meter::new(3.0));
+    assert!(parse(r#"{"value":1.5,"unit":"ft"}"#).is_err());
+    assert!(parse(r#"{"unit":"m"}"#).is_err());
+
+    let micros = serde_json::to_string(&Microsecond::new(7)).expect("serialize");
+    if cfg!(feature = "units-serde-struct") {
+        assert_eq!(micros, r#"{"value":7,"unit":"us"}"#);
+    } else {
+        assert_eq!(micros, "7");
+    }
+    let parse = |json: &str| serde_json::from_str::<Microsecond>(json);
+    assert_eq!(parse(&micros).expect("either form"), Microsecond::new(7));
+    assert_eq!(
+        parse(r#"{"value":7,"unit":"us"}"#).expect("struct form"),
+        Microsecond::new(7)
+    );
+    assert_eq!(parse("2.0").expect("integral float"), Microsecond::new(2));
+    assert!(parse("1.5").is_err());
+    assert!(parse(r#"{"value":1.5}"#).is_err());
+    assert!(parse("-1").is_err());
+}
+
+#[test]
+fn map_and_inner() {
+    use approx::assert_relative_eq;
+
+    let reading = Meter::new(1.23);
+    let snapped = reading.map(|v| (v / 0.05).round() * 0.05);
+    assert_relative_eq!(snapped.value(), 1.25, epsilon = 1e-12);
+    assert_eq!(Meter::new(snapped.into_inner()), snapped);
+    assert_eq!(Meter::new(*Meter::new(2.0).as_ref()), Meter::new(2.0));
+}
+
+#[test]
+fn family_compare() {
+    use crate::units::angle::{Angle, Degree, Radian};
+    use crate::units::length::Distance;
+    use std::cmp::Ordering;
+
+    assert_eq!(
+        Degree::new(90.0).compare(Radian::new(1.0)),
+        Ordering::Greater
+    );
+    assert_eq!(Radian::new(1.0).compare(Degree::new(90.0)), Ordering::Less);
+    assert!(Degree::new(180.0).approx_eq(Radian::new(std::f64::consts::PI), Radian::new(1e-9)));
+    assert!(!Degree::new(180.0).approx_eq(Radian::new(3.0), Radian::new(1e-9)));
+    assert_eq!(Foot::new(1.0).compare(Meter::new(0.3)), Ordering::Greater);
+    assert!(Foot::new(3.28084).approx_eq(Meter::new(1.0), Meter::new(1e-9)));
+}
+
+#[test]
+fn periodic_family() {
+    use crate::units::angle::{Angle, Degree, Radian, Rotation};
+    use approx::assert_relative_eq;
+
+    assert_eq!(Degree::new(180.0).normalize_signed(), Degree::new(-180.0));
+    assert_eq!(Degree::new(-180.0).normalize_signed(), Degree::new(-180.0));
+    assert_eq!(Degree::new(360.0).normalize_unsigned(), Degree::new(0.0));
+    assert_eq!(Degree::new(-1e-17).normalize_unsigned(), Degree::new(0.0));
+    assert_relative_eq!(Degree::new(540.0).normalize_signed().value(), -180.0);
+    assert_relative_eq!(
+        Degree::new(-450.0).normalize_unsigned().value(),
+        270.0,
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        Radian::new(7.0).normalize_signed().value(),
+        7.0 - std::f64::consts::TAU
+    );
+    assert_relative_eq!(Rotation::new(-0.25).normalize_unsigned().value(), 0.75);
+}
+
+#[test]
+fn duration_interop() {
+    use crate::units::time::{Hour, Microsecond, Millisecond};
+    use std::time::Duration;
+
+    assert_eq!(
+        Duration::from(Millisecond::new(20.0)),
+        Duration::from_millis(20)
+    );
+    assert_eq!(
+        Duration::from(Microsecond::new(1_500)),
+        Duration::from_micros(1_500)
+    );
+    assert_eq!(Duration::from(Second::new(-0.5)), Duration::ZERO);
+    assert_eq!(Duration::from(Second::NAN), Duration::ZERO);
+    assert_eq!(Duration::from(Second::new(-0.0)), Duration::ZERO);
+    assert_eq!(Duration::from(Second::INFINITY), Duration::MAX);
+    assert_eq!(Duration::from(Hour::new(1e30)), Duration::MAX);
+    assert_eq!(
+        Duration::from(Microsecond::new(u64::MAX)),
+        Duration::from_micros(u64::MAX)
+    );
+    assert_eq!(Second::from(Duration::from_millis(250)), Second::new(0.25));
+    assert_eq!(
+        Microsecond::from(Duration::from_nanos(2_999)),
+        Microsecond::new(2)
+    );
+}
+
+#[cfg(feature = "proptest")]
+mod strategy {
+    use crate::units::angle::{Degree, Radian};
+    use crate::units::length::{Foot, Meter};
+    use crate::units::strategy;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn sensible_round_trip(degrees in any::<Degree>()) {
+            prop_assert!(degrees.value().is_finite());
+            let radians: Radian = degrees.into();
+            prop_assert!((Degree::from(radians).value() - degrees.value()).abs() < 1e-6);
+        }
+
+        #[test]
+        fn finite_only(meters in strategy::finite::<Meter>()) {
+            prop_assert!(meters.value().is_finite());
+        }
+    }
+
+    crate::assert_unit_conversion_roundtrip!(Degree <-> Radian);
+    crate::assert_unit_conversion_roundtrip!(Foot <-> Meter, epsilon = 1e-12);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn half_backed() {
+    use half::f16;
+    use num::traits::Float;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    crate::unit!(HalfMeter("m"): float16);
+
+    let half = HalfMeter::from(1.5f32);
+    assert_eq!(half.value(), f16::from_f32(1.5));
+    assert_eq!(half + HalfMeter::ONE, HalfMeter::from(2.5f64));
+    assert_eq!(-half * 2u8, HalfMeter::from(-3.0f32));
+    assert_eq!(f32::from(half).to_bits(), 1.5f32.to_bits());
+    assert_eq!(half.into_unit::<Meter>(), Meter::new(1.5));
+    assert_eq!(
+        HalfMeter::from_unit(Meter::new(0.1)).value(),
+        f16::from_f64(0.1)
+    );
+    assert_eq!(
+        HalfMeter::from(3.5f32).rem_euclid(HalfMeter::ONE),
+        HalfMeter::from(0.5f32)
+    );
+    assert_eq!(Float::sqrt(HalfMeter::from(4u8)), HalfMeter::from(2u8));
+
+    assert!(HalfMeter::try_from_f64(1e6).is_err());
+    assert!(HalfMeter::try_new(f16::NAN).is_err());
+    assert_eq!(HalfMeter::try_from_f64(-2.0), Ok(HalfMeter::from(-2i8)));
+
+    let hash = |value: HalfMeter| {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(half), hash(HalfMeter::from(1.5f64)));
+    assert_ne!(hash(half), hash(HalfMeter::ZERO));
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&half).expect("serialize");
+        if cfg!(feature = "units-serde-struct") {
+            assert_eq!(json, r#"{"value":1.5,"unit":"m"}"#);
+        } else {
+            assert_eq!(json, "1.5");
+        }
+        let parse = |json: &str| serde_json::from_str::<HalfMeter>(json);
+        assert_eq!(parse(&json).expect("struct form"), half);
+        assert_eq!(parse("1.5").expect("bare number"), half);
+    }
+}
@@ -1,12 +1,12 @@
-use std::{ops::Neg, time::Duration};
+use core::ops::Neg;
 
-use crate::{unit, unit_conversion, unit_family};
+use crate::{unit, unit_conversion, unit_family, unit_time};
 
-unit!(Hour: float);
-unit!(Minute: float);
-unit!(Second: float);
-unit!(Millisecond: float);
-unit!(Microsecond: uint);
+unit!(Hour("h"): float);
+unit!(Minute("min"): float);
+unit!(Second("s"): float);
+unit!(Millisecond("ms"): float);
+unit!(Microsecond("us"): uint);
 
 unit_conversion!(Second(float) <-> Millisecond(float) ~ second_to_millisecond);
 unit_conversion!(Second(float) <-> Microsecond(uint) ~ second_to_microsecond);
@@ -85,62 +85,8 @@ fn hour_to_microsecond(hour: f64) -> u64 {
     second_to_microsecond(hour_to_second(hour))
 }
 
-impl From<Duration> for Hour {
-    fn from(duration: Duration) -> Self {
-        Self::new(duration.as_secs_f64() / 3600.0)
-    }
-}
-
-impl From<Duration> for Minute {
-    fn from(duration: Duration) -> Self {
-        Self::new(duration.as_secs_f64() / 60.0)
-    }
-}
-
-impl From<Duration> for Second {
-    fn from(duration: Duration) -> Self {
-        Self::new(duration.as_secs_f64())
-    }
-}
-
-impl From<Duration> for Millisecond {
-    fn from(duration: Duration) -> Self {
-        Self::new(duration.as_secs_f64() * 1000.0)
-    }
-}
-
-impl From<Duration> for Microsecond {
-    fn from(duration: Duration) -> Self {
-        Self::new(u64::try_from(duration.as_micros()).unwrap_or(u64::MAX))
-    }
-}
-
-impl From<Hour> for Duration {
-    fn from(hour: Hour) -> Self {
-        Self::from_secs_f64(hour.value() * 3600.0)
-    }
-}
-
-impl From<Minute> for Duration {
-    fn from(minute: Minute) -> Self {
-        Self::from_secs_f64(minute.value() * 60.0)
-    }
-}
-
-impl From<Second> for Duration {
-    fn from(second: Second) -> Self {
-        Self::from_secs_f64(second.value())
-    }
-}
-
-impl From<Millisecond> for Duration {
-    fn from(millisecond: Millisecond) -> Self {
-        Self::from_secs_f64(millisecond.value() / 1000.0)
-    }
-}
-
-impl From<Microsecond> for Duration {
-    fn from(microsecond: Microsecond) -> Self {
-        Self::from_micros(microsecond.value())
-    }
-}
+unit_time!(Hour, factor_to_nanos = 3_600_000_000_000, saturating);
+unit_time!(Minute, factor_to_nanos = 60_000_000_000, saturating);
+unit_time!(Second, factor_to_nanos = 1_000_000_000, saturating);
+unit_time!(Millisecond, factor_to_nanos = 1_000_000, saturating);
+unit_time!(Microsecond, factor_to_nanos = 1_000, saturating);
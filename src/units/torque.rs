@@ -1,10 +1,10 @@
 use crate::{unit, unit_conversion, unit_family};
 
-unit!(NewtonMeter: float);
-unit!(NewtonCentimeter: float);
-unit!(KilogramMeter: float);
-unit!(FootPound: float);
-unit!(InchPound: float);
+unit!(NewtonMeter("N*m"): float);
+unit!(NewtonCentimeter("N*cm"): float);
+unit!(KilogramMeter("kg*m"): float);
+unit!(FootPound("ft*lb"): float);
+unit!(InchPound("in*lb"): float);
 
 unit_conversion!(NewtonMeter(float) <-> NewtonCentimeter(float) ~ newton_meter_to_newton_centimeter);
 unit_conversion!(NewtonMeter(float) <-> KilogramMeter(float) ~ newton_meter_to_kilogram_meter);
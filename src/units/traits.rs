@@ -0,0 +1,36 @@
+use core::fmt::{Debug, Display};
+
+/// A trait implemented by every unit defined with [`unit!`](crate::unit).
+///
+/// Unlike the traits generated by [`unit_family!`](crate::unit_family),
+/// which group units that can be converted between each other,
+/// this trait spans all units and allows writing code generic over any unit.
+///
+/// # Example
+/// ```
+/// use frclib_core::units::{length::Meter, time::Second, Unit};
+///
+/// fn describe<U: Unit>(unit: U) -> String {
+///     format!("{} {}", unit.value(), U::SYMBOL.unwrap_or(U::NAME))
+/// }
+///
+/// assert_eq!(describe(Meter::new(1.5)), "1.5 m");
+/// assert_eq!(describe(Second::new(2.0)), "2 s");
+/// ```
+pub trait Unit: Copy + PartialOrd + Default + Debug + Display {
+    /// The primitive type the unit wraps.
+    type Backing: Copy + PartialOrd + Default + Debug + Display;
+
+    /// The name of the unit.
+    const NAME: &'static str;
+    /// The symbol of the unit, if one was given.
+    const SYMBOL: Option<&'static str>;
+
+    /// Creates a new instance of the unit with the given value.
+    #[must_use]
+    fn new(value: Self::Backing) -> Self;
+
+    /// Returns the inner value.
+    #[must_use]
+    fn value(self) -> Self::Backing;
+}
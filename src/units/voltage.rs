@@ -0,0 +1,8 @@
+use crate::{unit, unit_conversion, unit_family};
+
+unit!(Volt("V") | Volts: float);
+unit!(Millivolt("mV") | Millivolts: float);
+
+unit_conversion!(Volt(float) <-> Millivolt(float) ~ ratio 1000.0);
+
+unit_family!(Voltage(Volt): Millivolt);
@@ -12,7 +12,7 @@ mod test;
 mod trait_impls;
 mod traits;
 
-use crate::structure::{FrcStructure, FrcStructureBytes};
+use crate::structure::{try_unpack_spec, FrcStructure, FrcStructureBytes};
 pub use error::FrcValueCastError;
 pub use traits::IntoFrcValue;
 
@@ -251,33 +251,42 @@ impl FrcValue {
 impl FrcValue {
     /// Converts the given [``FrcStructure``](crate::structure::FrcStructure) into a [``FrcValue``](FrcValue)
     pub fn from_struct<T: FrcStructure>(value: &T) -> Self {
-        let mut buffer = Vec::with_capacity(T::SIZE);
-        value.pack(&mut buffer);
-        Self::Struct(Box::new(FrcStructureBytes::from_parts(
-            &T::DESCRIPTION,
-            1,
-            buffer.into_boxed_slice(),
+        Self::Struct(Box::new(FrcStructureBytes::pack_many(
+            std::slice::from_ref(value),
         )))
     }
 
     /// # Errors
     /// Returns an error if the value is not a struct or the struct is not the correct type
     pub fn try_into_struct<T: FrcStructure>(self) -> Result<T, FrcValueCastError> {
+        self.try_as_struct()
+    }
+
+    /// Unpacks a [``FrcStructure``](crate::structure::FrcStructure) from a borrowed [``FrcValue``](FrcValue)
+    ///
+    /// # Errors
+    /// Returns an error if the value is not a struct or the struct is not the correct type
+    pub fn try_as_struct<T: FrcStructure>(&self) -> Result<T, FrcValueCastError> {
         let frc_type = self.get_type();
         match self {
-            Self::Struct(bytes) => {
-                let buffer = bytes.data;
-                if buffer.len() == T::SIZE {
-                    let mut cursor = Cursor::new(buffer.as_ref());
-                    Ok(T::unpack(&mut cursor))
-                } else {
-                    Err(FrcValueCastError::InvalidCastTo(
+            Self::Struct(bytes) if bytes.desc.type_str != T::TYPE => Err(
+                FrcValueCastError::InvalidCastTo(frc_type, T::TYPE, CastErrorReason::Type),
+            ),
+            Self::Struct(bytes) if bytes.data.len() == T::SIZE => {
+                let mut cursor = Cursor::new(bytes.data.as_ref());
+                try_unpack_spec::<T>(&mut cursor).map_err(|_| {
+                    FrcValueCastError::InvalidCastTo(
                         frc_type,
                         T::TYPE,
                         CastErrorReason::Deserialization,
-                    ))
-                }
+                    )
+                })
             }
+            Self::Struct(_) => Err(FrcValueCastError::InvalidCastTo(
+                frc_type,
+                T::TYPE,
+                CastErrorReason::Deserialization,
+            )),
             _ => Err(FrcValueCastError::InvalidCastTo(
                 frc_type,
                 T::TYPE,
@@ -288,15 +297,7 @@ impl FrcValue {
 
     /// Converts the given [``FrcStructure``](crate::structure::FrcStructure) slice/array into a [``FrcValue``](FrcValue)
     pub fn from_struct_array<T: FrcStructure>(values: &[T]) -> Self {
-        let mut buffer = Vec::with_capacity(T::SIZE * values.len());
-        for value in values {
-            value.pack(&mut buffer);
-        }
-        Self::StructArray(Box::new(FrcStructureBytes::from_parts(
-            &T::DESCRIPTION,
-            values.len(),
-            buffer.into_boxed_slice(),
-        )))
+        Self::StructArray(Box::new(FrcStructureBytes::pack_many(values)))
     }
 
     /// # Errors
@@ -304,13 +305,22 @@ impl FrcValue {
     pub fn try_into_struct_array<T: FrcStructure>(self) -> Result<Vec<T>, FrcValueCastError> {
         let frc_type = self.get_type();
         match self {
+            Self::StructArray(bytes) if bytes.desc.type_str != T::TYPE => Err(
+                FrcValueCastError::InvalidCastTo(frc_type, T::TYPE, CastErrorReason::Type),
+            ),
             Self::StructArray(bytes) => {
                 let buffer = bytes.data;
                 if buffer.len() % T::SIZE == 0 {
                     let mut cursor = Cursor::new(buffer.as_ref());
                     let mut values = Vec::with_capacity(buffer.len() / T::SIZE);
                     while cursor.position() < buffer.len() as u64 {
-                        values.push(T::unpack(&mut cursor));
+                        values.push(try_unpack_spec::<T>(&mut cursor).map_err(|_| {
+                            FrcValueCastError::InvalidCastTo(
+                                frc_type,
+                                T::TYPE,
+                                CastErrorReason::Deserialization,
+                            )
+                        })?);
                     }
                     Ok(values)
                 } else {